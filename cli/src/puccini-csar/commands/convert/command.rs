@@ -0,0 +1,68 @@
+use super::super::create::*;
+
+use {clap::*, clap_num::*, puccini_csar::creator::*, std::path::*};
+
+//
+// Convert
+//
+
+/// Convert subcommand.
+#[derive(Args)]
+pub struct Convert {
+    /// path to source CSAR file
+    pub file: PathBuf,
+
+    /// path to target CSAR file
+    pub output: Option<PathBuf>,
+
+    /// source archive format;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// target archive format;
+    /// when absent will attempt to select according to target file extension
+    /// or default to "tarball" when target file not specified
+    #[arg(long = "format", short = 'f', verbatim_doc_comment, value_enum)]
+    pub format: Option<CsarFormat>,
+
+    /// compression level from 1 to 10;
+    /// 1 = least compression, fastest;
+    /// 10 = most compression, slowest;
+    /// leave empty to use format's default
+    #[arg(long = "compression", short = 'C', value_parser = compression_level_parser, verbatim_doc_comment)]
+    pub compression_level: Option<usize>,
+
+    /// enable reproducible mode;
+    /// all entries will have the same timestamp (from SOURCE_DATE_EPOCH
+    /// or 1980-01-01), normalized permissions, and no owner
+    #[arg(long = "reproducible", short = 'r', verbatim_doc_comment)]
+    pub reproducible: bool,
+
+    /// enable dry run;
+    /// do everything except write the CSAR
+    #[arg(long = "dry-run", short = 'd', verbatim_doc_comment)]
+    pub dry_run: bool,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
+
+impl Convert {
+    pub fn source_csar_format(&self) -> Option<Format> {
+        self.csar_format.as_ref().map(|format| format.to_puccini())
+    }
+
+    pub fn csar_format(&self) -> Option<Format> {
+        self.format.as_ref().map(|format| format.to_puccini())
+    }
+
+    pub fn compression_level(&self) -> Option<CompressionLevel> {
+        self.compression_level.map(CompressionLevel::new_unchecked)
+    }
+}
+
+fn compression_level_parser(representation: &str) -> Result<usize, String> {
+    number_range(representation, 1, 10)
+}
@@ -0,0 +1,5 @@
+mod command;
+mod print;
+mod run;
+
+pub use command::*;
@@ -0,0 +1,42 @@
+use super::command::*;
+
+use {
+    anstream::{eprint, eprintln},
+    depiction::*,
+    indicatif::*,
+    problemo::*,
+    puccini_csar::creator::*,
+};
+
+impl Convert {
+    pub fn print_introduction(&self) {
+        let prefix = self.prefix();
+
+        eprintln!("{}Reading from {:?}", prefix, DEFAULT_THEME.string(self.file.display()));
+
+        let output = match &self.output {
+            Some(archive_path) => format!("{:?}", DEFAULT_THEME.string(archive_path.display())),
+            None => format!("{}", DEFAULT_THEME.name("stdout")),
+        };
+
+        eprintln!("{}Writing to {}", prefix, output);
+    }
+
+    pub fn print_summary(&self, created: CreatedCsar) -> Result<(), Problem> {
+        eprint!("{}Wrote {}", self.prefix(), DEFAULT_THEME.name(created.format));
+
+        if let Some(file) = &self.output
+            && !self.dry_run
+        {
+            eprint!(" {}", DEFAULT_THEME.number(HumanBytes(file.metadata()?.len())));
+        }
+
+        eprintln!();
+
+        Ok(())
+    }
+
+    fn prefix(&self) -> String {
+        if self.dry_run { format!("{}", DEFAULT_THEME.meta("(dry run) ")) } else { Default::default() }
+    }
+}
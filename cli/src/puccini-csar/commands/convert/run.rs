@@ -0,0 +1,72 @@
+use super::{
+    super::{create::*, root::*},
+    command::*,
+};
+
+use {
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::{creator::*, reader::*},
+};
+
+impl Convert {
+    /// Run convert subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let mut problems = Problems::default();
+
+        if !root.quiet {
+            self.print_introduction();
+        }
+
+        let reader = CsarReader::open(&self.file, self.source_csar_format())?;
+
+        let mut creator = CsarCreator::new(
+            self.csar_format(),
+            self.compression_level(),
+            None,
+            None,
+            Default::default(),
+            None,
+            if root.quiet {
+                None
+            } else {
+                Some(Box::new(ReadTrackerChain::new(vec![
+                    Create::con_emu_osc_progress_state(),
+                    // indicatif second so it can clean up the line before OSC code is sent
+                    Create::indicatif_progress_bar(root),
+                ])))
+            },
+        );
+
+        if self.reproducible {
+            creator.reproducible = Some(Reproducible::from_environment()?);
+        }
+
+        let created = creator.convert_csar(
+            &reader,
+            self.output.as_ref().map(|path| path.as_ref()),
+            self.dry_run,
+            &mut problems,
+        )?;
+
+        match problems.check() {
+            Ok(_) => {
+                if !root.quiet
+                    && let Some(created) = created
+                {
+                    self.print_summary(created)?;
+                }
+
+                Ok(())
+            }
+
+            Err(problems) => {
+                if !root.quiet {
+                    problems.annotated_depiction().eprint_default_depiction();
+                }
+
+                Err(ExitError::failure())
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use super::format::*;
 
-use {clap::*, clap_num::*, puccini_csar::creator::*, std::path::*};
+use {
+    clap::*,
+    clap_num::*,
+    puccini_csar::{creator::*, manifest::*},
+    std::path::*,
+};
 
 //
 // Create
@@ -40,10 +45,62 @@ pub struct Create {
     #[arg(long = "other-definitions")]
     pub other_definitions: Vec<String>,
 
+    /// include only files matching this pattern (gitignore syntax);
+    /// can be used more than once
+    #[arg(long = "include", short = 'i', verbatim_doc_comment)]
+    pub include: Vec<String>,
+
+    /// exclude files matching this pattern (gitignore syntax);
+    /// can be used more than once
+    #[arg(long = "exclude", short = 'x', verbatim_doc_comment)]
+    pub exclude: Vec<String>,
+
+    /// whether to use the .csarignore file (gitignore syntax)
+    /// in the root of the source directory
+    #[arg(long = "ignore-file", action = clap::ArgAction::Set, default_value_t = true, verbatim_doc_comment)]
+    pub ignore_file: bool,
+
+    /// only include the TOSCA definitions, their relative imports, and their artifact files,
+    /// instead of all the files in the source directory;
+    /// remote imports will not be vendored
+    #[arg(long = "follow-imports", short = 'F', verbatim_doc_comment)]
+    pub follow_imports: bool,
+
+    /// enable reproducible mode;
+    /// all entries will have the same timestamp (from SOURCE_DATE_EPOCH
+    /// or 1980-01-01), normalized permissions, and no owner
+    #[arg(long = "reproducible", short = 'r', verbatim_doc_comment)]
+    pub reproducible: bool,
+
     /// maximum number of columns for TOSCA.meta file
     #[arg(long = "max-columns", short = 'c', default_value_t = 80)]
     pub max_columns: usize,
 
+    /// enable ETSI SOL004 mode;
+    /// a manifest of file digests with metadata will be added
+    /// and referenced from TOSCA.meta
+    #[arg(long = "sol004", verbatim_doc_comment)]
+    pub sol004: bool,
+
+    /// digest algorithm for the ETSI SOL004 manifest
+    #[arg(long = "sol004-algorithm", default_value_t = ManifestAlgorithm::SHA256, value_enum)]
+    pub sol004_algorithm: ManifestAlgorithm,
+
+    /// add a metadata entry to the ETSI SOL004 manifest in the form "key=value";
+    /// the metadata must have all the keys required for a VNF, PNF, or NS package
+    #[arg(long = "sol004-metadata", value_parser = metadata_parser, verbatim_doc_comment)]
+    pub sol004_metadata: Vec<(String, String)>,
+
+    /// path to private key file (PKCS#8 PEM, Ed25519) for signing;
+    /// a manifest of file digests and its signature will be added
+    #[arg(long = "sign-key", verbatim_doc_comment)]
+    pub sign_key: Option<PathBuf>,
+
+    /// path to certificate file (minisign public key or public key PEM) to add to a signed CSAR;
+    /// when absent will be derived from the private key
+    #[arg(long = "sign-cert", requires = "sign_key", verbatim_doc_comment)]
+    pub sign_cert: Option<PathBuf>,
+
     /// enable dry run;
     /// do everything except write the CSAR
     #[arg(long = "dry-run", short = 'd', verbatim_doc_comment)]
@@ -62,6 +119,18 @@ impl Create {
     pub fn compression_level(&self) -> Option<CompressionLevel> {
         self.compression_level.map(CompressionLevel::new_unchecked)
     }
+
+    pub fn sol004(&self) -> Option<Sol004> {
+        self.sol004
+            .then(|| Sol004::new(self.sol004_algorithm.to_puccini(), self.sol004_metadata.iter().cloned().collect()))
+    }
+}
+
+fn metadata_parser(representation: &str) -> Result<(String, String), String> {
+    match representation.split_once('=') {
+        Some((key, value)) => Ok((key.trim().into(), value.trim().into())),
+        None => Err("must be in the form \"key=value\"".into()),
+    }
 }
 
 fn compression_level_parser(representation: &str) -> Result<usize, String> {
@@ -36,3 +36,32 @@ impl ToString for CsarFormat {
         self.to_possible_value().expect("to_possible_value").get_name().into()
     }
 }
+
+//
+// ManifestAlgorithm
+//
+
+#[derive(Clone, ValueEnum)]
+pub enum ManifestAlgorithm {
+    #[value(name = "sha-256")]
+    SHA256,
+
+    #[value(name = "sha-512")]
+    SHA512,
+}
+
+impl ManifestAlgorithm {
+    /// To Puccini digest algorithm.
+    pub fn to_puccini(&self) -> puccini_csar::manifest::DigestAlgorithm {
+        match self {
+            ManifestAlgorithm::SHA256 => puccini_csar::manifest::DigestAlgorithm::SHA256,
+            ManifestAlgorithm::SHA512 => puccini_csar::manifest::DigestAlgorithm::SHA512,
+        }
+    }
+}
+
+impl ToString for ManifestAlgorithm {
+    fn to_string(&self) -> String {
+        self.to_possible_value().expect("to_possible_value").get_name().into()
+    }
+}
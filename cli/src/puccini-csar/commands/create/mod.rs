@@ -3,5 +3,6 @@ mod format;
 mod print;
 mod progress;
 mod run;
+mod tosca;
 
-pub use command::*;
+pub use {command::*, format::*};
@@ -4,7 +4,7 @@ use {
     compris::depict::*,
     depiction::*,
     problemo::{common::*, *},
-    puccini_csar::{creator::*, *},
+    puccini_csar::{creator::*, signature::*, *},
 };
 
 impl Create {
@@ -16,7 +16,37 @@ impl Create {
             self.print_introduction();
         }
 
-        let created = self.creator(root).create_from_directory(
+        let mut creator = self.creator(root);
+        creator.include = self.include.clone();
+        creator.exclude = self.exclude.clone();
+        creator.ignore_file = self.ignore_file;
+
+        if self.follow_imports {
+            let mut tosca_problems = Problems::default();
+            let files = self.tosca_files(&mut tosca_problems)?;
+
+            if let Err(tosca_problems) = tosca_problems.check() {
+                if !root.quiet {
+                    tosca_problems.annotated_depiction().eprint_default_depiction();
+                }
+
+                return Err(ExitError::failure());
+            }
+
+            creator.files = Some(files);
+        }
+
+        creator.sol004 = self.sol004();
+
+        if self.reproducible {
+            creator.reproducible = Some(Reproducible::from_environment()?);
+        }
+
+        if let Some(sign_key) = &self.sign_key {
+            creator.signer = Some(CsarSigner::read(sign_key, self.sign_cert.as_ref().map(|path| path.as_ref()))?);
+        }
+
+        let created = creator.create_from_directory(
             self.file.as_ref().map(|path| path.as_ref()),
             &self.directory,
             false,
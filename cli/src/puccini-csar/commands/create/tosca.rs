@@ -0,0 +1,73 @@
+use super::{
+    super::{definitions::*, inspect::*},
+    command::*,
+};
+
+use {
+    problemo::*,
+    puccini_csar::tosca_meta::*,
+    puccini_tosca::grammar::*,
+    std::{collections::*, path::*},
+};
+
+impl Create {
+    /// Files in the source directory that are reachable from the TOSCA definitions.
+    ///
+    /// These are the entry definitions, the other definitions, their transitive relative imports,
+    /// and their artifact files.
+    pub fn tosca_files(&self, problems: &mut Problems) -> Result<BTreeSet<PathBuf>, Problem> {
+        let tosca_meta = ToscaMeta::from_directory(&self.directory, problems)?;
+
+        let entry_definitions = match &self.entry_definitions {
+            Some(entry_definitions) => entry_definitions.clone(),
+
+            None => match tosca_meta.as_ref().and_then(|tosca_meta| tosca_meta.entry_definitions.clone()) {
+                Some(entry_definitions) => entry_definitions,
+
+                None => {
+                    let entry_definitions = entry_definitions_in_directory(&self.directory)?;
+                    let entry_definitions =
+                        entry_definitions.strip_prefix(&self.directory).unwrap_or(&entry_definitions);
+                    entry_definitions.display().to_string()
+                }
+            },
+        };
+
+        let mut definitions = vec![entry_definitions.as_str()];
+        definitions.extend(self.other_definitions.iter().map(|definitions| definitions.as_str()));
+        if let Some(tosca_meta) = &tosca_meta {
+            definitions.extend(tosca_meta.other_definitions.iter().map(|definitions| definitions.as_str()));
+        }
+
+        let url_context = Inspect::url_context()?;
+        let directory_url = url_context.url_or_file_path(&self.directory.display().to_string())?.to_string();
+        let directory_url = directory_url.trim_end_matches('/');
+
+        let exists = |path: &Path| self.directory.join(path).is_file();
+
+        let definitions = Definitions::load(
+            &definitions,
+            |path| SourceID::URL(format!("{}/{}", directory_url, path).into()),
+            exists,
+            &url_context,
+            problems,
+        )?;
+
+        for import in &definitions.remote_imports {
+            tracing::warn!("import is not vendored: {}", import);
+        }
+
+        for profile in &definitions.profile_imports {
+            tracing::warn!("profile import is not vendored: {}", profile);
+        }
+
+        let mut files: BTreeSet<_> = definitions.source_paths.values().cloned().collect();
+        files.extend(definitions.artifact_files(exists, problems)?);
+
+        for file in &files {
+            tracing::debug!("reachable from TOSCA definitions: {:?}", file.display());
+        }
+
+        Ok(files)
+    }
+}
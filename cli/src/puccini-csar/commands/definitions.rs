@@ -0,0 +1,193 @@
+use super::inspect::*;
+
+use {
+    compris::annotate::*,
+    problemo::{common::*, *},
+    puccini_csar::{reader::*, tosca_meta::*, url, *},
+    puccini_tosca::{dialect::tosca_2_0, grammar::*},
+    read_url::*,
+    std::{cell::*, collections::*, path::*},
+};
+
+//
+// Definitions
+//
+
+/// TOSCA definitions and their relative imports.
+///
+/// Paths are relative to the root of the CSAR (or the source directory).
+pub struct Definitions {
+    /// Catalog.
+    pub catalog: Catalog,
+
+    /// Paths of the loaded sources.
+    pub source_paths: BTreeMap<SourceID, PathBuf>,
+
+    /// Imports that are not relative.
+    pub remote_imports: BTreeSet<String>,
+
+    /// Profile imports that are not built in, which are looked for in the profile paths.
+    pub profile_imports: BTreeSet<String>,
+}
+
+impl Definitions {
+    /// Load the definitions and follow their relative imports.
+    ///
+    /// The source ID function converts a path to the source ID to load. The exists function
+    /// checks if there is a file at a path.
+    pub fn load<SourceIdT, ExistsT>(
+        definitions: &[&str],
+        source_id: SourceIdT,
+        exists: ExistsT,
+        url_context: &UrlContextRef,
+        problems: &mut Problems,
+    ) -> Result<Self, Problem>
+    where
+        SourceIdT: Fn(&str) -> SourceID,
+        ExistsT: Fn(&Path) -> bool,
+    {
+        let mut catalog = Catalog::default();
+        tosca_2_0::Dialect::add_to_catalog::<WithAnnotations>(&mut catalog)?;
+
+        let mut source_paths = BTreeMap::<SourceID, PathBuf>::default();
+        let mut remote_imports = BTreeSet::default();
+        let mut profile_imports = BTreeSet::default();
+
+        for definitions in definitions {
+            let path = PathBuf::from(definitions);
+            if exists(&path) {
+                let source_id = source_id(definitions);
+                catalog.load_source_with_annotations(&source_id, url_context, problems)?;
+                source_paths.insert(source_id, path);
+            }
+        }
+
+        // Follow the relative imports
+        let mut queue: VecDeque<_> = source_paths.keys().cloned().collect();
+        while let Some(source_id) = queue.pop_front() {
+            let (Some(source_path), Ok(source)) = (source_paths.get(&source_id), catalog.source(&source_id)) else {
+                continue;
+            };
+
+            let directory = source_path.parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+
+            let mut imports = Vec::default();
+            for dependency_source_id in source.dependencies.keys() {
+                match dependency_source_id {
+                    SourceID::URL(import) => match relative_path(&directory, import, &exists) {
+                        Ok(Some(path)) => imports.push((dependency_source_id.clone(), path)),
+                        Ok(None) => {
+                            remote_imports.insert(import.to_string());
+                        }
+                        Err(problem) => problems.give(problem)?,
+                    },
+
+                    SourceID::Profile(profile) => {
+                        if catalog.profiles.get(profile).is_none() {
+                            profile_imports.insert(profile.to_string());
+                        }
+                    }
+
+                    _ => {}
+                }
+            }
+
+            for (import_source_id, path) in imports {
+                if !source_paths.contains_key(&import_source_id) {
+                    source_paths.insert(import_source_id.clone(), path);
+                    queue.push_back(import_source_id);
+                }
+            }
+        }
+
+        Ok(Self { catalog, source_paths, remote_imports, profile_imports })
+    }
+
+    /// Load the definitions in a CSAR file and follow their relative imports.
+    ///
+    /// The entries are those returned by [entries](CsarReader::entries).
+    pub fn load_from_csar(
+        file: &Path,
+        reader: &CsarReader,
+        entries: &[CsarEntry],
+        tosca_meta: &ToscaMeta,
+        problems: &mut Problems,
+    ) -> Result<Self, Problem> {
+        let url_context = Inspect::url_context()?;
+        let archive_url = url_context.url_or_file_path(&file.display().to_string())?.to_string();
+        let url_format = match reader.format {
+            Format::ZIP => url::Format::ZIP,
+            _ => url::Format::Tarball,
+        };
+
+        let definitions: Vec<_> = tosca_meta
+            .entry_definitions
+            .iter()
+            .chain(tosca_meta.other_definitions.iter())
+            .map(|definitions| definitions.as_str())
+            .collect();
+
+        Self::load(
+            &definitions,
+            |path| SourceID::URL(url_format.with_scheme(&archive_url, path).into()),
+            |path| has_file(entries, path),
+            &url_context,
+            problems,
+        )
+    }
+
+    /// Relative artifact files.
+    ///
+    /// Artifact files that do not exist are given as problems.
+    pub fn artifact_files<ExistsT>(&self, exists: ExistsT, problems: &mut Problems) -> Result<Vec<PathBuf>, Problem>
+    where
+        ExistsT: Fn(&Path) -> bool,
+    {
+        let artifact_files = RefCell::new(Vec::default());
+
+        for (source_id, source_path) in &self.source_paths {
+            if let Ok(source) = self.catalog.source(source_id) {
+                let directory = source_path.parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+                tosca_2_0::Dialect::check_artifact_files::<WithAnnotations, _, _>(
+                    source,
+                    |file| match relative_path(&directory, file, &exists) {
+                        Ok(path) => {
+                            if let Some(path) = path {
+                                artifact_files.borrow_mut().push(path);
+                            }
+                            None
+                        }
+
+                        Err(problem) => Some(problem),
+                    },
+                    problems,
+                )?;
+            }
+        }
+
+        Ok(artifact_files.into_inner())
+    }
+}
+
+/// Relative path of a reference from a TOSCA file in a directory.
+///
+/// Returns [None] for URLs, which are not relative.
+pub fn relative_path<ExistsT>(directory: &Path, reference: &str, exists: ExistsT) -> Result<Option<PathBuf>, Problem>
+where
+    ExistsT: Fn(&Path) -> bool,
+{
+    if has_url_scheme(reference) {
+        return Ok(None);
+    }
+
+    if reference.starts_with('/') {
+        return Err(InvalidError::as_problem(format!("absolute path: {:?}", reference)).via(CsarError));
+    }
+
+    let path = safe_entry_path(&directory.join(reference))?;
+    if !exists(&path) {
+        return Err(NotFoundError::default_as_problem().with(path).via(CsarError));
+    }
+
+    Ok(Some(path))
+}
@@ -0,0 +1,52 @@
+use super::super::{create::*, inspect::*};
+
+use {clap::*, std::path::*};
+
+//
+// Diff
+//
+
+/// Diff subcommand.
+#[derive(Args)]
+pub struct Diff {
+    /// path to old CSAR file
+    pub old_file: PathBuf,
+
+    /// path to new CSAR file
+    pub new_file: PathBuf,
+
+    /// archive format of both CSARs;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// also compare the TOSCA definitions;
+    /// reports added, removed, and changed types and templates
+    #[arg(long = "semantic", short = 's', verbatim_doc_comment)]
+    pub semantic: bool,
+
+    /// output file path;
+    /// when absent will write to stdout
+    #[arg(long = "output-file", short = 'o', verbatim_doc_comment)]
+    pub output_file: Option<PathBuf>,
+
+    /// output format;
+    /// when absent will try to use the output file extension,
+    /// or default to "text" when output file not specified
+    #[arg(long = "format", short = 'f', value_enum, verbatim_doc_comment)]
+    pub output_format: Option<OutputFormat>,
+
+    /// plain output;
+    /// avoid whitespace and colors
+    #[arg(long = "plain", short = 'p', verbatim_doc_comment)]
+    pub output_plain: bool,
+
+    /// encode output to Base64;
+    /// for "cbor" and "messagepack" formats only
+    #[arg(long = "base64", verbatim_doc_comment)]
+    pub output_base64: bool,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
@@ -0,0 +1,6 @@
+mod command;
+mod run;
+mod semantic;
+mod text;
+
+pub use command::*;
@@ -0,0 +1,74 @@
+use super::{
+    super::{inspect::*, root::*},
+    command::*,
+};
+
+use {
+    compris::{annotate::*, normal::*, ser::*},
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::reader::*,
+    std::{
+        fs::*,
+        io::{self, Write},
+    },
+};
+
+impl Diff {
+    /// Run diff subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let mut problems = Problems::default();
+
+        let csar_format = self.csar_format.as_ref().map(|format| format.to_puccini());
+        let old_reader = CsarReader::open(&self.old_file, csar_format)?;
+        let new_reader = CsarReader::open(&self.new_file, csar_format)?;
+
+        let diff = old_reader.diff(&new_reader, &mut problems)?;
+
+        let entities =
+            if self.semantic { Some(self.diff_entities(&old_reader, &new_reader, &mut problems)?) } else { None };
+
+        if let Err(problems) = problems.check() {
+            if !root.quiet {
+                problems.annotated_depiction().eprint_default_depiction();
+            }
+
+            return Err(ExitError::failure());
+        }
+
+        let text = match &self.output_format {
+            Some(output_format) => matches!(output_format, OutputFormat::Text),
+            None => self.output_file.is_none(),
+        };
+
+        if text {
+            let text = self.text(&diff, entities.as_ref());
+            if let Some(output_file) = &self.output_file {
+                let mut file = io::BufWriter::new(File::create(output_file)?);
+                file.write_all(text.as_bytes())?;
+            } else if !root.quiet {
+                print!("{}", text);
+            }
+        } else {
+            let format = self.output_format.as_ref().and_then(|format| format.to_compris());
+
+            let mut map = Map::<WithoutAnnotations>::default();
+            map.into_insert("csar", diff);
+            if let Some(entities) = entities {
+                let entities: List<WithoutAnnotations> = entities.into_iter().map(|change| change.into()).collect();
+                map.into_insert("definitions", entities);
+            }
+            let variant: Variant<WithoutAnnotations> = map.into();
+
+            RepresentationWriter::new(format, !self.output_plain, self.output_base64).write_to_file_or_stdout(
+                &variant,
+                root.quiet,
+                false,
+                true,
+                self.output_file.as_ref(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
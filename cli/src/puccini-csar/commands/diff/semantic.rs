@@ -0,0 +1,108 @@
+use super::{super::definitions::*, command::*};
+
+use {
+    compris::normal::*,
+    depiction::*,
+    problemo::*,
+    puccini_csar::reader::*,
+    std::{collections::*, path::*},
+};
+
+//
+// EntityChange
+//
+
+/// Change of a TOSCA entity (type or template).
+#[derive(Clone, Debug)]
+pub struct EntityChange {
+    /// Path of the definitions file in the CSAR.
+    pub source: PathBuf,
+
+    /// Entity kind.
+    pub kind: String,
+
+    /// Entity name.
+    pub name: String,
+
+    /// Change type.
+    pub change_type: ChangeType,
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for EntityChange
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("change", self.change_type.as_str());
+        map.into_insert("source", self.source.display().to_string());
+        map.into_insert("kind", self.kind);
+        map.into_insert("name", self.name);
+
+        map.into()
+    }
+}
+
+// (Source path, entity kind, entity name)
+type EntityKey = (PathBuf, String, String);
+
+impl Diff {
+    /// Compare the TOSCA entities in the definitions of both CSARs.
+    ///
+    /// Entities are compared by their depiction, which does not include their annotations.
+    pub fn diff_entities(
+        &self,
+        old_reader: &CsarReader,
+        new_reader: &CsarReader,
+        problems: &mut Problems,
+    ) -> Result<Vec<EntityChange>, Problem> {
+        let mut old_entities = Self::entities(&self.old_file, old_reader, problems)?;
+        let mut new_entities = Self::entities(&self.new_file, new_reader, problems)?;
+
+        let keys: BTreeSet<_> = old_entities.keys().chain(new_entities.keys()).cloned().collect();
+
+        let mut changes = Vec::default();
+        for key in keys {
+            let old = old_entities.remove(&key);
+            let new = new_entities.remove(&key);
+            if old != new {
+                let (source, kind, name) = key;
+                changes.push(EntityChange { source, kind, name, change_type: ChangeType::from_options(&old, &new) });
+            }
+        }
+
+        Ok(changes)
+    }
+
+    fn entities(
+        file: &Path,
+        reader: &CsarReader,
+        problems: &mut Problems,
+    ) -> Result<BTreeMap<EntityKey, String>, Problem> {
+        let mut entities = BTreeMap::default();
+
+        // TOSCA.meta problems are already reported by the CSAR diff
+        let entries = reader.entries()?;
+        let Ok(Some((_, tosca_meta))) = reader.tosca_meta(&entries, &mut Problems::default()) else {
+            return Ok(entities);
+        };
+
+        let definitions = Definitions::load_from_csar(file, reader, &entries, &tosca_meta, problems)?;
+
+        let context = DepictionContext::default();
+        for (source_id, source_path) in &definitions.source_paths {
+            let Ok(source) = definitions.catalog.source(source_id) else {
+                continue;
+            };
+
+            let entity_kinds = definitions.catalog.dialect_entity_kinds(&source.dialect_id)?;
+            for (key, entity) in &source.entities {
+                let kind = entity_kinds.represent(key.entity_kind).to_string();
+                entities.insert((source_path.clone(), kind, key.inner.to_string()), entity.to_depiction(&context)?);
+            }
+        }
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,83 @@
+use super::{command::*, semantic::*};
+
+use puccini_csar::reader::*;
+
+impl Diff {
+    /// Differences as text.
+    ///
+    /// Lines start with "+" for added, "-" for removed, and "~" for changed.
+    pub fn text(&self, diff: &CsarDiff, entities: Option<&Vec<EntityChange>>) -> String {
+        let mut text = String::default();
+
+        if !diff.tosca_meta.is_empty() {
+            text += "TOSCA.meta:\n";
+            for change in &diff.tosca_meta {
+                text += &format!("{} ", marker(change.change_type()));
+                if change.block != 0 {
+                    text += &format!("[block {}] ", change.block);
+                }
+                text += &change.keyname;
+
+                match (&change.old, &change.new) {
+                    (Some(old), Some(new)) => text += &format!(": {:?} -> {:?}", old, new),
+                    (Some(value), None) | (None, Some(value)) => text += &format!(": {:?}", value),
+                    (None, None) => {}
+                }
+
+                text += "\n";
+            }
+        }
+
+        if !diff.entries.is_empty() {
+            text += "Entries:\n";
+            for change in &diff.entries {
+                text += &format!("{} {}", marker(change.change_type()), change.path.display());
+
+                match (&change.old, &change.new) {
+                    (Some(old), Some(new)) => {
+                        if old.entry_type != new.entry_type {
+                            text += &format!(" ({} -> {})", old.entry_type, new.entry_type);
+                        } else if old.size != new.size {
+                            text += &format!(" ({} -> {} bytes)", old.size, new.size);
+                        } else {
+                            text += " (content)";
+                        }
+                    }
+
+                    (Some(summary), None) | (None, Some(summary)) => {
+                        text += &format!(" ({} {} bytes)", summary.entry_type, summary.size)
+                    }
+
+                    (None, None) => {}
+                }
+
+                text += "\n";
+            }
+        }
+
+        if let Some(entities) = entities
+            && !entities.is_empty()
+        {
+            text += "Definitions:\n";
+            for change in entities {
+                text += &format!(
+                    "{} {} {:?} in {}\n",
+                    marker(change.change_type),
+                    change.kind,
+                    change.name,
+                    change.source.display()
+                );
+            }
+        }
+
+        text
+    }
+}
+
+fn marker(change_type: ChangeType) -> &'static str {
+    match change_type {
+        ChangeType::Added => "+",
+        ChangeType::Removed => "-",
+        ChangeType::Changed => "~",
+    }
+}
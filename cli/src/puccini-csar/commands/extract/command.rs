@@ -0,0 +1,33 @@
+use super::super::create::*;
+
+use {clap::*, std::path::*};
+
+//
+// Extract
+//
+
+/// Extract subcommand.
+#[derive(Args)]
+pub struct Extract {
+    /// path to CSAR file
+    pub file: PathBuf,
+
+    /// path to target directory;
+    /// will be created if it does not exist
+    #[arg(default_value = ".", verbatim_doc_comment)]
+    pub directory: PathBuf,
+
+    /// archive format;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// extract only this file entry and write it to stdout
+    /// instead of to the target directory
+    #[arg(long = "entry", short = 'e', verbatim_doc_comment)]
+    pub entry: Option<PathBuf>,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
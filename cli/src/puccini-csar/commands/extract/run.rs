@@ -0,0 +1,40 @@
+use super::{super::root::*, command::*};
+
+use {
+    anstream::eprintln,
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::{reader::*, *},
+    std::io::{self, Write},
+};
+
+impl Extract {
+    /// Run extract subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let reader = CsarReader::open(&self.file, self.csar_format.as_ref().map(|format| format.to_puccini()))?;
+
+        match &self.entry {
+            Some(entry) => {
+                let mut stdout = io::stdout().lock();
+                if !reader.write_entry(entry, &mut stdout)? {
+                    return Err(NotFoundError::default_as_problem().with(entry.clone()).via(CsarError));
+                }
+                stdout.flush()?;
+            }
+
+            None => {
+                let entries = reader.extract(&self.directory)?;
+
+                if !root.quiet {
+                    eprintln!(
+                        "Extracted {} entries to {:?}",
+                        DEFAULT_THEME.number(entries.len()),
+                        DEFAULT_THEME.string(self.directory.display())
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -79,7 +79,8 @@ impl Inspect {
         Ok(())
     }
 
-    fn url_context() -> Result<UrlContextRef, Problem> {
+    /// URL context with the working directory as the base URL.
+    pub fn url_context() -> Result<UrlContextRef, Problem> {
         let url_context = UrlContext::new();
 
         #[cfg(feature = "filesystem")]
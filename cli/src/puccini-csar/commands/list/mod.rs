@@ -0,0 +1,4 @@
+mod command;
+mod run;
+
+pub use command::*;
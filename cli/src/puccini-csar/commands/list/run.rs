@@ -0,0 +1,56 @@
+use super::{
+    super::{inspect::*, root::*},
+    command::*,
+};
+
+use {
+    compris::{annotate::*, normal::*, ser::*},
+    problemo::*,
+    puccini_csar::reader::*,
+    std::{
+        fs::*,
+        io::{self, Write},
+    },
+};
+
+impl List {
+    /// Run list subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let reader = CsarReader::open(&self.file, self.csar_format.as_ref().map(|format| format.to_puccini()))?;
+        let entries = reader.entries()?;
+
+        if let Some(output_format) = &self.output_format
+            && matches!(output_format, OutputFormat::Text)
+        {
+            let mut text = String::default();
+            for entry in entries {
+                text += &format!("{:<9} {:>12} {}", entry.entry_type, entry.size, entry.path.display());
+                if let Some(link_target) = entry.link_target {
+                    text += &format!(" -> {}", link_target.display());
+                }
+                text += "\n";
+            }
+
+            if let Some(output_file) = &self.output_file {
+                let mut file = io::BufWriter::new(File::create(output_file)?);
+                file.write_all(text.as_bytes())?;
+            } else if !root.quiet {
+                print!("{}", text);
+            }
+        } else {
+            let format = self.output_format.as_ref().and_then(|format| format.to_compris());
+            let variant: List<WithoutAnnotations> = entries.into_iter().map(|entry| entry.into()).collect();
+            let variant: Variant<WithoutAnnotations> = variant.into();
+
+            RepresentationWriter::new(format, !self.output_plain, self.output_base64).write_to_file_or_stdout(
+                &variant,
+                root.quiet,
+                false,
+                true,
+                self.output_file.as_ref(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,14 @@
+mod convert;
 mod create;
+mod definitions;
+mod diff;
+mod extract;
 mod inspect;
+mod list;
 mod meta;
 mod root;
+mod update;
+mod validate;
+mod verify;
 
 pub use root::*;
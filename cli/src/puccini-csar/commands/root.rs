@@ -1,4 +1,6 @@
-use super::{create::*, inspect::*, meta::*};
+use super::{
+    convert::*, create::*, diff::*, extract::*, inspect::*, list::*, meta::*, update::*, validate::*, verify::*,
+};
 
 use {clap::*, kutil::cli::clap::*, std::path::*};
 
@@ -67,6 +69,27 @@ pub enum SubCommand {
     /// inspect and extract meta information from a CSAR
     Inspect(Inspect),
 
+    /// convert a CSAR to another archive format
+    Convert(Convert),
+
+    /// update the entries and TOSCA.meta of a CSAR
+    Update(Update),
+
+    /// validate a CSAR and its TOSCA definitions
+    Validate(Validate),
+
+    /// verify the signature and file digests of a signed CSAR
+    Verify(Verify),
+
+    /// compare two CSARs
+    Diff(Diff),
+
+    /// list the entries in a CSAR
+    List(List),
+
+    /// extract the entries in a CSAR to a directory
+    Extract(Extract),
+
     /// show the version of puccini-csar
     #[command(action = ArgAction::Version)]
     Version(Version),
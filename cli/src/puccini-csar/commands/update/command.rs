@@ -0,0 +1,117 @@
+use super::super::create::*;
+
+use {
+    clap::*,
+    clap_num::*,
+    puccini_csar::creator::*,
+    std::{collections::*, path::*},
+};
+
+//
+// Update
+//
+
+/// Update subcommand.
+#[derive(Args)]
+pub struct Update {
+    /// path to CSAR file
+    pub file: PathBuf,
+
+    /// path to target CSAR file;
+    /// when absent will update the CSAR in place
+    #[arg(long = "output", short = 'o', verbatim_doc_comment)]
+    pub output: Option<PathBuf>,
+
+    /// archive format;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// compression level from 1 to 10;
+    /// 1 = least compression, fastest;
+    /// 10 = most compression, slowest;
+    /// leave empty to keep the source CSAR's level if it can be detected,
+    /// otherwise the format's default
+    #[arg(long = "compression", short = 'C', value_parser = compression_level_parser, verbatim_doc_comment)]
+    pub compression_level: Option<usize>,
+
+    /// add or replace a file in the form "path-in-csar=path-to-file";
+    /// can be used more than once
+    #[arg(long = "add", short = 'a', value_parser = add_parser, verbatim_doc_comment)]
+    pub add: Vec<(PathBuf, PathBuf)>,
+
+    /// remove an entry (removing a directory removes all its entries);
+    /// can be used more than once
+    #[arg(long = "remove", short = 'R', verbatim_doc_comment)]
+    pub remove: Vec<PathBuf>,
+
+    /// override the "Created-By" key
+    #[arg(long = "created-by")]
+    pub created_by: Option<String>,
+
+    /// override the "Entry-Definitions" key
+    #[arg(long = "entry-definitions")]
+    pub entry_definitions: Option<String>,
+
+    /// add an entry to the "Other-Definitions" key
+    #[arg(long = "other-definitions")]
+    pub other_definitions: Vec<String>,
+
+    /// enable reproducible mode;
+    /// all entries will have the same timestamp (from SOURCE_DATE_EPOCH
+    /// or 1980-01-01), normalized permissions, and no owner
+    #[arg(long = "reproducible", short = 'r', verbatim_doc_comment)]
+    pub reproducible: bool,
+
+    /// path to private key file (PKCS#8 PEM, Ed25519) for signing;
+    /// the manifest of file digests and its signature will be regenerated;
+    /// when absent existing signature files will be removed
+    #[arg(long = "sign-key", verbatim_doc_comment)]
+    pub sign_key: Option<PathBuf>,
+
+    /// path to certificate file (minisign public key or public key PEM) to add to a signed CSAR;
+    /// when absent will be derived from the private key
+    #[arg(long = "sign-cert", requires = "sign_key", verbatim_doc_comment)]
+    pub sign_cert: Option<PathBuf>,
+
+    /// maximum number of columns for TOSCA.meta file
+    #[arg(long = "max-columns", short = 'c', default_value_t = 80)]
+    pub max_columns: usize,
+
+    /// enable dry run;
+    /// do everything except write the CSAR
+    #[arg(long = "dry-run", short = 'd', verbatim_doc_comment)]
+    pub dry_run: bool,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
+
+impl Update {
+    pub fn csar_format(&self) -> Option<Format> {
+        self.csar_format.as_ref().map(|format| format.to_puccini())
+    }
+
+    pub fn compression_level(&self) -> Option<CompressionLevel> {
+        self.compression_level.map(CompressionLevel::new_unchecked)
+    }
+
+    pub fn changes(&self) -> CsarChanges {
+        CsarChanges {
+            files: self.add.iter().cloned().collect::<BTreeMap<_, _>>(),
+            remove: self.remove.iter().cloned().collect::<BTreeSet<_>>(),
+        }
+    }
+}
+
+fn add_parser(representation: &str) -> Result<(PathBuf, PathBuf), String> {
+    match representation.split_once('=') {
+        Some((name, file)) => Ok((name.trim().into(), file.trim().into())),
+        None => Err("must be in the form \"path-in-csar=path-to-file\"".into()),
+    }
+}
+
+fn compression_level_parser(representation: &str) -> Result<usize, String> {
+    number_range(representation, 1, 10)
+}
@@ -0,0 +1,69 @@
+use super::{super::root::*, command::*};
+
+use {
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::{creator::*, reader::*, signature::*},
+};
+
+impl Update {
+    /// Run update subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let mut problems = Problems::default();
+
+        let reader = CsarReader::open(&self.file, self.csar_format())?;
+
+        // The format is kept (we do not convert), and so is the compression level if we can detect it
+        let compression_level = match self.compression_level() {
+            Some(compression_level) => Some(compression_level),
+            None => detect_compression_level(&reader)?,
+        };
+
+        let mut creator = CsarCreator::new(
+            Some(reader.format),
+            compression_level,
+            self.created_by.clone(),
+            self.entry_definitions.clone(),
+            self.other_definitions.clone(),
+            Some(self.max_columns),
+            None,
+        );
+
+        if self.reproducible {
+            creator.reproducible = Some(Reproducible::from_environment()?);
+        }
+
+        if let Some(sign_key) = &self.sign_key {
+            creator.signer = Some(CsarSigner::read(sign_key, self.sign_cert.as_ref().map(|path| path.as_ref()))?);
+        }
+
+        let tosca_meta = creator.update_csar(
+            &reader,
+            self.output.as_ref().map(|path| path.as_ref()),
+            &self.changes(),
+            self.dry_run,
+            &mut problems,
+        )?;
+
+        match problems.check() {
+            Ok(_) => {
+                if !root.quiet
+                    && self.dry_run
+                    && let Some(tosca_meta) = tosca_meta
+                {
+                    print!("{}", tosca_meta.stringify(Some(self.max_columns))?);
+                }
+
+                Ok(())
+            }
+
+            Err(problems) => {
+                if !root.quiet {
+                    problems.annotated_depiction().eprint_default_depiction();
+                }
+
+                Err(ExitError::failure())
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::super::create::*;
+
+use {clap::*, std::path::*};
+
+//
+// Validate
+//
+
+/// Validate subcommand.
+#[derive(Args)]
+pub struct Validate {
+    /// path to CSAR file
+    pub file: PathBuf,
+
+    /// archive format;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// whether to validate the TOSCA definitions;
+    /// they must parse and their relative imports and artifact files
+    /// must be within the CSAR
+    #[arg(long = "tosca", action = clap::ArgAction::Set, default_value_t = true, verbatim_doc_comment)]
+    pub tosca: bool,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
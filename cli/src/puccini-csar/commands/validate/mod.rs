@@ -0,0 +1,5 @@
+mod command;
+mod run;
+mod tosca;
+
+pub use command::*;
@@ -0,0 +1,33 @@
+use super::{super::root::*, command::*};
+
+use {
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::reader::*,
+};
+
+impl Validate {
+    /// Run validate subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let mut problems = Problems::default();
+
+        let reader = CsarReader::open(&self.file, self.csar_format.as_ref().map(|format| format.to_puccini()))?;
+        let entries = reader.entries()?;
+
+        if let Some(tosca_meta) = reader.validate(&entries, &mut problems)?
+            && self.tosca
+        {
+            self.validate_tosca(&reader, &entries, &tosca_meta, &mut problems)?;
+        }
+
+        if let Err(problems) = problems.check() {
+            if !root.quiet {
+                problems.annotated_depiction().eprint_default_depiction();
+            }
+
+            return Err(ExitError::failure());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use super::{super::definitions::*, command::*};
+
+use {
+    problemo::*,
+    puccini_csar::{reader::*, tosca_meta::*},
+};
+
+impl Validate {
+    /// Validate the TOSCA definitions in the CSAR.
+    ///
+    /// The definitions must parse, and their relative imports and artifact files must be within
+    /// the CSAR.
+    pub fn validate_tosca(
+        &self,
+        reader: &CsarReader,
+        entries: &[CsarEntry],
+        tosca_meta: &ToscaMeta,
+        problems: &mut Problems,
+    ) -> Result<(), Problem> {
+        let definitions = Definitions::load_from_csar(&self.file, reader, entries, tosca_meta, problems)?;
+
+        definitions.artifact_files(|path| has_file(entries, path), problems)?;
+
+        Ok(())
+    }
+}
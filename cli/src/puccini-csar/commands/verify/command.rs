@@ -0,0 +1,30 @@
+use super::super::create::*;
+
+use {clap::*, std::path::*};
+
+//
+// Verify
+//
+
+/// Verify subcommand.
+#[derive(Args)]
+pub struct Verify {
+    /// path to CSAR file
+    pub file: PathBuf,
+
+    /// archive format;
+    /// when absent will be detected from the file content or extension
+    #[arg(long = "csar-format", verbatim_doc_comment, value_enum)]
+    pub csar_format: Option<CsarFormat>,
+
+    /// path to trusted certificate file (minisign public key or public key PEM);
+    /// when absent will use the certificate in the CSAR,
+    /// which can detect tampering but not who signed it,
+    /// and so verification will fail as untrusted
+    #[arg(long = "cert", verbatim_doc_comment)]
+    pub cert: Option<PathBuf>,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
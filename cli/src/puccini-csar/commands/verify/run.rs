@@ -0,0 +1,32 @@
+use super::{super::root::*, command::*};
+
+use {
+    depiction::*,
+    problemo::{common::*, *},
+    puccini_csar::{reader::*, signature::*},
+};
+
+impl Verify {
+    /// Run verify subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        let mut problems = Problems::default();
+
+        let public_key = match &self.cert {
+            Some(cert) => Some(read_public_key(cert)?),
+            None => None,
+        };
+
+        let reader = CsarReader::open(&self.file, self.csar_format.as_ref().map(|format| format.to_puccini()))?;
+        reader.verify(public_key.as_ref(), &mut problems)?;
+
+        if let Err(problems) = problems.check() {
+            if !root.quiet {
+                problems.annotated_depiction().eprint_default_depiction();
+            }
+
+            return Err(ExitError::failure());
+        }
+
+        Ok(())
+    }
+}
@@ -17,6 +17,13 @@ pub fn run() -> Result<(), Problem> {
             SubCommand::Create(create) => create.run(&root)?,
             SubCommand::Meta(meta) => meta.run(&root)?,
             SubCommand::Inspect(inspect) => inspect.run(&root)?,
+            SubCommand::Convert(convert) => convert.run(&root)?,
+            SubCommand::Update(update) => update.run(&root)?,
+            SubCommand::Validate(validate) => validate.run(&root)?,
+            SubCommand::Verify(verify) => verify.run(&root)?,
+            SubCommand::Diff(diff) => diff.run(&root)?,
+            SubCommand::List(list) => list.run(&root)?,
+            SubCommand::Extract(extract) => extract.run(&root)?,
             SubCommand::Version(version) => version.run::<Root>(),
             SubCommand::Completion(completion) => completion.run::<Root>(),
             SubCommand::Manual(manual) => manual.run::<Root>()?,
@@ -15,6 +15,21 @@ pub struct Compile {
     #[arg(verbatim_doc_comment)]
     pub input_file_or_url: Option<String>,
 
+    /// TOSCA service template to use for substituting node templates
+    /// marked with the "substitute" directive;
+    /// can be a file path or a URL;
+    /// can be used multiple times
+    #[arg(long = "substitution", verbatim_doc_comment)]
+    pub substitutions: Vec<String>,
+
+    /// directory or CSAR in which to look for imported TOSCA profiles;
+    /// can be a file path or a URL;
+    /// can be used multiple times;
+    /// will be searched before the paths in the
+    /// PUCCINI_TOSCA_PROFILE_PATH environment variable
+    #[arg(long = "profile-path", verbatim_doc_comment)]
+    pub profile_paths: Vec<String>,
+
     /// URL to Floria instance;
     /// when empty will use an in-memory instance
     #[arg(long = "floria", short = 'f', verbatim_doc_comment)]
@@ -63,10 +78,22 @@ pub struct Compile {
     #[arg(long = "output", verbatim_doc_comment)]
     pub outputs: Vec<String>,
 
+    /// evaluate pure built-in function calls with literal arguments
+    /// at compile time, replacing them with their values
+    #[arg(long = "fold-constants", verbatim_doc_comment)]
+    pub fold_constants: bool,
+
     /// instantiate the compiled Floria template
     #[arg(long = "instantiate", short = 'i')]
     pub instantiate: bool,
 
+    /// TOSCA service template with the existing nodes that can be
+    /// selected by node templates marked with the "select" directive;
+    /// can be a file path or a URL;
+    /// requires `--instantiate`
+    #[arg(long = "inventory", verbatim_doc_comment)]
+    pub inventory: Option<String>,
+
     /// propagate an event on the Floria instance;
     /// when used multiple times the events will be propagates in sequence;
     /// requires `--instantiate`
@@ -27,19 +27,14 @@ impl Compile {
     where
         StoreT: 'static + Clone + Send + Store,
     {
-        tracing::info!(directory = directory.to_string(), template = service_template_id.to_string(), "instantiating");
-
-        let floria_service_template = store
-            .get_vertex_template(service_template_id)?
-            .ok_or_else(|| StoreError::as_problem().with(service_template_id.clone()))?;
-
         let environment = PluginEnvironment::new(self.wasm_debug, self.wasm_cache)?;
         let mut context = self.plugin_context(environment, store.clone(), url_context.clone())?;
 
-        let mut floria_instance = floria_service_template.instantiate(
-            &directory,
-            None,
+        let mut floria_instance = Self::instantiate_service_template(
+            service_template_id,
             self.instantiation_payload(inputs).as_ref(),
+            directory,
+            store,
             &mut context,
             problems,
         )?;
@@ -51,6 +46,27 @@ impl Compile {
         Ok(Some(floria_instance))
     }
 
+    /// Instantiate a service template without handling events.
+    pub fn instantiate_service_template<StoreT>(
+        service_template_id: &ID,
+        payload: Option<&Expression>,
+        directory: &Directory,
+        store: StoreT,
+        context: &mut PluginContext<StoreT>,
+        problems: &mut Problems,
+    ) -> Result<Vertex, Problem>
+    where
+        StoreT: 'static + Clone + Send + Store,
+    {
+        tracing::info!(directory = directory.to_string(), template = service_template_id.to_string(), "instantiating");
+
+        let floria_service_template = store
+            .get_vertex_template(service_template_id)?
+            .ok_or_else(|| StoreError::as_problem().with(service_template_id.clone()))?;
+
+        floria_service_template.instantiate(&directory, None, payload, context, problems)
+    }
+
     fn instantiation_payload<AnnotatedT>(&self, inputs: Option<Map<AnnotatedT>>) -> Option<Expression> {
         let Some(inputs) = inputs else {
             return None;
@@ -0,0 +1,68 @@
+use super::command::*;
+
+use {
+    floria::{plugins::*, *},
+    problemo::*,
+    puccini_tosca::grammar::*,
+    read_url::*,
+    std::iter,
+};
+
+/// Floria directory segment for the inventory.
+pub const INVENTORY_DIRECTORY_SEGMENT: &str = "inventory";
+
+impl Compile {
+    /// Compile the inventory.
+    ///
+    /// The TOSCA service template specified with `--inventory` is compiled into its own Floria
+    /// directory and instantiated as is: `--event` and `--update` only apply to the service.
+    ///
+    /// Returns the Floria ID of the inventory vertex.
+    pub fn compile_inventory(
+        &self,
+        source_id: Option<&SourceID>,
+        catalog: &Catalog,
+        directory: &Directory,
+        store: InMemoryStore,
+        url_context: &UrlContextRef,
+        tosca_problems: &mut Problems,
+        floria_problems: &mut Problems,
+    ) -> Result<Option<ID>, Problem> {
+        let Some(source_id) = source_id else {
+            return Ok(None);
+        };
+
+        tracing::info!(source = source_id.to_string(), "compiling inventory");
+
+        let directory = Directory::new(
+            directory.segments.iter().cloned().chain(iter::once(INVENTORY_DIRECTORY_SEGMENT.into())).collect(),
+        )?;
+
+        let mut context =
+            CompilationContext::new(source_id, catalog, &directory, store.clone().as_ref(), tosca_problems.as_ref());
+
+        let inventory_template_id = if self.annotations {
+            catalog.compile_service_template_with_annotations(&mut context)
+        } else {
+            catalog.compile_service_template_without_annotations(&mut context)
+        }?;
+
+        let Some(inventory_template_id) = inventory_template_id else {
+            return Ok(None);
+        };
+
+        let environment = PluginEnvironment::new(self.wasm_debug, self.wasm_cache)?;
+        let mut context = self.plugin_context(environment, store.clone(), url_context.clone())?;
+
+        let inventory = Self::instantiate_service_template(
+            &inventory_template_id,
+            None,
+            &directory,
+            store,
+            &mut context,
+            floria_problems,
+        )?;
+
+        Ok(Some(inventory.instance.id))
+    }
+}
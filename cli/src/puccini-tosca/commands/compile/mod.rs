@@ -5,6 +5,8 @@ mod format;
 mod inputs;
 #[cfg(feature = "plugins")]
 mod instantiate;
+#[cfg(feature = "plugins")]
+mod inventory;
 mod output;
 mod run;
 
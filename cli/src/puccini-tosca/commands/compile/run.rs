@@ -32,6 +32,10 @@ impl Compile {
             if !self.outputs.is_empty() {
                 return Err(ExitError::failure_message("cannot use `--output` without `--instantiate`"));
             }
+
+            if self.inventory.is_some() {
+                return Err(ExitError::failure_message("cannot use `--inventory` without `--instantiate`"));
+            }
         }
 
         #[allow(unreachable_code)]
@@ -67,6 +71,11 @@ impl Compile {
         let source_id = self.source_id(&url_context, &mut csar_problems)?;
         let mut catalog = Self::catalog::<AnnotatedT>()?;
 
+        for profile_path in &self.profile_paths {
+            catalog.profiles.add_path(profile_path.clone().into());
+        }
+        catalog.profiles.add_paths_from_environment();
+
         let mut tosca_problems = Problems::default();
 
         // Inputs
@@ -83,6 +92,29 @@ impl Compile {
             url_context = new_url_context;
         }
 
+        for substitution in &self.substitutions {
+            let substitution_source_id = url_to_source_id(substitution.clone(), &url_context, &mut tosca_problems)?;
+            if self.annotations {
+                catalog.load_source_with_annotations(&substitution_source_id, &url_context, &mut tosca_problems)
+            } else {
+                catalog.load_source_without_annotations(&substitution_source_id, &url_context, &mut tosca_problems)
+            }?;
+        }
+
+        let inventory_source_id = match &self.inventory {
+            Some(inventory) => {
+                let inventory_source_id = url_to_source_id(inventory.clone(), &url_context, &mut tosca_problems)?;
+                if self.annotations {
+                    catalog.load_source_with_annotations(&inventory_source_id, &url_context, &mut tosca_problems)
+                } else {
+                    catalog.load_source_without_annotations(&inventory_source_id, &url_context, &mut tosca_problems)
+                }?;
+                Some(inventory_source_id)
+            }
+
+            None => None,
+        };
+
         // Complete
 
         if self.should_complete() {
@@ -96,14 +128,33 @@ impl Compile {
 
         let directory = self.floria_directory()?;
 
+        #[cfg(feature = "plugins")]
+        let mut floria_problems = Problems::default();
+
         if self.should_compile() {
+            #[cfg(feature = "plugins")]
+            let inventory = self.compile_inventory(
+                inventory_source_id.as_ref(),
+                &catalog,
+                &directory,
+                store.clone(),
+                &url_context,
+                &mut tosca_problems,
+                &mut floria_problems,
+            )?;
+
+            #[cfg(not(feature = "plugins"))]
+            let inventory = None;
+
             let mut context = CompilationContext::new(
                 &source_id,
                 &catalog,
                 &directory,
                 store.clone().as_ref(),
                 tosca_problems.as_ref(),
-            );
+            )
+            .with_inventory(inventory)
+            .with_fold_constants(self.fold_constants);
             floria_service_template_id = if self.annotations {
                 catalog.compile_service_template_with_annotations(&mut context)
             } else {
@@ -113,9 +164,6 @@ impl Compile {
 
         // Instantiate
 
-        #[cfg(feature = "plugins")]
-        let mut floria_problems = Problems::default();
-
         #[cfg(feature = "plugins")]
         let floria_instance = if self.instantiate
             && let Some(floria_service_template_id) = &floria_service_template_id
@@ -163,7 +211,8 @@ impl Compile {
         }
     }
 
-    fn url_context() -> Result<UrlContextRef, Problem> {
+    /// URL context with the working directory as the base URL.
+    pub fn url_context() -> Result<UrlContextRef, Problem> {
         let url_context = UrlContext::new();
 
         #[cfg(feature = "filesystem")]
@@ -1,4 +1,5 @@
 mod compile;
 mod root;
+mod validate;
 
 pub use root::*;
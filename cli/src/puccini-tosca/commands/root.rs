@@ -1,4 +1,4 @@
-use super::compile::*;
+use super::{compile::*, validate::*};
 
 use {clap::*, kutil::cli::clap::*, std::path::*};
 
@@ -61,6 +61,9 @@ pub enum SubCommand {
     /// compile a TOSCA service template to a Floria template
     Compile(Compile),
 
+    /// validate a TOSCA service template and report diagnostics
+    Validate(Validate),
+
     /// show the version of puccini-tosca
     Version(Version),
 
@@ -0,0 +1,72 @@
+use super::format::*;
+
+use {clap::*, std::path::*};
+
+/// Exit code when there are no problems.
+pub const EXIT_CODE_VALID: u8 = 0;
+
+/// Exit code when there are problems, which are reported as diagnostics.
+pub const EXIT_CODE_INVALID: u8 = 1;
+
+/// Exit code when validation could not be run, e.g. because of invalid flags or because the
+/// diagnostics could not be written.
+pub const EXIT_CODE_FAILURE: u8 = 2;
+
+//
+// Validate
+//
+
+/// Validate subcommand.
+#[derive(Args)]
+pub struct Validate {
+    /// TOSCA or CSAR;
+    /// can be a file path or a URL;
+    /// when absent will read TOSCA YAML from stdin
+    #[arg(verbatim_doc_comment)]
+    pub input_file_or_url: Option<String>,
+
+    /// TOSCA service template to use for substituting node templates
+    /// marked with the "substitute" directive;
+    /// can be a file path or a URL;
+    /// can be used multiple times
+    #[arg(long = "substitution", verbatim_doc_comment)]
+    pub substitutions: Vec<String>,
+
+    /// directory or CSAR in which to look for imported TOSCA profiles;
+    /// can be a file path or a URL;
+    /// can be used multiple times;
+    /// will be searched before the paths in the
+    /// PUCCINI_TOSCA_PROFILE_PATH environment variable
+    #[arg(long = "profile-path", verbatim_doc_comment)]
+    pub profile_paths: Vec<String>,
+
+    /// also compile to a Floria template,
+    /// which can detect more problems
+    #[arg(long = "compile", short = 'c', verbatim_doc_comment)]
+    pub compile: bool,
+
+    /// evaluate pure built-in function calls with literal arguments
+    /// while compiling, which can detect more problems;
+    /// requires `--compile`
+    #[arg(long = "fold-constants", requires = "compile", verbatim_doc_comment)]
+    pub fold_constants: bool,
+
+    /// output file path;
+    /// when absent will write to stdout
+    #[arg(long = "output-file", short = 'o', verbatim_doc_comment)]
+    pub output_file: Option<PathBuf>,
+
+    /// diagnostics format;
+    /// when absent will try to use the output file extension
+    #[arg(long = "format", verbatim_doc_comment, value_enum)]
+    pub output_format: Option<DiagnosticsFormat>,
+
+    /// plain output;
+    /// avoid colors and whitespace
+    #[arg(long = "plain", short = 'p', verbatim_doc_comment)]
+    pub output_plain: bool,
+
+    /// show this help
+    #[arg(long, short = 'h', action = ArgAction::Help)]
+    pub help: Option<bool>,
+}
@@ -0,0 +1,107 @@
+use {
+    compris::{annotate::*, normal::*},
+    problemo::*,
+    puccini_tosca::grammar::*,
+};
+
+/// Diagnostic severity.
+///
+/// All problems are currently errors.
+pub const ERROR_SEVERITY: &str = "error";
+
+//
+// Diagnostic
+//
+
+/// Machine-readable diagnostic for a problem.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    /// Phase in which the problem was detected, e.g. "tosca".
+    pub phase: &'static str,
+
+    /// Severity.
+    pub severity: &'static str,
+
+    /// Error kind, e.g. "UndeclaredError".
+    pub kind: &'static str,
+
+    /// Message.
+    pub message: String,
+
+    /// Source file or URL.
+    pub file: Option<String>,
+
+    /// Line.
+    pub line: Option<usize>,
+
+    /// Column.
+    pub column: Option<usize>,
+
+    /// Path of the value in the source.
+    pub path: Option<String>,
+}
+
+impl Diagnostic {
+    /// Constructor.
+    pub fn new(phase: &'static str, problem: &Problem) -> Self {
+        let mut diagnostic = Self {
+            phase,
+            severity: ERROR_SEVERITY,
+            kind: error_kind(problem),
+            message: problem.to_string(),
+            file: None,
+            line: None,
+            column: None,
+            path: None,
+        };
+
+        if let Some(annotations) = problem.attachment_of_type::<Annotations>() {
+            diagnostic.file = annotations.source.as_ref().map(|source| source.to_string());
+            diagnostic.path = annotations.path.as_ref().map(|path| path.to_string());
+
+            if let Some(span) = &annotations.span {
+                diagnostic.line = Some(span.start.row);
+                diagnostic.column = Some(span.start.column);
+            }
+        }
+
+        diagnostic
+    }
+
+    /// Diagnostics for problems.
+    pub fn from_problems(phase: &'static str, problems: &Problems) -> Vec<Self> {
+        problems.iter().map(|problem| Self::new(phase, problem)).collect()
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for Diagnostic
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("phase", self.phase);
+        map.into_insert("severity", self.severity);
+        map.into_insert("kind", self.kind);
+        map.into_insert("message", self.message);
+
+        if let Some(file) = self.file {
+            map.into_insert("file", file);
+        }
+
+        if let Some(line) = self.line {
+            map.into_insert("line", line as u64);
+        }
+
+        if let Some(column) = self.column {
+            map.into_insert("column", column as u64);
+        }
+
+        if let Some(path) = self.path {
+            map.into_insert("path", path);
+        }
+
+        map.into()
+    }
+}
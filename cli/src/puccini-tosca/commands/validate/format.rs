@@ -0,0 +1,48 @@
+use {clap::*, std::path::*};
+
+//
+// DiagnosticsFormat
+//
+
+#[derive(Clone, ValueEnum)]
+pub enum DiagnosticsFormat {
+    YAML,
+    JSON,
+    SARIF,
+    Depict,
+}
+
+impl DiagnosticsFormat {
+    /// From path extension.
+    pub fn from_path<PathT>(path: PathT) -> Option<Self>
+    where
+        PathT: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let name = path.to_string_lossy();
+        if name.ends_with(".sarif") || name.ends_with(".sarif.json") {
+            Some(Self::SARIF)
+        } else {
+            compris::Format::from_path(path).and_then(|format| match format {
+                compris::Format::YAML => Some(Self::YAML),
+                compris::Format::JSON => Some(Self::JSON),
+                _ => None,
+            })
+        }
+    }
+
+    /// To Compris format.
+    pub fn to_compris(&self) -> Option<compris::Format> {
+        match self {
+            DiagnosticsFormat::YAML => Some(compris::Format::YAML),
+            DiagnosticsFormat::JSON | DiagnosticsFormat::SARIF => Some(compris::Format::JSON),
+            DiagnosticsFormat::Depict => None,
+        }
+    }
+}
+
+impl ToString for DiagnosticsFormat {
+    fn to_string(&self) -> String {
+        self.to_possible_value().expect("to_possible_value").get_name().into()
+    }
+}
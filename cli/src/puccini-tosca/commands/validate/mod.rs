@@ -0,0 +1,7 @@
+mod command;
+mod diagnostic;
+mod format;
+mod run;
+mod sarif;
+
+pub use command::*;
@@ -0,0 +1,137 @@
+use super::{
+    super::{compile::*, root::*},
+    command::*,
+    diagnostic::*,
+    format::*,
+    sarif::*,
+};
+
+use {
+    compris::{annotate::*, normal::*, ser::*},
+    depiction::*,
+    floria::*,
+    problemo::{common::*, *},
+    puccini_tosca::grammar::*,
+};
+
+impl Validate {
+    /// Run validate subcommand.
+    pub fn run(&self, root: &Root) -> Result<(), Problem> {
+        #[cfg(not(feature = "with-annotations"))]
+        return Err(Self::failure("to use `validate` you must enable \"with-annotations\" feature in the build"));
+
+        #[allow(unreachable_code)]
+        let format = self.diagnostics_format()?;
+
+        let mut csar_problems = Problems::default();
+        let mut tosca_problems = Problems::default();
+
+        // Problems that stop validation are diagnostics, too
+        if let Err(problem) = self.validate(&mut csar_problems, &mut tosca_problems) {
+            tosca_problems.give(problem)?;
+        }
+
+        let mut diagnostics = Diagnostic::from_problems("csar", &csar_problems);
+        diagnostics.extend(Diagnostic::from_problems("tosca", &tosca_problems));
+        let valid = diagnostics.is_empty();
+
+        match format {
+            DiagnosticsFormat::Depict => {
+                if !root.quiet {
+                    if let Err(csar_problems) = csar_problems.check() {
+                        csar_problems.annotated_depiction().with_heading("CSAR Errors").eprint_default_depiction();
+                    }
+
+                    if let Err(tosca_problems) = tosca_problems.check() {
+                        tosca_problems.annotated_depiction().with_heading("TOSCA Errors").eprint_default_depiction();
+                    }
+                }
+            }
+
+            DiagnosticsFormat::SARIF => self.output(to_sarif(diagnostics), &format, root)?,
+
+            _ => {
+                let diagnostics: List<_> = diagnostics.into_iter().map(|diagnostic| diagnostic.into()).collect();
+                self.output(diagnostics.into(), &format, root)?;
+            }
+        }
+
+        if valid { Ok(()) } else { Err(ExitError::new(EXIT_CODE_INVALID, None).into()) }
+    }
+
+    fn validate(&self, csar_problems: &mut Problems, tosca_problems: &mut Problems) -> Result<(), Problem> {
+        let mut url_context = Compile::url_context()?;
+
+        let source_id = match self.input_file_or_url.clone() {
+            Some(url) => url_to_source_id(url, &url_context, csar_problems)?,
+            None => Default::default(),
+        };
+
+        let mut catalog = Compile::catalog::<WithAnnotations>()?;
+
+        for profile_path in &self.profile_paths {
+            catalog.profiles.add_path(profile_path.clone().into());
+        }
+        catalog.profiles.add_paths_from_environment();
+
+        // Load
+
+        if let Some(new_url_context) = catalog.load_source_with_annotations(&source_id, &url_context, tosca_problems)? {
+            url_context = new_url_context;
+        }
+
+        for substitution in &self.substitutions {
+            let substitution_source_id = url_to_source_id(substitution.clone(), &url_context, tosca_problems)?;
+            catalog.load_source_with_annotations(&substitution_source_id, &url_context, tosca_problems)?;
+        }
+
+        // Complete
+
+        catalog.complete_entities(tosca_problems)?;
+
+        // Compile
+
+        if self.compile {
+            let store = InMemoryStore::default();
+            let directory = Directory::default();
+            let mut context =
+                CompilationContext::new(&source_id, &catalog, &directory, store.as_ref(), tosca_problems.as_ref())
+                    .with_fold_constants(self.fold_constants);
+            catalog.compile_service_template_with_annotations(&mut context)?;
+        }
+
+        Ok(())
+    }
+
+    fn output(
+        &self,
+        variant: Variant<WithoutAnnotations>,
+        format: &DiagnosticsFormat,
+        root: &Root,
+    ) -> Result<(), Problem> {
+        RepresentationWriter::new(format.to_compris(), !self.output_plain, false)
+            .write_to_file_or_stdout(&variant, root.quiet, false, true, self.output_file.as_ref())
+            .map_err(|problem| Self::failure(problem.to_string()))
+    }
+
+    fn diagnostics_format(&self) -> Result<DiagnosticsFormat, Problem> {
+        match &self.output_format {
+            Some(output_format) => Ok(output_format.clone()),
+
+            None => match &self.output_file {
+                Some(output_file) => DiagnosticsFormat::from_path(output_file).ok_or_else(|| {
+                    Self::failure("cannot determine diagnostics format; specify it explicitly with --format")
+                }),
+
+                None => Ok(DiagnosticsFormat::Depict),
+            },
+        }
+    }
+
+    fn failure<MessageT>(message: MessageT) -> Problem
+    where
+        MessageT: ToString,
+    {
+        ExitError::new(EXIT_CODE_FAILURE, Some(message.to_string())).into()
+    }
+}
@@ -0,0 +1,96 @@
+use super::diagnostic::*;
+
+use {
+    compris::{annotate::*, normal::*},
+    std::collections::*,
+};
+
+/// SARIF version.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// SARIF schema.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Tool information URI.
+pub const SARIF_TOOL_INFORMATION_URI: &str = "https://puccini.cloud";
+
+/// Diagnostics as a [SARIF](https://sarifweb.azurewebsites.net/) log with a single run.
+///
+/// The error kinds are the rule IDs.
+pub fn to_sarif(diagnostics: Vec<Diagnostic>) -> Variant<WithoutAnnotations> {
+    let rules: BTreeSet<_> = diagnostics.iter().map(|diagnostic| diagnostic.kind).collect();
+    let rules: List<_> = rules
+        .into_iter()
+        .map(|rule| {
+            let mut map = Map::default();
+            map.into_insert("id", rule);
+            map.into()
+        })
+        .collect();
+
+    let mut driver = Map::default();
+    driver.into_insert("name", "puccini-tosca");
+    driver.into_insert("version", env!("CARGO_PKG_VERSION"));
+    driver.into_insert("informationUri", SARIF_TOOL_INFORMATION_URI);
+    driver.into_insert("rules", rules);
+
+    let mut tool = Map::default();
+    tool.into_insert("driver", driver);
+
+    let results: List<_> = diagnostics.into_iter().map(to_sarif_result).collect();
+
+    let mut run = Map::default();
+    run.into_insert("tool", tool);
+    run.into_insert("results", results);
+
+    let runs: List<_> = [run.into()].into_iter().collect();
+
+    let mut log = Map::default();
+    log.into_insert("$schema", SARIF_SCHEMA);
+    log.into_insert("version", SARIF_VERSION);
+    log.into_insert("runs", runs);
+    log.into()
+}
+
+fn to_sarif_result(diagnostic: Diagnostic) -> Variant<WithoutAnnotations> {
+    let mut message = Map::default();
+    message.into_insert("text", diagnostic.message);
+
+    let mut result = Map::default();
+    result.into_insert("ruleId", diagnostic.kind);
+    result.into_insert("level", diagnostic.severity);
+    result.into_insert("message", message);
+
+    if let Some(file) = diagnostic.file {
+        let mut artifact_location = Map::default();
+        artifact_location.into_insert("uri", file);
+
+        let mut physical_location = Map::default();
+        physical_location.into_insert("artifactLocation", artifact_location);
+
+        if let Some(line) = diagnostic.line {
+            let mut region = Map::default();
+            // SARIF lines and columns start at 1
+            region.into_insert("startLine", line.max(1) as u64);
+            if let Some(column) = diagnostic.column {
+                region.into_insert("startColumn", column.max(1) as u64);
+            }
+            physical_location.into_insert("region", region);
+        }
+
+        let mut location = Map::default();
+        location.into_insert("physicalLocation", physical_location);
+
+        if let Some(path) = diagnostic.path {
+            let mut logical_location = Map::default();
+            logical_location.into_insert("fullyQualifiedName", path);
+            let logical_locations: List<_> = [logical_location.into()].into_iter().collect();
+            location.into_insert("logicalLocations", logical_locations);
+        }
+
+        let locations: List<_> = [location.into()].into_iter().collect();
+        result.into_insert("locations", locations);
+    }
+
+    result.into()
+}
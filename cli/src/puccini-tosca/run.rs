@@ -15,6 +15,7 @@ pub fn run() -> Result<(), Problem> {
         None => {}
         Some(subcommand) => match subcommand {
             SubCommand::Compile(compile) => compile.run(&root)?,
+            SubCommand::Validate(validate) => validate.run(&root)?,
             SubCommand::Version(version) => version.run::<Root>(),
             SubCommand::Completion(completion) => completion.run::<Root>(),
             SubCommand::Manual(manual) => manual.run::<Root>()?,
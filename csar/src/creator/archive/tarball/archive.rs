@@ -72,6 +72,19 @@ where
         add_from_reader(self, name, &mut header, &mut source, read_tracker)
     }
 
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        mut source: Box<&mut dyn io::Read>,
+        size: usize,
+        metadata: EntryMetadata,
+        _compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        let mut header = metadata_header(size, metadata);
+        add_from_reader(self, name, &mut header, &mut source, read_tracker)
+    }
+
     fn add_from_file(
         &mut self,
         name: &Path,
@@ -83,6 +96,19 @@ where
         let mut source = io::BufReader::new(File::open(source)?);
         add_from_reader(self, name, &mut header, &mut source, read_tracker)
     }
+
+    fn add_symlink(&mut self, name: &Path, target: &Path, metadata: Option<EntryMetadata>) -> io::Result<()> {
+        let mut header = match metadata {
+            Some(metadata) => metadata_header(0, metadata),
+            None => {
+                let mut header = reader_header(0)?;
+                header.set_mode(0o777);
+                header
+            }
+        };
+        header.set_entry_type(EntryType::Symlink);
+        self.append_link(&mut header, name, target)
+    }
 }
 
 // Utils
@@ -114,6 +140,17 @@ fn reader_header(size: usize) -> io::Result<Header> {
     Ok(header)
 }
 
+fn metadata_header(size: usize, metadata: EntryMetadata) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_mtime(metadata.modified);
+    header.set_size(size as u64);
+    header.set_mode(metadata.permissions);
+    header.set_uid(0);
+    header.set_gid(0);
+    header.set_cksum();
+    header
+}
+
 fn file_header(path: &Path) -> io::Result<Header> {
     let metadata = path.metadata()?;
     let mut header = Header::new_gnu();
@@ -1,3 +1,4 @@
+mod reproducible;
 mod seek_writer;
 mod stream_writer;
 mod wrapper;
@@ -5,4 +6,4 @@ mod writer;
 mod writers;
 
 #[allow(unused_imports)]
-pub use {seek_writer::*, stream_writer::*, wrapper::*, writer::*, writers::*};
+pub use {reproducible::*, seek_writer::*, stream_writer::*, wrapper::*, writer::*, writers::*};
@@ -0,0 +1,84 @@
+use super::{
+    super::super::{compression_level::*, reproducible::*, tracker::*},
+    writer::*,
+};
+
+use std::{fs::*, io, os::unix::fs::*, path::*};
+
+//
+// ReproducibleArchiveWriter
+//
+
+/// [ArchiveWriter] that creates all entries with [Reproducible] metadata.
+pub struct ReproducibleArchiveWriter<'archive> {
+    /// Archive.
+    pub archive: ArchiveWriterRef<'archive>,
+
+    /// Reproducible.
+    pub reproducible: Reproducible,
+}
+
+impl<'archive> ReproducibleArchiveWriter<'archive> {
+    /// Constructor.
+    pub fn new(archive: ArchiveWriterRef<'archive>, reproducible: Reproducible) -> Self {
+        Self { archive, reproducible }
+    }
+
+    fn metadata(&self, permissions: u32) -> EntryMetadata {
+        EntryMetadata::new(self.reproducible.permissions(permissions), self.reproducible.modified)
+    }
+}
+
+impl<'archive> ArchiveWriter for ReproducibleArchiveWriter<'archive> {
+    fn add_from_reader(
+        &mut self,
+        name: &Path,
+        source: Box<&mut dyn io::Read>,
+        size: usize,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        let metadata = self.metadata(DEFAULT_ARCHIVE_ENTRY_PERMISSIONS);
+        self.archive.add_from_reader_with_metadata(name, source, size, metadata, compression_level, read_tracker)
+    }
+
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        source: Box<&mut dyn io::Read>,
+        size: usize,
+        metadata: EntryMetadata,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        let metadata = self.metadata(metadata.permissions);
+        self.archive.add_from_reader_with_metadata(name, source, size, metadata, compression_level, read_tracker)
+    }
+
+    fn add_from_file(
+        &mut self,
+        name: &Path,
+        source: &Path,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        let file_metadata = source.metadata()?;
+        let metadata = self.metadata(file_metadata.permissions().mode());
+
+        let mut source = io::BufReader::new(File::open(source)?);
+        self.archive.add_from_reader_with_metadata(
+            name,
+            Box::new(&mut source),
+            file_metadata.len() as usize,
+            metadata,
+            compression_level,
+            read_tracker,
+        )
+    }
+
+    fn add_symlink(&mut self, name: &Path, target: &Path, _metadata: Option<EntryMetadata>) -> io::Result<()> {
+        // Symbolic link permissions are not used, so we don't normalize them
+        let metadata = EntryMetadata::new(0o777, self.reproducible.modified);
+        self.archive.add_symlink(name, target, Some(metadata))
+    }
+}
@@ -55,6 +55,26 @@ impl ArchiveWriter for ArchiveWriterWrapper {
         }
     }
 
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        source: Box<&mut dyn io::Read>,
+        size: usize,
+        metadata: EntryMetadata,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        match self {
+            Self::Stream(writer) => {
+                writer.add_from_reader_with_metadata(name, source, size, metadata, compression_level, read_tracker)
+            }
+
+            Self::Seek(writer) => {
+                writer.add_from_reader_with_metadata(name, source, size, metadata, compression_level, read_tracker)
+            }
+        }
+    }
+
     fn add_from_file(
         &mut self,
         name: &Path,
@@ -67,6 +87,13 @@ impl ArchiveWriter for ArchiveWriterWrapper {
             Self::Seek(writer) => writer.add_from_file(name, source, compression_level, read_tracker),
         }
     }
+
+    fn add_symlink(&mut self, name: &Path, target: &Path, metadata: Option<EntryMetadata>) -> io::Result<()> {
+        match self {
+            Self::Stream(writer) => writer.add_symlink(name, target, metadata),
+            Self::Seek(writer) => writer.add_symlink(name, target, metadata),
+        }
+    }
 }
 
 impl From<ArchiveStreamWriter> for ArchiveWriterWrapper {
@@ -26,6 +26,17 @@ pub trait ArchiveWriter {
         read_tracker: Option<&ReadTrackerRef>,
     ) -> io::Result<()>;
 
+    /// Create a new archive entry from a reader with explicit metadata.
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        source: Box<&mut dyn io::Read>,
+        size: usize,
+        metadata: EntryMetadata,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()>;
+
     /// Create a new archive entry from a file.
     fn add_from_file(
         &mut self,
@@ -34,6 +45,32 @@ pub trait ArchiveWriter {
         compression_level: Option<CompressionLevel>,
         read_tracker: Option<&ReadTrackerRef>,
     ) -> io::Result<()>;
+
+    /// Create a new symbolic link archive entry.
+    fn add_symlink(&mut self, name: &Path, target: &Path, metadata: Option<EntryMetadata>) -> io::Result<()>;
+}
+
+//
+// EntryMetadata
+//
+
+/// Archive entry metadata.
+///
+/// Entries with explicit metadata have no owner.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EntryMetadata {
+    /// Unix permissions.
+    pub permissions: u32,
+
+    /// Modification time in seconds since the Unix epoch.
+    pub modified: u64,
+}
+
+impl EntryMetadata {
+    /// Constructor.
+    pub fn new(permissions: u32, modified: u64) -> Self {
+        Self { permissions, modified }
+    }
 }
 
 //
@@ -68,6 +68,20 @@ impl ArchiveWriter for ArchiveWriterT {
         })
     }
 
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        source: Box<&mut dyn io::Read>,
+        size: usize,
+        metadata: EntryMetadata,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        self.with_dependent_mut(|_writer, archive| {
+            archive.add_from_reader_with_metadata(name, source, size, metadata, compression_level, read_tracker)
+        })
+    }
+
     fn add_from_file(
         &mut self,
         name: &Path,
@@ -77,4 +91,8 @@ impl ArchiveWriter for ArchiveWriterT {
     ) -> io::Result<()> {
         self.with_dependent_mut(|_writer, archive| archive.add_from_file(name, source, compression_level, read_tracker))
     }
+
+    fn add_symlink(&mut self, name: &Path, target: &Path, metadata: Option<EntryMetadata>) -> io::Result<()> {
+        self.with_dependent_mut(|_writer, archive| archive.add_symlink(name, target, metadata))
+    }
 }
@@ -1,5 +1,5 @@
 use super::super::{
-    super::{compression_level::*, tracker::*},
+    super::{compression_level::*, reproducible::*, tracker::*},
     writer::*,
 };
 
@@ -45,6 +45,20 @@ where
         add_from_reader(self, name, options, &mut source, read_tracker)
     }
 
+    fn add_from_reader_with_metadata(
+        &mut self,
+        name: &Path,
+        mut source: Box<&mut dyn io::Read>,
+        _size: usize,
+        metadata: EntryMetadata,
+        compression_level: Option<CompressionLevel>,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> io::Result<()> {
+        let modified = UNIX_EPOCH + Duration::from_secs(metadata.modified);
+        let options = zip_options(metadata.permissions, modified, compression_level)?;
+        add_from_reader(self, name, options, &mut source, read_tracker)
+    }
+
     fn add_from_file(
         &mut self,
         name: &Path,
@@ -60,6 +74,18 @@ where
         let mut source = io::BufReader::new(File::open(source)?);
         add_from_reader(self, name, options, &mut source, read_tracker)
     }
+
+    fn add_symlink(&mut self, name: &Path, target: &Path, metadata: Option<EntryMetadata>) -> io::Result<()> {
+        let options = match metadata {
+            Some(metadata) => {
+                zip_options(metadata.permissions, UNIX_EPOCH + Duration::from_secs(metadata.modified), None)?
+            }
+
+            None => zip_options(0o777, SystemTime::now(), None)?,
+        };
+
+        Ok(self.add_symlink_from_path(name, target, options)?)
+    }
 }
 
 // Utils
@@ -107,6 +133,11 @@ fn zip_options<'options>(
 }
 
 fn to_zip_datetime(system_time: SystemTime) -> io::Result<DateTime> {
+    // ZIP can't represent timestamps before 1980 (which is our default reproducible timestamp)
+    if system_time < UNIX_EPOCH + Duration::from_secs(DEFAULT_REPRODUCIBLE_MODIFIED) {
+        return Ok(DateTime::default());
+    }
+
     let modified: chrono::DateTime<chrono::Utc> = system_time.into();
     modified.naive_utc().try_into().map_err(io::Error::other)
 }
@@ -0,0 +1,131 @@
+use super::{
+    super::{errors::*, reader::*},
+    archive::*,
+    created::*,
+    creator::*,
+    tracker::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{io, path::*},
+};
+
+impl CsarCreator {
+    /// Convert a CSAR to another format.
+    ///
+    /// Entries are streamed from the source CSAR, in order, so that large artifacts are not loaded
+    /// into memory. The `TOSCA.meta` is copied as is and added first. Entries keep their
+    /// permissions and timestamps (unless we are in [reproducible](CsarCreator::reproducible)
+    /// mode).
+    ///
+    /// If file is [None] will output to [stdout](std::io::stdout).
+    ///
+    /// If the format is not provided it will be selected according to the archive extension or default
+    /// to [Format::Tarball](super::Format::Tarball).
+    ///
+    /// Note that only file entries are supported. Other entries (e.g. symbolic links) are skipped.
+    pub fn convert_csar<ProblemReceiverT>(
+        &self,
+        source: &CsarReader,
+        file: Option<&Path>,
+        dry_run: bool,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<CreatedCsar>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        if let Some(file) = file
+            && file.exists()
+            && file.canonicalize()? == source.path.canonicalize()?
+        {
+            problems.give(
+                InvalidError::as_problem(format!("cannot convert into the source CSAR: {:?}", file.display()))
+                    .via(CsarError),
+            )?;
+            return Ok(None);
+        }
+
+        let entries = source.entries()?;
+
+        let Some((tosca_meta_location, tosca_meta)) = source.tosca_meta(&entries, problems)? else {
+            return Ok(None);
+        };
+
+        let format = give_unwrap!(self.target_format(file), problems, None);
+
+        let mut size = 0;
+        for entry in &entries {
+            match entry.entry_type {
+                CsarEntryType::File => size += entry.size,
+                CsarEntryType::Directory => {}
+                _ => tracing::warn!("skipping {}: {:?}", entry.entry_type, entry.path.display()),
+            }
+        }
+
+        if dry_run {
+            return Ok(Some(CreatedCsar::new(tosca_meta, format, self.compression_level, Some(size))));
+        }
+
+        let mut archive = self.create_archive(file, format)?;
+
+        let read_tracker = self.read_tracker.as_ref();
+        if let Some(read_tracker) = read_tracker {
+            read_tracker.initialize(size);
+        }
+
+        // Add TOSCA.meta first
+        // (It's important for tarballs that it's the first file added!)
+
+        if let Some(entry) = entries.iter().find(|entry| entry.path == tosca_meta_location) {
+            tracing::debug!("adding: {:?}", entry.path.display());
+            let content = source.read_entry(&entry.path)?;
+            self.copy_entry(&mut archive, entry, &mut content.as_slice(), read_tracker)?;
+        }
+
+        // Copy entries
+
+        source.visit_files(|entry, content| {
+            if entry.path != tosca_meta_location {
+                tracing::debug!("copying: {:?}", entry.path.display());
+                self.copy_entry(&mut archive, entry, content, read_tracker)?;
+            }
+
+            Ok(())
+        })?;
+
+        if let Some(read_tracker) = read_tracker {
+            tracing::info!("finishing read tracker");
+            read_tracker.finish(true);
+        }
+
+        Ok(Some(CreatedCsar::new(tosca_meta, format, self.compression_level, Some(size))))
+    }
+
+    /// Copy an entry from a CSAR into an archive.
+    ///
+    /// The entry's permissions and timestamp are kept if it has them.
+    pub(crate) fn copy_entry(
+        &self,
+        archive: &mut ArchiveWriterRef,
+        entry: &CsarEntry,
+        content: &mut dyn io::Read,
+        read_tracker: Option<&ReadTrackerRef>,
+    ) -> Result<(), Problem> {
+        let size = entry.size as usize;
+        match (entry.permissions, entry.modified) {
+            (Some(permissions), Some(modified)) => archive.add_from_reader_with_metadata(
+                &entry.path,
+                Box::new(content),
+                size,
+                EntryMetadata::new(permissions, modified),
+                self.compression_level,
+                read_tracker,
+            )?,
+
+            _ => archive.add_from_reader(&entry.path, Box::new(content), size, self.compression_level, read_tracker)?,
+        }
+
+        Ok(())
+    }
+}
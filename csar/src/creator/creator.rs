@@ -1,6 +1,12 @@
-use super::{super::tosca_meta::*, compression_level::*, format::*, tracker::*};
+use super::{super::tosca_meta::*, compression_level::*, format::*, reproducible::*, tracker::*};
 
-use std::mem::*;
+#[cfg(feature = "manifest")]
+use super::super::manifest::*;
+
+#[cfg(feature = "signature")]
+use super::super::signature::*;
+
+use std::{collections::*, mem::*, path::*};
 
 //
 // CsarCreator
@@ -28,6 +34,33 @@ pub struct CsarCreator {
 
     /// Reader tracker.
     pub read_tracker: Option<ReadTrackerRef>,
+
+    /// Reproducible mode.
+    pub reproducible: Option<Reproducible>,
+
+    /// Files to include, relative to the source directory.
+    ///
+    /// When [None] will include all files in the directory.
+    pub files: Option<BTreeSet<PathBuf>>,
+
+    /// Include patterns (gitignore syntax).
+    ///
+    /// When empty will include all files in the directory.
+    pub include: Vec<String>,
+
+    /// Exclude patterns (gitignore syntax).
+    pub exclude: Vec<String>,
+
+    /// Whether to use the ignore file in the directory (see [CSAR_IGNORE_FILE](super::CSAR_IGNORE_FILE)).
+    pub ignore_file: bool,
+
+    /// ETSI SOL004 manifest mode.
+    #[cfg(feature = "manifest")]
+    pub sol004: Option<Sol004>,
+
+    /// Signer.
+    #[cfg(feature = "signature")]
+    pub signer: Option<CsarSigner>,
 }
 
 impl CsarCreator {
@@ -49,6 +82,15 @@ impl CsarCreator {
             additional_other_definitions,
             max_columns,
             read_tracker,
+            reproducible: None,
+            files: None,
+            include: Default::default(),
+            exclude: Default::default(),
+            ignore_file: true,
+            #[cfg(feature = "manifest")]
+            sol004: None,
+            #[cfg(feature = "signature")]
+            signer: None,
         }
     }
 
@@ -85,6 +127,15 @@ impl Default for CsarCreator {
             additional_other_definitions: Default::default(),
             max_columns: Some(80),
             read_tracker: None,
+            reproducible: None,
+            files: None,
+            include: Default::default(),
+            exclude: Default::default(),
+            ignore_file: true,
+            #[cfg(feature = "manifest")]
+            sol004: None,
+            #[cfg(feature = "signature")]
+            signer: None,
         }
     }
 }
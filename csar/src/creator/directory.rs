@@ -3,9 +3,13 @@ use super::{
     archive::*,
     created::*,
     creator::*,
+    filter::*,
     format::*,
 };
 
+#[cfg(feature = "signature")]
+use super::super::signature::*;
+
 use {
     problemo::{common::*, *},
     std::{fs::*, io::Write, path::*},
@@ -85,6 +89,9 @@ impl CsarCreator {
             }
         }
 
+        #[cfg(feature = "manifest")]
+        let sol004_manifest_location = self.sol004_manifest_location(&mut tosca_meta, problems)?;
+
         let tosca_meta_string = tosca_meta.stringify(self.max_columns)?;
 
         if write_tosca_meta {
@@ -94,38 +101,64 @@ impl CsarCreator {
             return Ok(Some(CreatedCsar::new(tosca_meta, Format::Tarball, None, None)));
         }
 
-        // Determine format
+        // Filter
 
-        let format = match self.format {
-            Some(format) => format,
+        let filter = give_unwrap!(self.file_filter(directory), problems, None);
 
-            None => match file {
-                Some(file) => match Format::from_extension(file) {
-                    Some(format) => format,
+        // Generated files
+        // (Existing ones in the directory will be skipped)
 
-                    None => {
-                        problems.give(InvalidError::as_problem("must specify format").via(CsarError))?;
-                        return Ok(None);
-                    }
-                },
+        #[allow(unused_mut)]
+        let mut generated_files = Vec::<(PathBuf, String)>::default();
 
-                None => Format::Tarball, // for stdout
-            },
+        #[cfg(feature = "manifest")]
+        let locations = {
+            let mut locations = locations;
+
+            if let Some(location) = &sol004_manifest_location {
+                locations.push(location.clone());
+            }
+
+            #[cfg(feature = "signature")]
+            if self.signer.is_some() {
+                locations.extend(signature_locations());
+            }
+
+            let tosca_meta_file = (locations.get(0).expect("not empty").clone(), tosca_meta_string.clone());
+
+            if let Some(location) = &sol004_manifest_location
+                && let Some(file) =
+                    self.sol004_manifest_file(location, directory, &[tosca_meta_file.clone()], &locations, problems)?
+            {
+                generated_files.push(file);
+            }
+
+            #[cfg(feature = "signature")]
+            {
+                let mut generated = vec![tosca_meta_file];
+                generated.extend(generated_files.iter().cloned());
+                if let Some(signature_files) = self.signature_files(directory, &generated, &locations, problems)? {
+                    generated_files.extend(signature_files);
+                }
+            }
+
+            locations
         };
 
+        // Determine format
+
+        let format = give_unwrap!(self.target_format(file), problems, None);
+
         // Create archive
 
-        let mut archive: Option<ArchiveWriterRef> = if dry_run {
-            None
-        } else {
-            Some(give_unwrap!(create_archive_file_or_stdout(file, format, self.compression_level), problems))
-        };
+        let mut archive: Option<ArchiveWriterRef> =
+            if dry_run { None } else { Some(give_unwrap!(self.create_archive(file, format), problems)) };
 
         // Initialize read tracker and size
 
         let (read_tracker, size) = match &self.read_tracker {
             Some(read_tracker) => {
-                let size = directory_size(directory, directory_components, &locations, problems)?;
+                let size = self.directory_size(directory, directory_components, &locations, &filter, problems)?;
                 read_tracker.initialize(size);
                 (Some(read_tracker), Some(size))
             }
@@ -147,6 +180,11 @@ impl CsarCreator {
                 ),
                 problems
             );
+
+            for (name, content) in &generated_files {
+                tracing::debug!("adding: {:?}", name.display());
+                give_unwrap!(archive.add_string(name, content, self.compression_level, read_tracker), problems);
+            }
         }
 
         // Add directory to CSAR
@@ -160,7 +198,7 @@ impl CsarCreator {
                 let path = entry.path();
                 let name = path_to_name(path, directory_components);
 
-                if locations.contains(&name) {
+                if locations.contains(&name) || !filter.includes(&name) {
                     tracing::debug!("skipping: {:?}", path.display());
                     continue;
                 }
@@ -184,38 +222,74 @@ impl CsarCreator {
 
 // Utils
 
-fn directory_size<ProblemReceiverT>(
-    directory: &Path,
-    directory_components: usize,
-    locations: &Vec<PathBuf>,
-    problems: &mut ProblemReceiverT,
-) -> Result<u64, Problem>
-where
-    ProblemReceiverT: ProblemReceiver,
-{
-    let mut size = 0;
-
-    for entry in files_in_directory(&directory, false) {
-        if let Some(entry) = entry.give_ok(problems)? {
-            if entry.file_type().is_dir() {
-                continue;
-            }
+impl CsarCreator {
+    /// Target format.
+    ///
+    /// If the format is not provided it will be selected according to the archive extension or default
+    /// to [Format::Tarball] for [stdout](std::io::stdout).
+    pub fn target_format(&self, file: Option<&Path>) -> Result<Format, Problem> {
+        match self.format {
+            Some(format) => Ok(format),
 
-            let name = path_to_name(entry.path(), directory_components);
-            if locations.contains(&name) {
-                continue;
-            }
+            None => match file {
+                Some(file) => Format::from_extension(file)
+                    .ok_or_else(|| InvalidError::as_problem("must specify format").via(CsarError)),
 
-            if let Some(metadata) = entry.metadata().give_ok(problems)? {
-                size += metadata.len();
-            }
+                None => Ok(Format::Tarball), // for stdout
+            },
         }
     }
 
-    Ok(size)
+    /// Create an archive file or write to stdout.
+    ///
+    /// See [create_archive_file_or_stdout].
+    pub fn create_archive<'path>(
+        &self,
+        file: Option<&'path Path>,
+        format: Format,
+    ) -> Result<ArchiveWriterRef<'path>, Problem> {
+        let archive = create_archive_file_or_stdout(file, format, self.compression_level)?;
+        Ok(match self.reproducible {
+            Some(reproducible) => Box::new(ReproducibleArchiveWriter::new(archive, reproducible)),
+            None => archive,
+        })
+    }
+
+    fn directory_size<ProblemReceiverT>(
+        &self,
+        directory: &Path,
+        directory_components: usize,
+        locations: &Vec<PathBuf>,
+        filter: &FileFilter,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<u64, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let mut size = 0;
+
+        for entry in files_in_directory(&directory, false) {
+            if let Some(entry) = entry.give_ok(problems)? {
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                let name = path_to_name(entry.path(), directory_components);
+                if locations.contains(&name) || !filter.includes(&name) {
+                    continue;
+                }
+
+                if let Some(metadata) = entry.metadata().give_ok(problems)? {
+                    size += metadata.len();
+                }
+            }
+        }
+
+        Ok(size)
+    }
 }
 
-fn files_in_directory(directory: &Path, sort: bool) -> walkdir::WalkDir {
+pub(crate) fn files_in_directory(directory: &Path, sort: bool) -> walkdir::WalkDir {
     let mut walkdir = walkdir::WalkDir::new(&directory).follow_links(true);
     if sort {
         walkdir = walkdir.sort_by_file_name();
@@ -223,6 +297,6 @@ fn files_in_directory(directory: &Path, sort: bool) -> walkdir::WalkDir {
     walkdir
 }
 
-fn path_to_name(path: &Path, count: usize) -> PathBuf {
+pub(crate) fn path_to_name(path: &Path, count: usize) -> PathBuf {
     path.components().skip(count).collect()
 }
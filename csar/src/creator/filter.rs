@@ -0,0 +1,114 @@
+use super::{super::errors::*, creator::*};
+
+use {
+    ignore::gitignore::*,
+    problemo::{common::*, *},
+    std::{collections::*, path::*},
+};
+
+/// Ignore file name.
+///
+/// It uses the gitignore syntax and is located in the root of the source directory.
+pub const CSAR_IGNORE_FILE: &str = ".csarignore";
+
+//
+// FileFilter
+//
+
+/// Filter for the files in a source directory.
+///
+/// Patterns use the gitignore syntax and paths are relative to the source directory.
+#[derive(Clone, Debug, Default)]
+pub struct FileFilter {
+    /// Files to include.
+    ///
+    /// When [None] will include all files.
+    pub files: Option<BTreeSet<PathBuf>>,
+
+    /// Include patterns.
+    ///
+    /// When [None] will include all files.
+    pub include: Option<Gitignore>,
+
+    /// Exclude patterns.
+    pub exclude: Option<Gitignore>,
+}
+
+impl FileFilter {
+    /// Whether to include a file.
+    pub fn includes(&self, name: &Path) -> bool {
+        if let Some(files) = &self.files
+            && !files.contains(name)
+        {
+            return false;
+        }
+
+        if let Some(include) = &self.include
+            && !include.matched_path_or_any_parents(name, false).is_ignore()
+        {
+            return false;
+        }
+
+        if let Some(exclude) = &self.exclude
+            && exclude.matched_path_or_any_parents(name, false).is_ignore()
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl CsarCreator {
+    /// File filter for a source directory.
+    ///
+    /// Combines [files](CsarCreator::files), [include](CsarCreator::include),
+    /// [exclude](CsarCreator::exclude), and, if [ignore_file](CsarCreator::ignore_file) is true,
+    /// the [CSAR_IGNORE_FILE] in the directory (which is itself excluded).
+    pub fn file_filter(&self, directory: &Path) -> Result<FileFilter, Problem> {
+        let include = if self.include.is_empty() {
+            None
+        } else {
+            let mut builder = GitignoreBuilder::new(directory);
+            for pattern in &self.include {
+                add_pattern(&mut builder, pattern)?;
+            }
+            Some(build(builder)?)
+        };
+
+        let mut builder = GitignoreBuilder::new(directory);
+
+        if self.ignore_file {
+            let ignore_file = directory.join(CSAR_IGNORE_FILE);
+            if ignore_file.is_file() {
+                tracing::info!("using {:?}", ignore_file.display());
+                if let Some(error) = builder.add(&ignore_file) {
+                    return Err(ignore_problem(error));
+                }
+                add_pattern(&mut builder, &format!("/{}", CSAR_IGNORE_FILE))?;
+            }
+        }
+
+        for pattern in &self.exclude {
+            add_pattern(&mut builder, pattern)?;
+        }
+
+        let exclude = build(builder)?;
+        let exclude = if exclude.is_empty() { None } else { Some(exclude) };
+
+        Ok(FileFilter { files: self.files.clone(), include, exclude })
+    }
+}
+
+fn add_pattern(builder: &mut GitignoreBuilder, pattern: &str) -> Result<(), Problem> {
+    builder.add_line(None, pattern).map_err(ignore_problem)?;
+    Ok(())
+}
+
+fn build(builder: GitignoreBuilder) -> Result<Gitignore, Problem> {
+    builder.build().map_err(ignore_problem)
+}
+
+fn ignore_problem(error: ignore::Error) -> Problem {
+    InvalidError::as_problem(format!("pattern: {}", error)).via(CsarError)
+}
@@ -0,0 +1,112 @@
+use super::{
+    super::{manifest::*, tosca_meta::*},
+    creator::*,
+    directory::{files_in_directory, path_to_name},
+};
+
+use {
+    problemo::*,
+    std::{fs::*, path::*},
+};
+
+impl CsarCreator {
+    /// ETSI SOL004 manifest file for a directory.
+    ///
+    /// Sets `ETSI-Entry-Manifest` in the TOSCA meta and validates the manifest metadata.
+    ///
+    /// Returns the manifest location or [None] if we are not in SOL004 mode. Call
+    /// [sol004_manifest_file](CsarCreator::sol004_manifest_file) after stringifying the TOSCA
+    /// meta to create the file.
+    pub fn sol004_manifest_location<ProblemReceiverT>(
+        &self,
+        tosca_meta: &mut ToscaMeta,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<PathBuf>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(sol004) = &self.sol004 else {
+            return Ok(None);
+        };
+
+        validate_sol004_metadata(&sol004.metadata, problems)?;
+
+        let location = match tosca_meta.sol004_manifest_location() {
+            Some(location) => location,
+            None => match &tosca_meta.entry_definitions {
+                Some(entry_definitions) => sol004_manifest_location(entry_definitions),
+                None => return Ok(None),
+            },
+        };
+
+        tosca_meta.set_sol004_manifest_location(&location)?;
+        Ok(Some(location))
+    }
+
+    /// ETSI SOL004 manifest file for a directory.
+    ///
+    /// Returns the name and content of the manifest, or [None] if we are not in SOL004 mode.
+    pub fn sol004_manifest_file<ProblemReceiverT>(
+        &self,
+        location: &Path,
+        directory: &Path,
+        generated: &[(PathBuf, String)],
+        skip: &Vec<PathBuf>,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<(PathBuf, String)>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(sol004) = &self.sol004 else {
+            return Ok(None);
+        };
+
+        let mut manifest = sol004.new_manifest();
+        if !self.add_to_manifest(&mut manifest, sol004.algorithm, directory, generated, skip, problems)? {
+            return Ok(None);
+        }
+
+        Ok(Some((location.into(), manifest.stringify())))
+    }
+
+    /// Add the digests of generated files and the files in a directory to a manifest.
+    ///
+    /// Returns false if a file could not be read.
+    pub(crate) fn add_to_manifest<ProblemReceiverT>(
+        &self,
+        manifest: &mut Manifest,
+        algorithm: DigestAlgorithm,
+        directory: &Path,
+        generated: &[(PathBuf, String)],
+        skip: &Vec<PathBuf>,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<bool, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        for (name, content) in generated {
+            give_unwrap!(manifest.add(name, algorithm, &mut content.as_bytes()), problems, false);
+        }
+
+        let filter = give_unwrap!(self.file_filter(directory), problems, false);
+        let directory_components = directory.components().count();
+
+        for entry in files_in_directory(directory, false) {
+            if let Some(entry) = entry.give_ok(problems)? {
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                let name = path_to_name(entry.path(), directory_components);
+                if skip.contains(&name) || !filter.includes(&name) {
+                    continue;
+                }
+
+                let mut file = give_unwrap!(File::open(entry.path()), problems, false);
+                give_unwrap!(manifest.add(name, algorithm, &mut file), problems, false);
+            }
+        }
+
+        Ok(true)
+    }
+}
@@ -1,11 +1,26 @@
 mod archive;
 mod compression_level;
+#[cfg(feature = "reader")]
+mod convert;
 mod created;
 mod creator;
 mod directory;
-mod format;
+mod filter;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod reproducible;
+#[cfg(feature = "signature")]
+mod signature;
 mod tracker;
+#[cfg(feature = "reader")]
+mod update;
 mod writer;
 
+use super::format;
+
+#[allow(unused_imports)]
+pub use {archive::*, compression_level::*, created::*, creator::*, filter::*, format::*, reproducible::*, tracker::*};
+
+#[cfg(feature = "reader")]
 #[allow(unused_imports)]
-pub use {archive::*, compression_level::*, created::*, creator::*, format::*, tracker::*};
+pub use update::*;
@@ -0,0 +1,65 @@
+use super::super::errors::*;
+
+use {
+    problemo::{common::*, *},
+    std::env,
+};
+
+/// Environment variable for the reproducible timestamp.
+///
+/// See the [specification](https://reproducible-builds.org/specs/source-date-epoch/).
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Default reproducible timestamp (1980-01-01T00:00:00Z) in seconds since the Unix epoch.
+///
+/// This is the earliest timestamp supported by ZIP.
+pub const DEFAULT_REPRODUCIBLE_MODIFIED: u64 = 315532800;
+
+//
+// Reproducible
+//
+
+/// Reproducible archive mode.
+///
+/// All entries get the same timestamp, normalized permissions, and no owner, so that the same
+/// content will always result in the same archive bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reproducible {
+    /// Modification time for all entries in seconds since the Unix epoch.
+    pub modified: u64,
+}
+
+impl Reproducible {
+    /// Constructor.
+    pub fn new(modified: u64) -> Self {
+        Self { modified }
+    }
+
+    /// From the `SOURCE_DATE_EPOCH` environment variable, falling back to
+    /// [DEFAULT_REPRODUCIBLE_MODIFIED].
+    pub fn from_environment() -> Result<Self, Problem> {
+        match env::var(SOURCE_DATE_EPOCH) {
+            Ok(modified) => match modified.trim().parse() {
+                Ok(modified) => Ok(Self::new(modified)),
+                Err(_) => {
+                    Err(MalformedError::as_problem(format!("{}: {:?}", SOURCE_DATE_EPOCH, modified)).via(CsarError))
+                }
+            },
+
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Normalized permissions.
+    ///
+    /// Executables get 0o755 and everything else gets 0o644.
+    pub fn permissions(&self, permissions: u32) -> u32 {
+        if permissions & 0o111 != 0 { 0o755 } else { 0o644 }
+    }
+}
+
+impl Default for Reproducible {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPRODUCIBLE_MODIFIED)
+    }
+}
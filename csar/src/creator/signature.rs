@@ -0,0 +1,44 @@
+use super::{
+    super::{manifest::*, signature::*},
+    creator::*,
+};
+
+use {problemo::*, std::path::*};
+
+impl CsarCreator {
+    /// Signature files for a directory.
+    ///
+    /// The generated files (e.g. `TOSCA.meta`) are listed in the manifest together with the
+    /// files in the directory.
+    ///
+    /// Returns the names and contents of the manifest, its signature, and the certificate, or
+    /// [None] if we have no signer.
+    pub fn signature_files<ProblemReceiverT>(
+        &self,
+        directory: &Path,
+        generated: &[(PathBuf, String)],
+        skip: &Vec<PathBuf>,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<Vec<(PathBuf, String)>>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(signer) = &self.signer else {
+            return Ok(None);
+        };
+
+        let mut manifest = Manifest::default();
+        if !self.add_to_manifest(&mut manifest, DigestAlgorithm::SHA256, directory, generated, skip, problems)? {
+            return Ok(None);
+        }
+
+        let manifest = manifest.stringify();
+        let signature = signer.sign(&manifest);
+
+        Ok(Some(vec![
+            (MANIFEST_LOCATION.into(), manifest),
+            (SIGNATURE_LOCATION.into(), signature),
+            (CERTIFICATE_LOCATION.into(), signer.certificate()),
+        ]))
+    }
+}
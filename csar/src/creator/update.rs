@@ -0,0 +1,634 @@
+use super::{
+    super::{errors::*, reader::*, tosca_meta::*},
+    archive::*,
+    compression_level::*,
+    creator::*,
+    format::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{collections::*, ffi::*, fs::*, io, path::*},
+};
+
+#[cfg(feature = "manifest")]
+use super::super::manifest::*;
+
+#[cfg(feature = "signature")]
+use super::super::signature::*;
+
+//
+// CsarChanges
+//
+
+/// Changes for [CsarCreator::update_csar].
+#[derive(Clone, Debug, Default)]
+pub struct CsarChanges {
+    /// Files to add or replace.
+    ///
+    /// The keys are paths in the archive and the values are paths of source files.
+    pub files: BTreeMap<PathBuf, PathBuf>,
+
+    /// Entries to remove.
+    ///
+    /// Removing a directory removes all the entries in it.
+    pub remove: BTreeSet<PathBuf>,
+}
+
+impl CsarChanges {
+    /// Whether an entry is removed.
+    pub fn removes(&self, path: &Path) -> bool {
+        self.remove.iter().any(|remove| path.starts_with(remove))
+    }
+}
+
+impl CsarCreator {
+    /// Update a CSAR by creating a new one with changes.
+    ///
+    /// Entries are streamed from the source CSAR, in order, so that large artifacts are not loaded
+    /// into memory. Replaced files keep their position and added files come last, followed by
+    /// symbolic links. Entries keep their permissions and timestamps (unless we are in
+    /// [reproducible](CsarCreator::reproducible) mode).
+    ///
+    /// The `TOSCA.meta` keeps its location. `Created-By` and `Entry-Definitions` are set if
+    /// provided, and [additional_other_definitions](CsarCreator::additional_other_definitions) are
+    /// added to `Other-Definitions`.
+    ///
+    /// If the format is not provided the source format is used. If file is [None] the source
+    /// CSAR will be replaced. The file cannot be the source CSAR itself.
+    ///
+    /// The ETSI SOL004 manifest, if there is one, is regenerated with its original metadata. The
+    /// manifest signature files are regenerated if we have a [signer](CsarCreator::signer),
+    /// otherwise they are removed (with a warning) because they would no longer be valid.
+    ///
+    /// Note that only file, directory, and symbolic link entries are supported. Other entries
+    /// (e.g. hard links) are skipped.
+    ///
+    /// The [compression level](CsarCreator::compression_level) is used for all written entries. To
+    /// keep the source CSAR's compression level, set it with [detect_compression_level].
+    ///
+    /// Returns the new TOSCA meta if the CSAR was updated.
+    pub fn update_csar<ProblemReceiverT>(
+        &self,
+        source: &CsarReader,
+        file: Option<&Path>,
+        changes: &CsarChanges,
+        dry_run: bool,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<ToscaMeta>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        // To update in place the file should be None (we write to a temporary file first)
+        if let Some(file) = file
+            && file.exists()
+            && file.canonicalize()? == source.path.canonicalize()?
+        {
+            problems.give(
+                InvalidError::as_problem(format!("cannot write into the source CSAR: {:?}", file.display()))
+                    .via(CsarError),
+            )?;
+            return Ok(None);
+        }
+
+        let entries = source.entries()?;
+
+        let Some((tosca_meta_location, mut tosca_meta)) = source.tosca_meta(&entries, problems)? else {
+            return Ok(None);
+        };
+
+        // Update TOSCA.meta
+
+        if let Some(created_by) = &self.created_by {
+            tosca_meta.created_by = Some(created_by.clone());
+        }
+
+        if let Some(entry_definitions) = &self.entry_definitions {
+            tosca_meta.entry_definitions = Some(entry_definitions.clone());
+        }
+
+        for other_definitions in &self.additional_other_definitions {
+            if !tosca_meta.other_definitions.contains(other_definitions) {
+                tosca_meta.other_definitions.push(other_definitions.clone());
+            }
+        }
+
+        // Validate changes
+        // (We won't write anything if they are invalid)
+
+        let mut valid = true;
+
+        for remove in &changes.remove {
+            match safe_entry_path(remove) {
+                Ok(remove) => {
+                    if !entries
+                        .iter()
+                        .any(|entry| safe_entry_path(&entry.path).is_ok_and(|path| path.starts_with(&remove)))
+                    {
+                        problems.give(NotFoundError::default_as_problem().with(remove).via(CsarError))?;
+                        valid = false;
+                    }
+                }
+
+                Err(problem) => {
+                    problems.give(problem)?;
+                    valid = false;
+                }
+            }
+        }
+
+        let mut files = BTreeMap::default();
+        for (name, source_file) in &changes.files {
+            let name = match safe_entry_path(name) {
+                Ok(name) => name,
+                Err(problem) => {
+                    problems.give(problem)?;
+                    valid = false;
+                    continue;
+                }
+            };
+
+            if tosca_meta_locations().contains(&name) {
+                problems
+                    .give(InvalidError::as_problem("use TOSCA.meta keys to change \"TOSCA.meta\"").via(CsarError))?;
+                valid = false;
+                continue;
+            }
+
+            if !source_file.is_file() {
+                problems.give(NotFoundError::default_as_problem().with(source_file.clone()).via(CsarError))?;
+                valid = false;
+                continue;
+            }
+
+            files.insert(name, source_file.clone());
+        }
+
+        for entry in &entries {
+            if (entry.entry_type == CsarEntryType::Symlink)
+                && !changes.removes(&entry.path)
+                && let Some(link_target) = &entry.link_target
+                && let Err(problem) = safe_link_target(&entry.path, link_target)
+            {
+                problems.give(problem)?;
+                valid = false;
+            }
+        }
+
+        let exists = |path: &Path| files.contains_key(path) || (!changes.removes(path) && has_file(&entries, path));
+
+        for definitions in tosca_meta.entry_definitions.iter().chain(tosca_meta.other_definitions.iter()) {
+            let path = PathBuf::from(definitions);
+            if !exists(&path) {
+                problems.give(NotFoundError::default_as_problem().with(path).via(CsarError))?;
+                valid = false;
+            }
+        }
+
+        if !valid {
+            return Ok(None);
+        }
+
+        for entry in &entries {
+            if !matches!(entry.entry_type, CsarEntryType::File | CsarEntryType::Directory | CsarEntryType::Symlink) {
+                tracing::warn!("skipping {}: {:?}", entry.entry_type, entry.path.display());
+            }
+        }
+
+        // Manifests and signatures would no longer be valid, so we will regenerate or remove them
+        // (Unless they are themselves replaced or removed)
+
+        #[allow(unused_variables)]
+        let exists_unchanged =
+            |path: &Path| !files.contains_key(path) && !changes.removes(path) && has_file(&entries, path);
+
+        #[allow(unused_mut)]
+        let mut stale = Vec::<PathBuf>::default();
+
+        #[cfg(feature = "manifest")]
+        if let Some(location) = tosca_meta.sol004_manifest_location()
+            && exists_unchanged(&location)
+        {
+            stale.push(location);
+        }
+
+        #[cfg(feature = "signature")]
+        for location in signature_locations() {
+            if exists_unchanged(&location) {
+                if self.signer.is_none() {
+                    tracing::warn!("removing because it would no longer be valid: {:?}", location.display());
+                }
+                stale.push(location);
+            }
+        }
+
+        let tosca_meta_string = tosca_meta.stringify(self.max_columns)?;
+
+        if dry_run {
+            return Ok(Some(tosca_meta));
+        }
+
+        #[allow(unused_mut)]
+        let mut generated = Vec::<(PathBuf, String)>::default();
+
+        #[cfg(feature = "manifest")]
+        if let Some(location) = tosca_meta.sol004_manifest_location()
+            && stale.contains(&location)
+        {
+            let manifest = String::from_utf8(source.read_entry(&location)?)
+                .map_err(|_| MalformedError::as_problem("SOL004 manifest is not UTF-8").via(CsarError))?;
+            let manifest = Manifest::read(&manifest, problems)?;
+            let algorithm =
+                manifest.digests.values().next().map(|digest| digest.algorithm).unwrap_or(DigestAlgorithm::SHA256);
+
+            let mut updated_manifest = Manifest { metadata: manifest.metadata, ..Default::default() };
+            add_updated_to_manifest(
+                &mut updated_manifest,
+                algorithm,
+                source,
+                &tosca_meta_location,
+                &tosca_meta_string,
+                &generated,
+                &files,
+                changes,
+                &stale,
+            )?;
+
+            tracing::debug!("regenerating: {:?}", location.display());
+            generated.push((location, updated_manifest.stringify()));
+        }
+
+        #[cfg(feature = "signature")]
+        if let Some(signer) = &self.signer
+            && !signature_locations().iter().any(|location| files.contains_key(location))
+        {
+            let mut manifest = Manifest::default();
+            add_updated_to_manifest(
+                &mut manifest,
+                DigestAlgorithm::SHA256,
+                source,
+                &tosca_meta_location,
+                &tosca_meta_string,
+                &generated,
+                &files,
+                changes,
+                &stale,
+            )?;
+
+            let manifest = manifest.stringify();
+            let signature = signer.sign(&manifest);
+
+            tracing::debug!("regenerating: {:?}", MANIFEST_LOCATION);
+            generated.push((MANIFEST_LOCATION.into(), manifest));
+            generated.push((SIGNATURE_LOCATION.into(), signature));
+            generated.push((CERTIFICATE_LOCATION.into(), signer.certificate()));
+        }
+
+        // Create archive
+        // (When updating in place we write to a temporary file first)
+
+        let format = self.format.unwrap_or(source.format);
+
+        let temporary_file = match file {
+            Some(_) => None,
+            None => Some(temporary_path(&source.path)),
+        };
+
+        let file = match &temporary_file {
+            Some(temporary_file) => temporary_file.as_path(),
+            None => file.expect("file"),
+        };
+
+        let result = self.write_updated_csar(
+            source,
+            &entries,
+            file,
+            format,
+            &tosca_meta_location,
+            &tosca_meta_string,
+            &files,
+            changes,
+            &stale,
+            &generated,
+        );
+
+        if let Some(temporary_file) = &temporary_file {
+            match result {
+                Ok(_) => {
+                    tracing::debug!("replacing: {:?}", source.path.display());
+                    rename(temporary_file, &source.path)?;
+                }
+
+                Err(_) => {
+                    let _ = remove_file(temporary_file);
+                }
+            }
+        }
+
+        result?;
+
+        Ok(Some(tosca_meta))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_updated_csar(
+        &self,
+        source: &CsarReader,
+        entries: &[CsarEntry],
+        file: &Path,
+        format: Format,
+        tosca_meta_location: &Path,
+        tosca_meta: &str,
+        files: &BTreeMap<PathBuf, PathBuf>,
+        changes: &CsarChanges,
+        stale: &[PathBuf],
+        generated: &[(PathBuf, String)],
+    ) -> Result<(), Problem> {
+        let mut archive = self.create_archive(Some(file), format)?;
+
+        // Add TOSCA.meta first
+
+        tracing::debug!("adding: {:?}", tosca_meta_location.display());
+        archive.add_string(tosca_meta_location, tosca_meta, self.compression_level, None)?;
+
+        // Copy entries
+
+        let mut replaced = BTreeSet::default();
+
+        source.visit_files(|entry, content| {
+            if entry.path == tosca_meta_location {
+                return Ok(());
+            }
+
+            if let Some(source_file) = files.get(&entry.path) {
+                tracing::debug!("replacing: {:?}", entry.path.display());
+                archive.add_file(&entry.path, source_file, self.compression_level, None)?;
+                replaced.insert(entry.path.clone());
+                return Ok(());
+            }
+
+            if changes.removes(&entry.path) || stale.contains(&entry.path) {
+                tracing::debug!("removing: {:?}", entry.path.display());
+                return Ok(());
+            }
+
+            tracing::debug!("copying: {:?}", entry.path.display());
+            self.copy_entry(&mut archive, entry, content, None)?;
+
+            Ok(())
+        })?;
+
+        // Add new files
+
+        for (name, source_file) in files {
+            if !replaced.contains(name) {
+                tracing::debug!("adding: {:?}", name.display());
+                archive.add_file(name, source_file, self.compression_level, None)?;
+            }
+        }
+
+        // Add regenerated manifests and signatures
+
+        for (name, content) in generated {
+            tracing::debug!("adding: {:?}", name.display());
+            archive.add_string(name, content, self.compression_level, None)?;
+        }
+
+        // Copy symbolic links
+        // (Last, so that extracting the archive never writes files through them)
+
+        for entry in entries {
+            if (entry.entry_type != CsarEntryType::Symlink)
+                || files.contains_key(&entry.path)
+                || changes.removes(&entry.path)
+            {
+                continue;
+            }
+
+            if let Some(link_target) = &entry.link_target {
+                tracing::debug!("copying: {:?}", entry.path.display());
+                let metadata = entry
+                    .permissions
+                    .zip(entry.modified)
+                    .map(|(permissions, modified)| EntryMetadata::new(permissions, modified));
+                archive.add_symlink(&entry.path, link_target, metadata)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Detect the compression level of a CSAR.
+///
+/// Only some formats record a hint: Gzip tarballs (in the header's extra flags) and ZIP (in the
+/// first entry's DEFLATE flags). Returns [None] if there is no hint or if the format's default
+/// was used.
+pub fn detect_compression_level(source: &CsarReader) -> Result<Option<CompressionLevel>, Problem> {
+    let mut header = [0u8; 10];
+    if io::Read::read_exact(&mut File::open(&source.path)?, &mut header).is_err() {
+        return Ok(None);
+    }
+
+    Ok(match source.format {
+        // See: https://www.rfc-editor.org/rfc/rfc1952#section-2.3.1
+        Format::GzipTarball => match header[8] {
+            2 => Some(CompressionLevel::new_unchecked(10)),
+            4 => Some(CompressionLevel::new_unchecked(2)),
+            _ => None,
+        },
+
+        // See: https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT (section 4.4.4)
+        Format::ZIP if (header[..4] == [0x50, 0x4b, 0x03, 0x04]) && (header[8] == 8) => match (header[6] >> 1) & 0b11 {
+            1 => Some(CompressionLevel::new_unchecked(10)),
+            2 => Some(CompressionLevel::new_unchecked(3)),
+            3 => Some(CompressionLevel::new_unchecked(1)),
+            _ => None,
+        },
+
+        _ => None,
+    })
+}
+
+// Add the digests of the files of the updated CSAR to a manifest
+#[cfg(feature = "manifest")]
+#[allow(clippy::too_many_arguments)]
+fn add_updated_to_manifest(
+    manifest: &mut Manifest,
+    algorithm: DigestAlgorithm,
+    source: &CsarReader,
+    tosca_meta_location: &Path,
+    tosca_meta: &str,
+    generated: &[(PathBuf, String)],
+    files: &BTreeMap<PathBuf, PathBuf>,
+    changes: &CsarChanges,
+    skip: &[PathBuf],
+) -> Result<(), Problem> {
+    manifest.add(tosca_meta_location, algorithm, &mut tosca_meta.as_bytes())?;
+
+    for (name, content) in generated {
+        manifest.add(name, algorithm, &mut content.as_bytes())?;
+    }
+
+    source.visit_files(|entry, content| {
+        if (entry.path == tosca_meta_location)
+            || files.contains_key(&entry.path)
+            || changes.removes(&entry.path)
+            || skip.contains(&entry.path)
+        {
+            return Ok(());
+        }
+
+        manifest.add(&entry.path, algorithm, content)?;
+        Ok(())
+    })?;
+
+    for (name, source_file) in files {
+        manifest.add(name, algorithm, &mut io::BufReader::new(File::open(source_file)?))?;
+    }
+
+    Ok(())
+}
+
+// Temporary file in the same directory, so that it can be renamed
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut file_name = OsString::from(".");
+    if let Some(name) = path.file_name() {
+        file_name.push(name);
+    }
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+#[cfg(all(test, feature = "tarball"))]
+mod tests {
+    use super::*;
+
+    // Fresh temporary directory for a test
+    fn temporary_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("puccini-csar-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&directory);
+        create_dir_all(&directory).expect("create_dir_all");
+        directory
+    }
+
+    // Create a CSAR from files and return its reader
+    fn create_csar(directory: &Path, files: &[(&str, &str)]) -> CsarReader {
+        let source = directory.join("source");
+        for (name, content) in files {
+            let path = source.join(name);
+            create_dir_all(path.parent().expect("parent")).expect("create_dir_all");
+            write(path, content).expect("write");
+        }
+
+        let creator = CsarCreator {
+            format: Some(Format::Tarball),
+            entry_definitions: Some("main.yaml".into()),
+            ..Default::default()
+        };
+
+        let file = directory.join("source.tar");
+        creator.create_from_directory(Some(&file), &source, true, false, false, &mut FailFast).expect("create");
+        CsarReader::open(file, None).expect("open")
+    }
+
+    fn read_entry_string(reader: &CsarReader, path: &str) -> String {
+        String::from_utf8(reader.read_entry(path).expect("read_entry")).expect("UTF-8")
+    }
+
+    fn has_entry(reader: &CsarReader, path: &str) -> bool {
+        reader.entries().expect("entries").iter().any(|entry| entry.path == Path::new(path))
+    }
+
+    const FILES: &[(&str, &str)] = &[
+        ("main.yaml", "tosca_definitions_version: tosca_2_0\n"),
+        ("files/keep.txt", "keep"),
+        ("files/replace.txt", "old"),
+        ("files/remove.txt", "remove"),
+    ];
+
+    fn changes(directory: &Path) -> CsarChanges {
+        let replacement = directory.join("replacement.txt");
+        write(&replacement, "new").expect("write");
+        let addition = directory.join("addition.txt");
+        write(&addition, "added").expect("write");
+
+        let mut changes = CsarChanges::default();
+        changes.files.insert("files/replace.txt".into(), replacement);
+        changes.files.insert("files/add.txt".into(), addition);
+        changes.remove.insert("files/remove.txt".into());
+        changes
+    }
+
+    #[test]
+    fn update_preserves_unchanged_entries() {
+        let directory = temporary_directory("update");
+        let source = create_csar(&directory, FILES);
+        let changes = changes(&directory);
+
+        let file = directory.join("updated.tar");
+        let tosca_meta =
+            CsarCreator::default().update_csar(&source, Some(&file), &changes, false, &mut FailFast).expect("update");
+        assert!(tosca_meta.is_some());
+
+        let updated = CsarReader::open(&file, None).expect("open");
+        assert_eq!(updated.format, Format::Tarball);
+        assert_eq!(read_entry_string(&updated, "main.yaml"), "tosca_definitions_version: tosca_2_0\n");
+        assert_eq!(read_entry_string(&updated, "files/keep.txt"), "keep");
+        assert_eq!(read_entry_string(&updated, "files/replace.txt"), "new");
+        assert_eq!(read_entry_string(&updated, "files/add.txt"), "added");
+        assert!(!has_entry(&updated, "files/remove.txt"));
+
+        // The source is untouched
+        assert_eq!(read_entry_string(&source, "files/replace.txt"), "old");
+        assert!(has_entry(&source, "files/remove.txt"));
+
+        let _ = remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn update_in_place() {
+        let directory = temporary_directory("update-in-place");
+        let source = create_csar(&directory, FILES);
+        let changes = changes(&directory);
+
+        CsarCreator::default().update_csar(&source, None, &changes, false, &mut FailFast).expect("update");
+
+        assert_eq!(read_entry_string(&source, "files/keep.txt"), "keep");
+        assert_eq!(read_entry_string(&source, "files/replace.txt"), "new");
+        assert!(!has_entry(&source, "files/remove.txt"));
+        assert!(!temporary_path(&source.path).exists());
+
+        let _ = remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn update_invalid() {
+        let directory = temporary_directory("update-invalid");
+        let source = create_csar(&directory, FILES);
+
+        // Writing into the source
+        let mut problems = Problems::default();
+        let tosca_meta = CsarCreator::default().update_csar(
+            &source,
+            Some(&source.path),
+            &CsarChanges::default(),
+            false,
+            &mut problems,
+        );
+        assert!(tosca_meta.expect("update").is_none());
+        assert!(problems.check().is_err());
+
+        // Removing the entry definitions
+        let mut changes = CsarChanges::default();
+        changes.remove.insert("main.yaml".into());
+        let mut problems = Problems::default();
+        let file = directory.join("updated.tar");
+        let tosca_meta = CsarCreator::default().update_csar(&source, Some(&file), &changes, false, &mut problems);
+        assert!(tosca_meta.expect("update").is_none());
+        assert!(problems.check().is_err());
+        assert!(!file.exists());
+
+        let _ = remove_dir_all(&directory);
+    }
+}
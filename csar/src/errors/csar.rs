@@ -5,3 +5,7 @@ tag_error!(CsarError, "CSAR");
 gloss_error!(RequiredKeynameError, "required keyname");
 gloss_error!(UnsupportedKeynameError, "unsupported keyname");
 gloss_error!(UnsupportedVersionError, "unsupported version");
+gloss_error!(SignatureError, "invalid signature");
+gloss_error!(TamperedError, "tampered");
+gloss_error!(UnlistedError, "unlisted");
+gloss_error!(UntrustedError, "untrusted");
@@ -44,6 +44,24 @@ impl Format {
             None
         }
     }
+
+    /// From the magic bytes at the start of the file.
+    ///
+    /// Tarballs are recognized by the "ustar" magic in the header of the first entry, so at least
+    /// 262 bytes are required to recognize them.
+    pub fn from_magic(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Some(Self::GzipTarball)
+        } else if bytes.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Self::ZstandardTarball)
+        } else if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            Some(Self::ZIP)
+        } else if bytes.len() >= 262 && &bytes[257..262] == b"ustar" {
+            Some(Self::Tarball)
+        } else {
+            None
+        }
+    }
 }
 
 impl FromStr for Format {
@@ -12,11 +12,24 @@ For documentation and usage examples see the
 */
 
 mod errors;
+mod format;
 
 /// CSAR creator.
 #[cfg(feature = "creator")]
 pub mod creator;
 
+/// CSAR reader.
+#[cfg(feature = "reader")]
+pub mod reader;
+
+/// CSAR manifest, including ETSI SOL004.
+#[cfg(feature = "manifest")]
+pub mod manifest;
+
+/// CSAR signing and signature verification.
+#[cfg(feature = "signature")]
+pub mod signature;
+
 /// pyo3 support.
 #[cfg(feature = "pyo3")]
 pub mod pyo3;
@@ -29,4 +42,4 @@ pub mod tosca_meta;
 pub mod url;
 
 #[allow(unused_imports)]
-pub use {errors::*, tosca_meta::*};
+pub use {errors::*, format::*, tosca_meta::*};
@@ -0,0 +1,74 @@
+use {
+    sha2::{Digest, Sha256, Sha512},
+    std::{fmt, fmt::Write as _, io},
+};
+
+//
+// DigestAlgorithm
+//
+
+/// Manifest digest algorithm.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DigestAlgorithm {
+    /// SHA-256.
+    #[default]
+    SHA256,
+
+    /// SHA-512.
+    SHA512,
+}
+
+impl DigestAlgorithm {
+    /// Name as used in manifests.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SHA256 => "SHA-256",
+            Self::SHA512 => "SHA-512",
+        }
+    }
+
+    /// From name as used in manifests.
+    ///
+    /// Case-insensitive.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "SHA-256" | "SHA256" => Some(Self::SHA256),
+            "SHA-512" | "SHA512" => Some(Self::SHA512),
+            _ => None,
+        }
+    }
+
+    /// Hex-encoded digest of content.
+    pub fn digest<ReadT>(&self, content: &mut ReadT) -> io::Result<String>
+    where
+        ReadT: io::Read + ?Sized,
+    {
+        Ok(match self {
+            Self::SHA256 => {
+                let mut hasher = Sha256::new();
+                io::copy(content, &mut hasher)?;
+                to_hex(&hasher.finalize())
+            }
+
+            Self::SHA512 => {
+                let mut hasher = Sha512::new();
+                io::copy(content, &mut hasher)?;
+                to_hex(&hasher.finalize())
+            }
+        })
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), formatter)
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
@@ -0,0 +1,293 @@
+use super::{super::errors::*, algorithm::*};
+
+use {
+    indexmap::*,
+    problemo::{common::*, *},
+    std::{collections::*, fmt::Write as _, io, path::*},
+};
+
+//
+// Manifest
+//
+
+/// Manifest with the digests of the files in a CSAR.
+///
+/// The syntax is that of the ETSI SOL004 manifest: an optional `metadata:` block followed by a
+/// block per file, separated by blank lines, with `Source`, `Algorithm`, and `Hash` keys.
+///
+/// Other blocks (e.g. `non_mano_artifact_sets:`) and embedded CMS signatures are ignored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Manifest {
+    /// Metadata.
+    pub metadata: IndexMap<String, String>,
+
+    /// Digests by file path.
+    pub digests: BTreeMap<String, FileDigest>,
+}
+
+//
+// FileDigest
+//
+
+/// Digest of a file in a [Manifest].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileDigest {
+    /// Algorithm.
+    pub algorithm: DigestAlgorithm,
+
+    /// Hex-encoded hash.
+    pub hash: String,
+}
+
+impl FileDigest {
+    /// Constructor.
+    pub fn new(algorithm: DigestAlgorithm, hash: String) -> Self {
+        Self { algorithm, hash }
+    }
+
+    /// True if the digest of the content matches.
+    pub fn matches<ReadT>(&self, content: &mut ReadT) -> io::Result<bool>
+    where
+        ReadT: io::Read + ?Sized,
+    {
+        Ok(self.algorithm.digest(content)? == self.hash)
+    }
+}
+
+impl Manifest {
+    /// Add the digest of a file's content.
+    pub fn add<PathT, ReadT>(&mut self, path: PathT, algorithm: DigestAlgorithm, content: &mut ReadT) -> io::Result<()>
+    where
+        PathT: AsRef<Path>,
+        ReadT: io::Read + ?Sized,
+    {
+        self.digests.insert(path_to_source(path.as_ref()), FileDigest::new(algorithm, algorithm.digest(content)?));
+        Ok(())
+    }
+
+    /// Read.
+    pub fn read<ProblemReceiverT>(content: &str, problems: &mut ProblemReceiverT) -> Result<Self, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let mut manifest = Self::default();
+
+        // Embedded signatures come last
+        let content = content.replace("\r\n", "\n");
+        let content = match content.find("-----BEGIN") {
+            Some(index) => {
+                tracing::debug!("ignoring embedded signature in manifest");
+                &content[..index]
+            }
+
+            None => content.as_str(),
+        };
+
+        for block in content.split("\n\n") {
+            let mut lines = block.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).peekable();
+
+            // Named blocks
+            if let Some(line) = lines.peek()
+                && let Some(name) = line.strip_suffix(':')
+                && !name.contains(':')
+            {
+                let name = name.trim().to_string();
+                lines.next();
+
+                if name == "metadata" {
+                    for line in lines {
+                        match line.split_once(':') {
+                            Some((key, value)) => {
+                                manifest.metadata.insert(key.trim().into(), value.trim().into());
+                            }
+
+                            None => problems.give(
+                                MalformedError::as_problem(format!(
+                                    "manifest metadata line is not a key-value pair: {:?}",
+                                    line
+                                ))
+                                .via(CsarError),
+                            )?,
+                        }
+                    }
+                } else {
+                    tracing::debug!("ignoring manifest block: {}", name);
+                }
+
+                continue;
+            }
+
+            let mut source = None;
+            let mut algorithm = None;
+            let mut hash = None;
+
+            for line in lines {
+                match line.split_once(':') {
+                    Some((key, value)) => {
+                        let value = value.trim().to_string();
+                        match key.trim() {
+                            "Source" => source = Some(value),
+                            "Algorithm" => algorithm = Some(value),
+                            "Hash" => hash = Some(value),
+                            key => {
+                                problems.give(UnsupportedKeynameError::as_problem(key.to_string()).via(CsarError))?
+                            }
+                        }
+                    }
+
+                    None => problems.give(
+                        MalformedError::as_problem(format!("manifest line is not a key-value pair: {:?}", line))
+                            .via(CsarError),
+                    )?,
+                }
+            }
+
+            match (source, algorithm, hash) {
+                (None, None, None) => {}
+
+                (Some(source), Some(algorithm), Some(hash)) => match DigestAlgorithm::from_name(&algorithm) {
+                    Some(algorithm) => {
+                        manifest.digests.insert(source, FileDigest::new(algorithm, hash.to_lowercase()));
+                    }
+
+                    None => problems
+                        .give(UnsupportedError::as_problem("manifest algorithm").with(algorithm).via(CsarError))?,
+                },
+
+                _ => problems.give(
+                    MalformedError::as_problem("manifest block must have \"Source\", \"Algorithm\", and \"Hash\"")
+                        .via(CsarError),
+                )?,
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Stringify.
+    pub fn stringify(&self) -> String {
+        let mut string = String::default();
+
+        if !self.metadata.is_empty() {
+            string += "metadata:\n";
+            for (key, value) in &self.metadata {
+                let _ = write!(string, "{}: {}\n", key, value);
+            }
+        }
+
+        for (source, digest) in &self.digests {
+            if !string.is_empty() {
+                string.push('\n');
+            }
+
+            let _ = write!(string, "Source: {}\nAlgorithm: {}\nHash: {}\n", source, digest.algorithm, digest.hash);
+        }
+
+        string
+    }
+}
+
+/// Manifest source for a path.
+///
+/// Always uses "/" as the separator.
+pub fn path_to_source(path: &Path) -> String {
+    path.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    const MANIFEST: &str = "metadata:\r
+vnf_product_name: Example\r
+vnf_release_date_time: 2024-01-01T00:00:00+00:00\r
+\r
+Source: Definitions/main.yaml\r
+Algorithm: SHA-256\r
+Hash: 2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824\r
+\r
+non_mano_artifact_sets:\r
+  foo:\r
+    Source: Files/foo.txt\r
+\r
+Source: Files/data.bin\r
+Algorithm: sha512\r
+Hash: 00\r
+-----BEGIN CMS-----\r
+ignored\r
+-----END CMS-----\r
+";
+
+    #[test]
+    fn read() {
+        let manifest = Manifest::read(MANIFEST, &mut FailFast).expect("read");
+
+        assert_eq!(manifest.metadata.len(), 2);
+        assert_eq!(manifest.metadata.get("vnf_product_name").map(|value| value.as_str()), Some("Example"));
+        assert_eq!(
+            manifest.metadata.get("vnf_release_date_time").map(|value| value.as_str()),
+            Some("2024-01-01T00:00:00+00:00")
+        );
+
+        assert_eq!(manifest.digests.len(), 2);
+        assert_eq!(
+            manifest.digests.get("Definitions/main.yaml"),
+            Some(&FileDigest::new(DigestAlgorithm::SHA256, HELLO_SHA256.into()))
+        );
+        assert_eq!(
+            manifest.digests.get("Files/data.bin"),
+            Some(&FileDigest::new(DigestAlgorithm::SHA512, "00".into()))
+        );
+    }
+
+    #[test]
+    fn read_problems() {
+        let mut problems = Problems::default();
+        Manifest::read("Source: a.txt\nAlgorithm: SHA-256\n", &mut problems).expect("read");
+        assert!(problems.check().is_err());
+
+        let mut problems = Problems::default();
+        Manifest::read("Source: a.txt\nAlgorithm: MD5\nHash: 00\n", &mut problems).expect("read");
+        assert!(problems.check().is_err());
+
+        let mut problems = Problems::default();
+        Manifest::read("Source: a.txt\nAlgorithm: SHA-256\nHash: 00\nSize: 10\n", &mut problems).expect("read");
+        assert!(problems.check().is_err());
+
+        assert!(Manifest::read("metadata:\nnot a pair\n", &mut FailFast).is_err());
+    }
+
+    #[test]
+    fn stringify() {
+        let mut manifest = Manifest::default();
+        manifest.metadata.insert("vnf_product_name".into(), "Example".into());
+        manifest.add("Files/b.txt", DigestAlgorithm::SHA256, &mut "hello".as_bytes()).expect("add");
+        manifest
+            .add(Path::new("Definitions").join("a.yaml"), DigestAlgorithm::SHA256, &mut "hello".as_bytes())
+            .expect("add");
+
+        assert_eq!(
+            manifest.stringify(),
+            format!(
+                "metadata:\nvnf_product_name: Example\n\n\
+                Source: Definitions/a.yaml\nAlgorithm: SHA-256\nHash: {0}\n\n\
+                Source: Files/b.txt\nAlgorithm: SHA-256\nHash: {0}\n",
+                HELLO_SHA256
+            )
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut manifest = Manifest::default();
+        manifest.add("TOSCA-Metadata/TOSCA.meta", DigestAlgorithm::SHA512, &mut "meta".as_bytes()).expect("add");
+        manifest.add("main.yaml", DigestAlgorithm::SHA256, &mut "main".as_bytes()).expect("add");
+
+        assert_eq!(Manifest::read(&manifest.stringify(), &mut FailFast).expect("read"), manifest);
+
+        // Without metadata or digests
+        assert_eq!(Manifest::read(&Manifest::default().stringify(), &mut FailFast).expect("read"), Manifest::default());
+    }
+}
@@ -0,0 +1,6 @@
+mod algorithm;
+mod manifest;
+mod sol004;
+
+#[allow(unused_imports)]
+pub use {algorithm::*, manifest::*, sol004::*};
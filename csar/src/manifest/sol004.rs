@@ -0,0 +1,114 @@
+use super::{
+    super::{errors::*, tosca_meta::*},
+    algorithm::*,
+    manifest::*,
+};
+
+use {
+    indexmap::*,
+    problemo::{common::*, *},
+    std::path::*,
+};
+
+/// `TOSCA.meta` keyname for the location of the ETSI SOL004 manifest.
+pub const ETSI_ENTRY_MANIFEST: &str = "ETSI-Entry-Manifest";
+
+/// Required ETSI SOL004 manifest metadata keys for a VNF package.
+pub const VNF_METADATA_KEYS: &[&str] =
+    &["vnf_provider_id", "vnf_product_name", "vnf_release_date_time", "vnf_package_version"];
+
+/// Required ETSI SOL004 manifest metadata keys for a PNF package.
+pub const PNF_METADATA_KEYS: &[&str] =
+    &["pnfd_provider", "pnfd_name", "pnfd_release_date_time", "pnfd_archive_version"];
+
+/// Required ETSI SOL004 manifest metadata keys for an NS package.
+pub const NS_METADATA_KEYS: &[&str] =
+    &["nsd_designer", "nsd_invariant_id", "nsd_name", "nsd_release_date_time", "nsd_file_structure_version"];
+
+//
+// Sol004
+//
+
+/// ETSI SOL004 manifest mode.
+#[derive(Clone, Debug, Default)]
+pub struct Sol004 {
+    /// Digest algorithm.
+    pub algorithm: DigestAlgorithm,
+
+    /// Manifest metadata.
+    pub metadata: IndexMap<String, String>,
+}
+
+impl Sol004 {
+    /// Constructor.
+    pub fn new(algorithm: DigestAlgorithm, metadata: IndexMap<String, String>) -> Self {
+        Self { algorithm, metadata }
+    }
+
+    /// New manifest with our metadata.
+    pub fn new_manifest(&self) -> Manifest {
+        Manifest { metadata: self.metadata.clone(), ..Default::default() }
+    }
+}
+
+/// Validate ETSI SOL004 manifest metadata.
+///
+/// The package kind (VNF, PNF, or NS) is determined by the key prefixes and all its required
+/// keys must be present.
+pub fn validate_sol004_metadata<ProblemReceiverT>(
+    metadata: &IndexMap<String, String>,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    ProblemReceiverT: ProblemReceiver,
+{
+    let required_keys = if metadata.keys().any(|key| key.starts_with("vnf_")) {
+        VNF_METADATA_KEYS
+    } else if metadata.keys().any(|key| key.starts_with("pnfd_")) {
+        PNF_METADATA_KEYS
+    } else if metadata.keys().any(|key| key.starts_with("nsd_")) {
+        NS_METADATA_KEYS
+    } else {
+        problems
+            .give(InvalidError::as_problem("manifest metadata is not for a VNF, PNF, or NS package").via(CsarError))?;
+        return Ok(());
+    };
+
+    for key in required_keys {
+        match metadata.get(*key) {
+            Some(value) if !value.is_empty() => {}
+            _ => problems.give(RequiredKeynameError::as_problem(*key).via(CsarError))?,
+        }
+    }
+
+    Ok(())
+}
+
+/// ETSI SOL004 manifest location for entry definitions.
+///
+/// It has the same name as the entry definitions with the ".mf" extension.
+pub fn sol004_manifest_location(entry_definitions: &str) -> PathBuf {
+    PathBuf::from(entry_definitions).with_extension("mf")
+}
+
+impl ToscaMeta {
+    /// ETSI SOL004 manifest location.
+    ///
+    /// Looks for the `ETSI-Entry-Manifest` key in the extra blocks.
+    pub fn sol004_manifest_location(&self) -> Option<PathBuf> {
+        self.extra_blocks.iter().find_map(|block| block.get(ETSI_ENTRY_MANIFEST)).map(PathBuf::from)
+    }
+
+    /// Set the ETSI SOL004 manifest location.
+    ///
+    /// Adds an extra block if the `ETSI-Entry-Manifest` key is not already set.
+    pub fn set_sol004_manifest_location(&mut self, location: &Path) -> Result<(), Problem> {
+        if self.sol004_manifest_location().is_none() {
+            let mut block = ToscaMetaBlock::default();
+            block.insert(ETSI_ENTRY_MANIFEST.into(), path_to_source(location))?;
+            self.extra_blocks.push(block);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,343 @@
+use super::{
+    super::{manifest::*, tosca_meta::*},
+    entry::*,
+    path::*,
+    reader::*,
+};
+
+use {
+    compris::normal::*,
+    problemo::*,
+    std::{collections::*, fmt, path::*},
+};
+
+//
+// ChangeType
+//
+
+/// Type of change in a [CsarDiff].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChangeType {
+    /// Added.
+    Added,
+
+    /// Removed.
+    Removed,
+
+    /// Changed.
+    Changed,
+}
+
+impl ChangeType {
+    /// As string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        }
+    }
+
+    /// From old and new values.
+    pub fn from_options<ValueT>(old: &Option<ValueT>, new: &Option<ValueT>) -> Self {
+        match (old, new) {
+            (None, _) => Self::Added,
+            (_, None) => Self::Removed,
+            _ => Self::Changed,
+        }
+    }
+}
+
+impl fmt::Display for ChangeType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), formatter)
+    }
+}
+
+//
+// ToscaMetaChange
+//
+
+/// Change of a key in a `TOSCA.meta` block.
+#[derive(Clone, Debug)]
+pub struct ToscaMetaChange {
+    /// Block index.
+    pub block: usize,
+
+    /// Keyname.
+    pub keyname: String,
+
+    /// Old value.
+    pub old: Option<String>,
+
+    /// New value.
+    pub new: Option<String>,
+}
+
+impl ToscaMetaChange {
+    /// Change type.
+    pub fn change_type(&self) -> ChangeType {
+        ChangeType::from_options(&self.old, &self.new)
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for ToscaMetaChange
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("change", self.change_type().as_str());
+        map.into_insert("block", self.block as u64);
+        map.into_insert("keyname", self.keyname);
+
+        if let Some(old) = self.old {
+            map.into_insert("old", old);
+        }
+
+        if let Some(new) = self.new {
+            map.into_insert("new", new);
+        }
+
+        map.into()
+    }
+}
+
+//
+// EntrySummary
+//
+
+/// Summary of an entry for comparison.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntrySummary {
+    /// Type.
+    pub entry_type: CsarEntryType,
+
+    /// Size in bytes (uncompressed).
+    pub size: u64,
+
+    /// Link target.
+    pub link_target: Option<PathBuf>,
+
+    /// Hex-encoded SHA-256 digest of the content, for files.
+    pub digest: Option<String>,
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for EntrySummary
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("type", self.entry_type.as_str());
+        map.into_insert("size", self.size);
+
+        if let Some(link_target) = self.link_target {
+            map.into_insert("link-target", link_target.display().to_string());
+        }
+
+        if let Some(digest) = self.digest {
+            map.into_insert("digest", digest);
+        }
+
+        map.into()
+    }
+}
+
+//
+// EntryChange
+//
+
+/// Change of an entry.
+#[derive(Clone, Debug)]
+pub struct EntryChange {
+    /// Path in the archive.
+    pub path: PathBuf,
+
+    /// Old entry.
+    pub old: Option<EntrySummary>,
+
+    /// New entry.
+    pub new: Option<EntrySummary>,
+}
+
+impl EntryChange {
+    /// Change type.
+    pub fn change_type(&self) -> ChangeType {
+        ChangeType::from_options(&self.old, &self.new)
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for EntryChange
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("change", self.change_type().as_str());
+        map.into_insert("path", self.path.display().to_string());
+
+        if let Some(old) = self.old {
+            map.into_insert("old", old);
+        }
+
+        if let Some(new) = self.new {
+            map.into_insert("new", new);
+        }
+
+        map.into()
+    }
+}
+
+//
+// CsarDiff
+//
+
+/// Differences between two CSARs.
+#[derive(Clone, Debug, Default)]
+pub struct CsarDiff {
+    /// `TOSCA.meta` changes.
+    pub tosca_meta: Vec<ToscaMetaChange>,
+
+    /// Entry changes.
+    pub entries: Vec<EntryChange>,
+}
+
+impl CsarDiff {
+    /// True if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.tosca_meta.is_empty() && self.entries.is_empty()
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for CsarDiff
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        let tosca_meta: List<AnnotatedT> = self.tosca_meta.into_iter().map(|change| change.into()).collect();
+        map.into_insert("tosca-meta", tosca_meta);
+
+        let entries: List<AnnotatedT> = self.entries.into_iter().map(|change| change.into()).collect();
+        map.into_insert("entries", entries);
+
+        map.into()
+    }
+}
+
+impl CsarReader {
+    /// Compare with another CSAR.
+    ///
+    /// `TOSCA.meta` blocks are compared key by key. Entries are compared by path, type, size,
+    /// link target, and content digest. Permissions and timestamps are ignored.
+    pub fn diff<ProblemReceiverT>(
+        &self,
+        other: &CsarReader,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<CsarDiff, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let old_entries = self.entries()?;
+        let new_entries = other.entries()?;
+
+        let old_tosca_meta = self.tosca_meta(&old_entries, problems)?.map(|(_, tosca_meta)| tosca_meta);
+        let new_tosca_meta = other.tosca_meta(&new_entries, problems)?.map(|(_, tosca_meta)| tosca_meta);
+        let tosca_meta = diff_tosca_meta(old_tosca_meta.as_ref(), new_tosca_meta.as_ref())?;
+
+        let mut old_summaries = self.entry_summaries(&old_entries)?;
+        let mut new_summaries = other.entry_summaries(&new_entries)?;
+
+        let paths: BTreeSet<_> = old_summaries.keys().chain(new_summaries.keys()).cloned().collect();
+
+        let mut entries = Vec::default();
+        for path in paths {
+            let old = old_summaries.remove(&path);
+            let new = new_summaries.remove(&path);
+            if old != new {
+                entries.push(EntryChange { path, old, new });
+            }
+        }
+
+        Ok(CsarDiff { tosca_meta, entries })
+    }
+
+    /// Entry summaries by path.
+    ///
+    /// The entries are those returned by [entries](CsarReader::entries). Reads the archive once to
+    /// calculate the digests.
+    pub fn entry_summaries(&self, entries: &[CsarEntry]) -> Result<BTreeMap<PathBuf, EntrySummary>, Problem> {
+        let mut summaries = BTreeMap::default();
+
+        for entry in entries {
+            let path = safe_entry_path(&entry.path).unwrap_or_else(|_| entry.path.clone());
+            summaries.insert(
+                path,
+                EntrySummary {
+                    entry_type: entry.entry_type,
+                    size: entry.size,
+                    link_target: entry.link_target.clone(),
+                    digest: None,
+                },
+            );
+        }
+
+        self.visit_files(|entry, content| {
+            let digest = DigestAlgorithm::SHA256.digest(content)?;
+            if let Some(summary) = summaries.get_mut(&entry.path) {
+                summary.digest = Some(digest);
+            }
+            Ok(())
+        })?;
+
+        Ok(summaries)
+    }
+}
+
+/// Compare `TOSCA.meta` blocks key by key.
+///
+/// A missing `TOSCA.meta` is treated as having no blocks.
+pub fn diff_tosca_meta(old: Option<&ToscaMeta>, new: Option<&ToscaMeta>) -> Result<Vec<ToscaMetaChange>, Problem> {
+    let old_blocks = tosca_meta_blocks(old)?;
+    let new_blocks = tosca_meta_blocks(new)?;
+
+    let mut changes = Vec::default();
+
+    let default_block = ToscaMetaBlock::default();
+    for block in 0..old_blocks.len().max(new_blocks.len()) {
+        let old_block = old_blocks.get(block).unwrap_or(&default_block);
+        let new_block = new_blocks.get(block).unwrap_or(&default_block);
+
+        // Keep the order of keys, old first
+        let mut keynames: Vec<_> = old_block.keynames().collect();
+        for keyname in new_block.keynames() {
+            if !keynames.contains(&keyname) {
+                keynames.push(keyname);
+            }
+        }
+
+        for keyname in keynames {
+            let old = old_block.get(keyname);
+            let new = new_block.get(keyname);
+            if old != new {
+                changes.push(ToscaMetaChange { block, keyname: keyname.clone(), old: old.cloned(), new: new.cloned() });
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+fn tosca_meta_blocks(tosca_meta: Option<&ToscaMeta>) -> Result<Vec<ToscaMetaBlock>, Problem> {
+    let mut blocks = Vec::default();
+    if let Some(tosca_meta) = tosca_meta {
+        blocks.push(tosca_meta.try_into()?);
+        blocks.extend(tosca_meta.extra_blocks.iter().cloned());
+    }
+    Ok(blocks)
+}
@@ -0,0 +1,78 @@
+use super::{
+    super::{errors::*, manifest::*},
+    entry::*,
+    path::*,
+    reader::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{collections::*, path::*},
+};
+
+impl CsarReader {
+    /// Check the file digests in a manifest.
+    ///
+    /// Every file in the archive, except for those in skip, must be listed in the manifest and
+    /// its digest must match. Every file listed in the manifest must exist in the archive.
+    ///
+    /// Links (and other entries that are neither files nor directories) cannot have digests, so
+    /// they are always reported as unlisted.
+    pub fn check_digests<ProblemReceiverT>(
+        &self,
+        manifest: &Manifest,
+        skip: &[PathBuf],
+        problems: &mut ProblemReceiverT,
+    ) -> Result<(), Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let mut found = BTreeSet::default();
+
+        for entry in self.entries()? {
+            match entry.entry_type {
+                CsarEntryType::File | CsarEntryType::Directory => {}
+
+                _ => {
+                    let source = path_to_source(&safe_entry_path(&entry.path)?);
+                    problems.give(UnlistedError::as_problem(source.clone()).via(CsarError))?;
+                    found.insert(source);
+                }
+            }
+        }
+
+        self.visit_files(|entry, content| {
+            if skip.iter().any(|skip| *skip == entry.path) {
+                return Ok(());
+            }
+
+            let source = path_to_source(&entry.path);
+
+            match manifest.digests.get(&source) {
+                Some(digest) => {
+                    if !digest.matches(content)? {
+                        problems.give(TamperedError::as_problem(source.clone()).via(CsarError))?;
+                    }
+                }
+
+                None => problems.give(UnlistedError::as_problem(source.clone()).via(CsarError))?,
+            }
+
+            found.insert(source);
+            Ok(())
+        })?;
+
+        for source in manifest.digests.keys() {
+            if !found.contains(source) {
+                problems.give(NotFoundError::default_as_problem().with(PathBuf::from(source)).via(CsarError))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn read_string(&self, path: &Path) -> Result<String, Problem> {
+        String::from_utf8(self.read_entry(path)?)
+            .map_err(|_| MalformedError::as_problem(format!("not UTF-8: {}", path.display())).via(CsarError))
+    }
+}
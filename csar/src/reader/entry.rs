@@ -0,0 +1,121 @@
+use {
+    compris::normal::*,
+    depiction::*,
+    std::{fmt, path::*},
+};
+
+//
+// CsarEntryType
+//
+
+/// Type of [CsarEntry].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CsarEntryType {
+    /// File.
+    File,
+
+    /// Directory.
+    Directory,
+
+    /// Symbolic link.
+    Symlink,
+
+    /// Hard link.
+    HardLink,
+
+    /// Other.
+    Other,
+}
+
+impl CsarEntryType {
+    /// As string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Directory => "directory",
+            Self::Symlink => "symlink",
+            Self::HardLink => "hardlink",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for CsarEntryType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), formatter)
+    }
+}
+
+//
+// CsarEntry
+//
+
+/// Entry in a CSAR.
+#[derive(Clone, Debug, Depict)]
+pub struct CsarEntry {
+    /// Path in the archive.
+    #[depict(as(debug), style(string))]
+    pub path: PathBuf,
+
+    /// Type.
+    #[depict(as(display), style(symbol))]
+    pub entry_type: CsarEntryType,
+
+    /// Size in bytes (uncompressed).
+    #[depict(style(number))]
+    pub size: u64,
+
+    /// Link target, for [Symlink](CsarEntryType::Symlink) and
+    /// [HardLink](CsarEntryType::HardLink).
+    #[depict(option, as(debug), style(string))]
+    pub link_target: Option<PathBuf>,
+
+    /// Unix permissions.
+    #[depict(option, style(number))]
+    pub permissions: Option<u32>,
+
+    /// Modification time in seconds since the Unix epoch.
+    #[depict(option, style(number))]
+    pub modified: Option<u64>,
+}
+
+impl CsarEntry {
+    /// Constructor.
+    pub fn new(path: PathBuf, entry_type: CsarEntryType, size: u64, link_target: Option<PathBuf>) -> Self {
+        Self { path, entry_type, size, link_target, permissions: None, modified: None }
+    }
+
+    /// With metadata.
+    pub fn with_metadata(mut self, permissions: Option<u32>, modified: Option<u64>) -> Self {
+        self.permissions = permissions;
+        self.modified = modified;
+        self
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for CsarEntry
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        let mut map = Map::default();
+
+        map.into_insert("path", self.path.display().to_string());
+        map.into_insert("type", self.entry_type.as_str());
+        map.into_insert("size", self.size);
+
+        if let Some(link_target) = self.link_target {
+            map.into_insert("link-target", link_target.display().to_string());
+        }
+
+        if let Some(permissions) = self.permissions {
+            map.into_insert("permissions", format!("{:04o}", permissions));
+        }
+
+        if let Some(modified) = self.modified {
+            map.into_insert("modified", modified);
+        }
+
+        map.into()
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(feature = "manifest")]
+mod diff;
+#[cfg(feature = "manifest")]
+mod digests;
+mod entry;
+mod path;
+mod reader;
+#[cfg(feature = "manifest")]
+mod sol004;
+#[cfg(feature = "tarball")]
+mod tarball;
+mod validate;
+#[cfg(feature = "signature")]
+mod verify;
+#[cfg(feature = "zip")]
+mod zip;
+
+#[allow(unused_imports)]
+pub use {entry::*, path::*, reader::*, validate::*};
+
+#[cfg(feature = "manifest")]
+#[allow(unused_imports)]
+pub use diff::*;
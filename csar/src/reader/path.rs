@@ -0,0 +1,42 @@
+use super::super::errors::*;
+
+use {
+    problemo::{common::*, *},
+    std::path::*,
+};
+
+/// Entry path relative to the archive root.
+///
+/// Returns an error if the path is absolute or if it would escape the archive root, e.g. via
+/// `..`. This protects against path traversal when extracting untrusted archives.
+pub fn safe_entry_path(path: &Path) -> Result<PathBuf, Problem> {
+    let mut safe_path = PathBuf::default();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(segment) => safe_path.push(segment),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !safe_path.pop() {
+                    return Err(escape_problem(path));
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(escape_problem(path)),
+        }
+    }
+
+    Ok(safe_path)
+}
+
+/// Link target relative to the archive root.
+///
+/// The target is relative to the directory of the link entry. Returns an error if the target is
+/// absolute or if it would escape the archive root.
+pub fn safe_link_target(path: &Path, target: &Path) -> Result<PathBuf, Problem> {
+    let directory = safe_entry_path(path)?.parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+    safe_entry_path(&directory.join(target)).map_err(|_| escape_problem(target))
+}
+
+pub(crate) fn escape_problem(path: &Path) -> Problem {
+    InvalidError::as_problem(format!("entry path escapes the archive root: {:?}", path.display())).via(CsarError)
+}
@@ -0,0 +1,177 @@
+use super::{
+    super::{errors::*, format::*},
+    entry::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{
+        fs::*,
+        io::{self, Read},
+        path::*,
+    },
+};
+
+//
+// CsarReader
+//
+
+/// CSAR reader.
+///
+/// Supports tarballs (plain, Gzip, and Zstandard) and ZIP, according to the enabled features.
+///
+/// Tarballs do not support random access, so every operation reads the archive from the start.
+#[derive(Clone, Debug)]
+pub struct CsarReader {
+    /// Path.
+    pub path: PathBuf,
+
+    /// Format.
+    pub format: Format,
+}
+
+impl CsarReader {
+    /// Constructor.
+    pub fn new(path: PathBuf, format: Format) -> Self {
+        Self { path, format }
+    }
+
+    /// Open a CSAR file.
+    ///
+    /// If the format is not provided it will be detected from the magic bytes at the start of
+    /// the file, falling back to the file extension.
+    pub fn open<PathT>(path: PathT, format: Option<Format>) -> Result<Self, Problem>
+    where
+        PathT: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let format = match format {
+            Some(format) => format,
+
+            None => {
+                let mut magic = Vec::with_capacity(262);
+                File::open(path)?.take(262).read_to_end(&mut magic)?;
+
+                match Format::from_magic(&magic).or_else(|| Format::from_extension(path)) {
+                    Some(format) => format,
+                    None => {
+                        return Err(UnsupportedError::as_problem("CSAR format")
+                            .with(path.to_path_buf())
+                            .via(CsarError));
+                    }
+                }
+            }
+        };
+
+        Ok(Self::new(path.into(), format))
+    }
+
+    /// List entries.
+    pub fn entries(&self) -> Result<Vec<CsarEntry>, Problem> {
+        match self.format {
+            #[cfg(feature = "tarball")]
+            Format::Tarball | Format::GzipTarball | Format::ZstandardTarball => self.tarball_entries(),
+
+            #[cfg(feature = "zip")]
+            Format::ZIP => self.zip_entries(),
+
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Read an entry.
+    ///
+    /// Only file entries can be read.
+    pub fn read_entry<PathT>(&self, path: PathT) -> Result<Vec<u8>, Problem>
+    where
+        PathT: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        let mut content = Vec::default();
+        if !self.write_entry(path, &mut content)? {
+            return Err(NotFoundError::default_as_problem().with(path.to_path_buf()).via(CsarError));
+        }
+
+        Ok(content)
+    }
+
+    /// Write an entry.
+    ///
+    /// Only file entries can be written.
+    ///
+    /// Returns false if the entry was not found.
+    #[allow(unused_variables)]
+    pub fn write_entry<WriteT>(&self, path: &Path, writer: &mut WriteT) -> Result<bool, Problem>
+    where
+        WriteT: io::Write,
+    {
+        match self.format {
+            #[cfg(feature = "tarball")]
+            Format::Tarball | Format::GzipTarball | Format::ZstandardTarball => self.tarball_write_entry(path, writer),
+
+            #[cfg(feature = "zip")]
+            Format::ZIP => self.zip_write_entry(path, writer),
+
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Visit all file entries with their content, in archive order.
+    ///
+    /// Entry paths are normalized with [safe_entry_path](super::safe_entry_path).
+    ///
+    /// Unlike [read_entry](CsarReader::read_entry) this reads the archive only once.
+    #[allow(unused_variables)]
+    pub fn visit_files<VisitT>(&self, visit: VisitT) -> Result<(), Problem>
+    where
+        VisitT: FnMut(&CsarEntry, &mut dyn io::Read) -> Result<(), Problem>,
+    {
+        match self.format {
+            #[cfg(feature = "tarball")]
+            Format::Tarball | Format::GzipTarball | Format::ZstandardTarball => self.tarball_visit_files(visit),
+
+            #[cfg(feature = "zip")]
+            Format::ZIP => self.zip_visit_files(visit),
+
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    /// Extract all entries into a directory.
+    ///
+    /// The directory will be created if it does not exist. Entries with paths or link targets
+    /// that would escape the directory cause an error (see
+    /// [safe_entry_path](super::safe_entry_path)), as do entries that would be written through a
+    /// symbolic link.
+    ///
+    /// Returns the extracted entries.
+    #[allow(unused_variables)]
+    pub fn extract<PathT>(&self, directory: PathT) -> Result<Vec<CsarEntry>, Problem>
+    where
+        PathT: AsRef<Path>,
+    {
+        let directory = directory.as_ref();
+        create_dir_all(directory)?;
+
+        match self.format {
+            #[cfg(feature = "tarball")]
+            Format::Tarball | Format::GzipTarball | Format::ZstandardTarball => self.tarball_extract(directory),
+
+            #[cfg(feature = "zip")]
+            Format::ZIP => self.zip_extract(directory),
+
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported()),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn unsupported(&self) -> Problem {
+        UnsupportedError::as_problem("CSAR format").with(self.format).via(CsarError)
+    }
+}
@@ -0,0 +1,47 @@
+use super::{
+    super::{manifest::*, tosca_meta::*},
+    reader::*,
+};
+
+use problemo::*;
+
+#[cfg(feature = "signature")]
+use super::super::signature::*;
+
+impl CsarReader {
+    /// Check the ETSI SOL004 manifest.
+    ///
+    /// The manifest location is the value of `ETSI-Entry-Manifest` in the TOSCA meta. Its
+    /// metadata is validated and the digests of all files are recomputed (see
+    /// [check_digests](CsarReader::check_digests)).
+    ///
+    /// Returns the manifest if it could be read, or [None] if the TOSCA meta has no manifest
+    /// location.
+    pub fn check_sol004_manifest<ProblemReceiverT>(
+        &self,
+        tosca_meta: &ToscaMeta,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<Manifest>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(location) = tosca_meta.sol004_manifest_location() else {
+            return Ok(None);
+        };
+
+        let manifest = give_unwrap!(self.read_string(&location), problems, None);
+        let manifest = Manifest::read(&manifest, problems)?;
+
+        validate_sol004_metadata(&manifest.metadata, problems)?;
+
+        #[allow(unused_mut)]
+        let mut skip = vec![location];
+
+        #[cfg(feature = "signature")]
+        skip.extend(signature_locations());
+
+        self.check_digests(&manifest, &skip, problems)?;
+
+        Ok(Some(manifest))
+    }
+}
@@ -0,0 +1,140 @@
+use super::{
+    super::{errors::*, format::*},
+    entry::*,
+    path::*,
+    reader::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{fs::*, io, path::*},
+    tar::*,
+};
+
+impl CsarReader {
+    pub(crate) fn tarball_entries(&self) -> Result<Vec<CsarEntry>, Problem> {
+        let mut archive = self.tarball_archive()?;
+
+        let mut entries = Vec::default();
+        for entry in archive.entries()? {
+            entries.push(tarball_entry(&entry?)?);
+        }
+
+        Ok(entries)
+    }
+
+    pub(crate) fn tarball_write_entry<WriteT>(&self, path: &Path, writer: &mut WriteT) -> Result<bool, Problem>
+    where
+        WriteT: io::Write,
+    {
+        let path = safe_entry_path(path)?;
+        let mut archive = self.tarball_archive()?;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_file() && safe_entry_path(&entry.path()?)? == path {
+                io::copy(&mut entry, writer)?;
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub(crate) fn tarball_visit_files<VisitT>(&self, mut visit: VisitT) -> Result<(), Problem>
+    where
+        VisitT: FnMut(&CsarEntry, &mut dyn io::Read) -> Result<(), Problem>,
+    {
+        let mut archive = self.tarball_archive()?;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_file() {
+                let mut csar_entry = tarball_entry(&entry)?;
+                csar_entry.path = safe_entry_path(&csar_entry.path)?;
+                visit(&csar_entry, &mut entry)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn tarball_extract(&self, directory: &Path) -> Result<Vec<CsarEntry>, Problem> {
+        let mut archive = self.tarball_archive()?;
+
+        let mut entries = Vec::default();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let csar_entry = tarball_entry(&entry)?;
+
+            // Validate before unpacking so that we fail instead of silently skipping
+            safe_entry_path(&csar_entry.path)?;
+            if let Some(link_target) = &csar_entry.link_target {
+                match csar_entry.entry_type {
+                    // Hard link targets are relative to the archive root
+                    CsarEntryType::HardLink => safe_entry_path(link_target)?,
+                    _ => safe_link_target(&csar_entry.path, link_target)?,
+                };
+            }
+
+            tracing::debug!("extracting: {:?}", csar_entry.path.display());
+
+            // unpack_in also protects against writing through symbolic links outside the directory
+            if !entry.unpack_in(directory)? {
+                return Err(InvalidError::as_problem(format!(
+                    "entry path escapes the archive root: {:?}",
+                    csar_entry.path.display()
+                ))
+                .via(CsarError));
+            }
+
+            entries.push(csar_entry);
+        }
+
+        Ok(entries)
+    }
+
+    fn tarball_archive(&self) -> Result<Archive<Box<dyn io::Read>>, Problem> {
+        let file = io::BufReader::new(File::open(&self.path)?);
+
+        let reader: Box<dyn io::Read> = match self.format {
+            Format::Tarball => Box::new(file),
+
+            #[cfg(feature = "gzip")]
+            Format::GzipTarball => Box::new(flate2::bufread::GzDecoder::new(file)),
+
+            #[cfg(feature = "zstandard")]
+            Format::ZstandardTarball => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+
+            #[allow(unreachable_patterns)]
+            _ => return Err(UnsupportedError::as_problem("CSAR format").with(self.format).via(CsarError)),
+        };
+
+        Ok(Archive::new(reader))
+    }
+}
+
+fn tarball_entry<ReadT>(entry: &Entry<'_, ReadT>) -> Result<CsarEntry, Problem>
+where
+    ReadT: io::Read,
+{
+    let entry_type = entry.header().entry_type();
+
+    let csar_entry_type = if entry_type.is_file() {
+        CsarEntryType::File
+    } else if entry_type.is_dir() {
+        CsarEntryType::Directory
+    } else if entry_type.is_symlink() {
+        CsarEntryType::Symlink
+    } else if entry_type.is_hard_link() {
+        CsarEntryType::HardLink
+    } else {
+        CsarEntryType::Other
+    };
+
+    let link_target = entry.link_name()?.map(|link_name| link_name.into_owned());
+
+    let header = entry.header();
+    Ok(CsarEntry::new(entry.path()?.into_owned(), csar_entry_type, entry.size(), link_target)
+        .with_metadata(header.mode().ok(), header.mtime().ok()))
+}
@@ -0,0 +1,147 @@
+use super::{
+    super::{errors::*, tosca_meta::*},
+    entry::*,
+    path::*,
+    reader::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::path::*,
+};
+
+impl CsarReader {
+    /// Validate the archive.
+    ///
+    /// Checks that:
+    ///
+    /// * No entry path (or link target) is absolute or escapes the archive root
+    /// * There is exactly one `TOSCA.meta` in one of the [tosca_meta_locations]
+    /// * The `TOSCA.meta` is valid
+    /// * `Entry-Definitions` and all `Other-Definitions` exist in the archive
+    /// * If there is an ETSI SOL004 manifest, it is valid and the file digests match (see
+    ///   [check_sol004_manifest](CsarReader::check_sol004_manifest))
+    ///
+    /// Note that the contents of the TOSCA files are *not* validated.
+    ///
+    /// The entries are those returned by [entries](CsarReader::entries).
+    ///
+    /// Returns the TOSCA meta if it could be read.
+    pub fn validate<ProblemReceiverT>(
+        &self,
+        entries: &[CsarEntry],
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<ToscaMeta>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        for entry in entries {
+            if let Err(problem) = safe_entry_path(&entry.path) {
+                problems.give(problem)?;
+                continue;
+            }
+
+            if let Some(link_target) = &entry.link_target {
+                let link_target = match entry.entry_type {
+                    // Hard link targets are relative to the archive root
+                    CsarEntryType::HardLink => safe_entry_path(link_target),
+                    _ => safe_link_target(&entry.path, link_target),
+                };
+
+                if let Err(problem) = link_target {
+                    problems.give(problem)?;
+                }
+            }
+        }
+
+        let Some((_, tosca_meta)) = self.tosca_meta(entries, problems)? else {
+            return Ok(None);
+        };
+
+        match &tosca_meta.entry_definitions {
+            Some(entry_definitions) => check_definitions(entries, entry_definitions, problems)?,
+            None => problems.give(MissingError::as_problem("Entry-Definitions").via(CsarError))?,
+        }
+
+        for other_definition in &tosca_meta.other_definitions {
+            check_definitions(entries, other_definition, problems)?;
+        }
+
+        #[cfg(feature = "manifest")]
+        self.check_sol004_manifest(&tosca_meta, problems)?;
+
+        Ok(Some(tosca_meta))
+    }
+
+    /// Read the `TOSCA.meta`.
+    ///
+    /// There must be exactly one `TOSCA.meta` in one of the [tosca_meta_locations].
+    ///
+    /// The entries are those returned by [entries](CsarReader::entries).
+    ///
+    /// Returns the location and the TOSCA meta if it could be read.
+    pub fn tosca_meta<ProblemReceiverT>(
+        &self,
+        entries: &[CsarEntry],
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<(PathBuf, ToscaMeta)>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let mut location = None;
+        for tosca_meta_location in tosca_meta_locations() {
+            if has_file(entries, &tosca_meta_location) {
+                if location.is_some() {
+                    problems.give(InvalidError::as_problem("multiple \"TOSCA.meta\" files in CSAR").via(CsarError))?;
+                }
+
+                location = Some(tosca_meta_location);
+            }
+        }
+
+        let Some(location) = location else {
+            problems.give(
+                InvalidError::as_problem(format!("archive does not have \"TOSCA.meta\": {:?}", self.path.display()))
+                    .via(CsarError),
+            )?;
+            return Ok(None);
+        };
+
+        let content = self.read_entry(&location)?;
+        let tosca_meta = ToscaMeta::read(&mut content.as_slice(), problems)?;
+
+        Ok(Some((location, tosca_meta)))
+    }
+}
+
+/// True if the entries have a file at the path.
+///
+/// The paths are compared after normalization with [safe_entry_path].
+pub fn has_file(entries: &[CsarEntry], path: &Path) -> bool {
+    let Ok(path) = safe_entry_path(path) else {
+        return false;
+    };
+
+    entries.iter().any(|entry| {
+        matches!(entry.entry_type, CsarEntryType::File | CsarEntryType::Symlink | CsarEntryType::HardLink)
+            && safe_entry_path(&entry.path).is_ok_and(|entry_path| entry_path == path)
+    })
+}
+
+fn check_definitions<ProblemReceiverT>(
+    entries: &[CsarEntry],
+    definitions: &str,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    ProblemReceiverT: ProblemReceiver,
+{
+    let path = PathBuf::from(definitions);
+    give_unwrap!(safe_entry_path(&path), problems);
+
+    if !has_file(entries, &path) {
+        problems.give(NotFoundError::default_as_problem().with(path).via(CsarError))?;
+    }
+
+    Ok(())
+}
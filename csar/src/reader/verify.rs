@@ -0,0 +1,62 @@
+use super::{
+    super::{errors::*, manifest::*, signature::*},
+    reader::*,
+};
+
+use {problemo::*, std::path::*};
+
+impl CsarReader {
+    /// Verify the signature and the manifest.
+    ///
+    /// Checks that:
+    ///
+    /// * The manifest signature is valid for the public key
+    /// * Every file in the archive is listed in the manifest (except for the signature files
+    ///   themselves), and there are no links
+    /// * The digest of every file matches the one in the manifest
+    /// * Every file listed in the manifest exists in the archive
+    ///
+    /// If the public key is not provided we will use the certificate in the archive. This can
+    /// only detect tampering with the contents, not who signed them, so in that case we also give
+    /// an [UntrustedError] problem.
+    ///
+    /// Returns the manifest if it could be read.
+    pub fn verify<ProblemReceiverT>(
+        &self,
+        public_key: Option<&MinisignPublicKey>,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<Manifest>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let manifest = give_unwrap!(self.read_string(Path::new(MANIFEST_LOCATION)), problems, None);
+        let signature = give_unwrap!(self.read_string(Path::new(SIGNATURE_LOCATION)), problems, None);
+
+        let public_key = match public_key {
+            Some(public_key) => public_key.clone(),
+            None => {
+                let certificate = give_unwrap!(self.read_string(Path::new(CERTIFICATE_LOCATION)), problems, None);
+                let public_key = give_unwrap!(MinisignPublicKey::parse(&certificate), problems, None);
+
+                problems.give(
+                    UntrustedError::as_problem(format!(
+                        "verified with the certificate in the CSAR (key {}), not with a trusted one",
+                        public_key.key_id_string()
+                    ))
+                    .via(CsarError),
+                )?;
+
+                public_key
+            }
+        };
+
+        if let Err(problem) = verify_signature(&manifest, &signature, &public_key) {
+            problems.give(problem)?;
+        }
+
+        let manifest = Manifest::read(&manifest, problems)?;
+        self.check_digests(&manifest, &signature_locations(), problems)?;
+
+        Ok(Some(manifest))
+    }
+}
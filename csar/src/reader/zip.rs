@@ -0,0 +1,170 @@
+use super::{
+    super::{errors::*, format::*},
+    entry::*,
+    path::*,
+    reader::*,
+};
+
+use {
+    problemo::{common::*, *},
+    std::{
+        fs::*,
+        io::{self, Read},
+        path::*,
+    },
+    zip::{ZipArchive, read::ZipFile},
+};
+
+impl CsarReader {
+    pub(crate) fn zip_entries(&self) -> Result<Vec<CsarEntry>, Problem> {
+        let mut archive = self.zip_archive()?;
+
+        let mut entries = Vec::default();
+        for index in 0..archive.len() {
+            entries.push(zip_entry(&mut archive.by_index(index)?)?);
+        }
+
+        Ok(entries)
+    }
+
+    pub(crate) fn zip_write_entry<WriteT>(&self, path: &Path, writer: &mut WriteT) -> Result<bool, Problem>
+    where
+        WriteT: io::Write,
+    {
+        let path = safe_entry_path(path)?;
+        let mut archive = self.zip_archive()?;
+
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if file.is_file() && safe_entry_path(Path::new(file.name()))? == path {
+                io::copy(&mut file, writer)?;
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub(crate) fn zip_visit_files<VisitT>(&self, mut visit: VisitT) -> Result<(), Problem>
+    where
+        VisitT: FnMut(&CsarEntry, &mut dyn io::Read) -> Result<(), Problem>,
+    {
+        let mut archive = self.zip_archive()?;
+
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if file.is_file() {
+                let mut csar_entry = zip_entry(&mut file)?;
+                csar_entry.path = safe_entry_path(&csar_entry.path)?;
+                visit(&csar_entry, &mut file)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn zip_extract(&self, directory: &Path) -> Result<Vec<CsarEntry>, Problem> {
+        let mut archive = self.zip_archive()?;
+
+        let mut entries = Vec::default();
+        let mut symlinks = Vec::default();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            let csar_entry = zip_entry(&mut file)?;
+
+            let entry_path = safe_entry_path(&csar_entry.path)?;
+
+            tracing::debug!("extracting: {:?}", csar_entry.path.display());
+
+            match csar_entry.entry_type {
+                CsarEntryType::Directory => {
+                    let path = extract_path(directory, &entry_path)?;
+                    create_dir_all(&path)?;
+                }
+
+                CsarEntryType::Symlink => {
+                    let Some(link_target) = &csar_entry.link_target else {
+                        continue;
+                    };
+
+                    safe_link_target(&csar_entry.path, link_target)?;
+
+                    // Symbolic links are created last so that no entry can be written through them
+                    symlinks.push((entry_path, link_target.clone()));
+                }
+
+                _ => {
+                    let path = extract_path(directory, &entry_path)?;
+                    let mut writer = io::BufWriter::new(File::create(&path)?);
+                    io::copy(&mut file, &mut writer)?;
+                }
+            }
+
+            entries.push(csar_entry);
+        }
+
+        for (entry_path, link_target) in symlinks {
+            let path = extract_path(directory, &entry_path)?;
+
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(link_target, &path)?;
+
+            #[cfg(not(unix))]
+            tracing::warn!("skipping symbolic link: {:?}", entry_path.display());
+        }
+
+        Ok(entries)
+    }
+
+    fn zip_archive(&self) -> Result<ZipArchive<io::BufReader<File>>, Problem> {
+        match self.format {
+            Format::ZIP => Ok(ZipArchive::new(io::BufReader::new(File::open(&self.path)?))?),
+            _ => Err(UnsupportedError::as_problem("CSAR format").with(self.format).via(CsarError)),
+        }
+    }
+}
+
+fn zip_entry<ReadT>(file: &mut ZipFile<'_, ReadT>) -> Result<CsarEntry, Problem>
+where
+    ReadT: io::Read,
+{
+    let path = PathBuf::from(file.name());
+    let permissions = file.unix_mode();
+    let modified = file
+        .last_modified()
+        .and_then(|modified| chrono::NaiveDateTime::try_from(modified).ok())
+        .map(|modified| modified.and_utc().timestamp().max(0) as u64);
+
+    let entry = if file.is_dir() {
+        CsarEntry::new(path, CsarEntryType::Directory, 0, None)
+    } else if file.is_symlink() {
+        // The content of a symbolic link entry is its target
+        let mut link_target = String::default();
+        file.read_to_string(&mut link_target)?;
+        CsarEntry::new(path, CsarEntryType::Symlink, file.size(), Some(link_target.into()))
+    } else {
+        CsarEntry::new(path, CsarEntryType::File, file.size(), None)
+    };
+
+    Ok(entry.with_metadata(permissions, modified))
+}
+
+// Creates the parent directories of the entry, refusing to write through symbolic links (which
+// could point outside of the directory)
+fn extract_path(directory: &Path, entry_path: &Path) -> Result<PathBuf, Problem> {
+    let mut path = directory.to_path_buf();
+    for segment in entry_path.iter() {
+        path.push(segment);
+        if let Ok(metadata) = symlink_metadata(&path)
+            && metadata.file_type().is_symlink()
+        {
+            return Err(escape_problem(entry_path));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    Ok(path)
+}
@@ -0,0 +1,179 @@
+use super::super::errors::*;
+
+use {
+    base64::{Engine, engine::general_purpose::STANDARD as BASE64},
+    blake2::{Blake2b512, Digest},
+    ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey, pkcs8::DecodePublicKey},
+    problemo::{common::*, *},
+};
+
+// See: https://jedisct1.github.io/minisign/
+
+const ALGORITHM: &[u8; 2] = b"Ed";
+const HASHED_ALGORITHM: &[u8; 2] = b"ED";
+const UNTRUSTED_COMMENT: &str = "untrusted comment: ";
+const TRUSTED_COMMENT: &str = "trusted comment: ";
+
+//
+// MinisignPublicKey
+//
+
+/// Minisign public key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinisignPublicKey {
+    /// Key ID.
+    pub key_id: [u8; 8],
+
+    /// Verifying key.
+    pub verifying_key: VerifyingKey,
+}
+
+impl MinisignPublicKey {
+    /// Constructor.
+    pub fn new(key_id: [u8; 8], verifying_key: VerifyingKey) -> Self {
+        Self { key_id, verifying_key }
+    }
+
+    /// Parse.
+    ///
+    /// Accepts both the minisign format and a public key PEM. For the latter the key ID is
+    /// derived from the key.
+    pub fn parse(text: &str) -> Result<Self, Problem> {
+        if text.trim_start().starts_with("-----BEGIN") {
+            return Ok(verifying_key_from_pem(text)?.into());
+        }
+
+        let bytes = decode_line(text, 0, "public key")?;
+        if (bytes.len() != 42) || (&bytes[..2] != ALGORITHM) {
+            return Err(MalformedError::as_problem("public key is not a minisign Ed25519 key").via(CsarError));
+        }
+
+        let key_id = bytes[2..10].try_into().expect("8 bytes");
+        let verifying_key = VerifyingKey::from_bytes(&bytes[10..].try_into().expect("32 bytes"))
+            .map_err(|error| InvalidError::as_problem(format!("public key: {}", error)).via(CsarError))?;
+
+        Ok(Self::new(key_id, verifying_key))
+    }
+
+    /// Key ID as used by minisign, e.g. in comments.
+    pub fn key_id_string(&self) -> String {
+        format!("{:016X}", u64::from_le_bytes(self.key_id))
+    }
+
+    /// Stringify in the minisign format.
+    pub fn stringify(&self) -> String {
+        let mut bytes = Vec::with_capacity(42);
+        bytes.extend_from_slice(ALGORITHM);
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(self.verifying_key.as_bytes());
+
+        format!("{}minisign public key {}\n{}\n", UNTRUSTED_COMMENT, self.key_id_string(), BASE64.encode(bytes))
+    }
+
+    /// Sign in the minisign format.
+    ///
+    /// The signature is over the BLAKE2b-512 hash of the content, and the global signature is
+    /// over the signature and the trusted comment.
+    pub fn sign(&self, signing_key: &SigningKey, content: &[u8], trusted_comment: &str) -> String {
+        let signature = signing_key.sign(&Blake2b512::digest(content)).to_bytes();
+
+        let mut bytes = Vec::with_capacity(74);
+        bytes.extend_from_slice(HASHED_ALGORITHM);
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(&signature);
+
+        let mut global = Vec::with_capacity(64 + trusted_comment.len());
+        global.extend_from_slice(&signature);
+        global.extend_from_slice(trusted_comment.as_bytes());
+        let global_signature = signing_key.sign(&global).to_bytes();
+
+        format!(
+            "{}signature from puccini-csar secret key {}\n{}\n{}{}\n{}\n",
+            UNTRUSTED_COMMENT,
+            self.key_id_string(),
+            BASE64.encode(bytes),
+            TRUSTED_COMMENT,
+            trusted_comment,
+            BASE64.encode(global_signature)
+        )
+    }
+
+    /// Verify a signature in the minisign format.
+    ///
+    /// Returns the trusted comment.
+    pub fn verify(&self, content: &[u8], signature_text: &str, name: &str) -> Result<String, Problem> {
+        let invalid = || SignatureError::as_problem(name.to_string()).via(CsarError);
+
+        let bytes = decode_line(signature_text, 0, "signature")?;
+        if bytes.len() != 74 {
+            return Err(MalformedError::as_problem("signature is not a minisign Ed25519 signature").via(CsarError));
+        }
+
+        if bytes[2..10] != self.key_id {
+            return Err(InvalidError::as_problem(format!(
+                "signature is not from key {}: {:016X}",
+                self.key_id_string(),
+                u64::from_le_bytes(bytes[2..10].try_into().expect("8 bytes"))
+            ))
+            .via(CsarError));
+        }
+
+        let signature_bytes: [u8; 64] = bytes[10..].try_into().expect("64 bytes");
+        let signature = Signature::from_bytes(&signature_bytes);
+
+        match &bytes[..2] {
+            algorithm if algorithm == HASHED_ALGORITHM => {
+                self.verifying_key.verify_strict(&Blake2b512::digest(content), &signature).map_err(|_| invalid())?
+            }
+
+            algorithm if algorithm == ALGORITHM => {
+                self.verifying_key.verify_strict(content, &signature).map_err(|_| invalid())?
+            }
+
+            _ => return Err(MalformedError::as_problem("unsupported minisign signature algorithm").via(CsarError)),
+        }
+
+        let trusted_comment = signature_text
+            .lines()
+            .nth(2)
+            .and_then(|line| line.strip_prefix(TRUSTED_COMMENT))
+            .ok_or_else(|| MalformedError::as_problem("signature has no trusted comment").via(CsarError))?;
+
+        let global_signature = decode_line(signature_text, 1, "global signature")?;
+        let global_signature = Signature::from_slice(&global_signature)
+            .map_err(|_| MalformedError::as_problem("global signature is not an Ed25519 signature").via(CsarError))?;
+
+        let mut global = Vec::with_capacity(64 + trusted_comment.len());
+        global.extend_from_slice(&signature_bytes);
+        global.extend_from_slice(trusted_comment.as_bytes());
+        self.verifying_key.verify_strict(&global, &global_signature).map_err(|_| invalid())?;
+
+        Ok(trusted_comment.into())
+    }
+}
+
+impl From<VerifyingKey> for MinisignPublicKey {
+    fn from(verifying_key: VerifyingKey) -> Self {
+        // Derived from the key so that the same key always has the same ID
+        let key_id = Blake2b512::digest(verifying_key.as_bytes())[..8].try_into().expect("8 bytes");
+        Self::new(key_id, verifying_key)
+    }
+}
+
+/// Verifying key from a public key PEM.
+pub fn verifying_key_from_pem(pem: &str) -> Result<VerifyingKey, Problem> {
+    VerifyingKey::from_public_key_pem(pem)
+        .map_err(|error| InvalidError::as_problem(format!("public key: {}", error)).via(CsarError))
+}
+
+// Decodes the nth base64 line that is not a comment
+fn decode_line(text: &str, index: usize, name: &str) -> Result<Vec<u8>, Problem> {
+    let line = text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with(UNTRUSTED_COMMENT) && !line.starts_with(TRUSTED_COMMENT))
+        .nth(index)
+        .ok_or_else(|| MalformedError::as_problem(format!("{} is missing", name)).via(CsarError))?;
+
+    BASE64.decode(line).map_err(|_| MalformedError::as_problem(format!("{} is not base64", name)).via(CsarError))
+}
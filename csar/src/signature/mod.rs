@@ -0,0 +1,5 @@
+mod minisign;
+mod signer;
+
+#[allow(unused_imports)]
+pub use {minisign::*, signer::*};
@@ -0,0 +1,97 @@
+use super::{super::errors::*, minisign::*};
+
+use {
+    ed25519_dalek::{SigningKey, pkcs8::DecodePrivateKey},
+    problemo::{common::*, *},
+    std::{fs::*, path::*},
+};
+
+/// Manifest location.
+pub const MANIFEST_LOCATION: &str = "TOSCA-Metadata/TOSCA.mf";
+
+/// Manifest signature location.
+pub const SIGNATURE_LOCATION: &str = "TOSCA-Metadata/TOSCA.mf.sig";
+
+/// Signer certificate location.
+pub const CERTIFICATE_LOCATION: &str = "TOSCA-Metadata/TOSCA.cert";
+
+/// Locations of the signature files, which are not listed in the manifest.
+pub fn signature_locations() -> Vec<PathBuf> {
+    vec![MANIFEST_LOCATION.into(), SIGNATURE_LOCATION.into(), CERTIFICATE_LOCATION.into()]
+}
+
+//
+// CsarSigner
+//
+
+/// CSAR signer.
+///
+/// Signatures are [minisign](https://jedisct1.github.io/minisign/) signatures over the
+/// [Manifest](super::super::manifest::Manifest), detached in their own file, so they can also be verified with the minisign tool.
+/// The "certificate" is the signer's minisign public key.
+#[derive(Clone, Debug)]
+pub struct CsarSigner {
+    /// Signing key.
+    pub signing_key: SigningKey,
+
+    /// Public key.
+    pub public_key: MinisignPublicKey,
+}
+
+impl CsarSigner {
+    /// Constructor.
+    pub fn new(signing_key: SigningKey, public_key: MinisignPublicKey) -> Self {
+        Self { signing_key, public_key }
+    }
+
+    /// Read the signing key (PKCS#8 PEM) and optionally the certificate (minisign public key or
+    /// public key PEM) from files.
+    ///
+    /// When the certificate is not provided it is derived from the signing key.
+    pub fn read(key: &Path, certificate: Option<&Path>) -> Result<Self, Problem> {
+        let signing_key = SigningKey::from_pkcs8_pem(&read_to_string(key)?)
+            .map_err(|error| InvalidError::as_problem(format!("signing key: {}", error)).via(CsarError))?;
+
+        let public_key = match certificate {
+            Some(certificate) => {
+                let public_key = read_public_key(certificate)?;
+                if public_key.verifying_key != signing_key.verifying_key() {
+                    return Err(InvalidError::as_problem("certificate does not match signing key").via(CsarError));
+                }
+                public_key
+            }
+
+            None => signing_key.verifying_key().into(),
+        };
+
+        Ok(Self::new(signing_key, public_key))
+    }
+
+    /// Certificate.
+    ///
+    /// Returns the content of the certificate file.
+    pub fn certificate(&self) -> String {
+        self.public_key.stringify()
+    }
+
+    /// Sign a manifest.
+    ///
+    /// Returns the content of the signature file.
+    pub fn sign(&self, manifest: &str) -> String {
+        self.public_key.sign(&self.signing_key, manifest.as_bytes(), TRUSTED_COMMENT)
+    }
+}
+
+/// Read a public key (minisign public key or public key PEM) from a file.
+pub fn read_public_key(path: &Path) -> Result<MinisignPublicKey, Problem> {
+    MinisignPublicKey::parse(&read_to_string(path)?)
+}
+
+/// Verify the signature of a manifest.
+pub fn verify_signature(manifest: &str, signature: &str, public_key: &MinisignPublicKey) -> Result<(), Problem> {
+    public_key.verify(manifest.as_bytes(), signature, MANIFEST_LOCATION)?;
+    Ok(())
+}
+
+// No timestamp, so that reproducible archives have reproducible signatures
+const TRUSTED_COMMENT: &str = "file:TOSCA.mf\thashed";
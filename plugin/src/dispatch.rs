@@ -64,5 +64,6 @@ impl_dispatch!(arguments, call_site, {
     "apply" => apply(arguments, call_site),
     "schema" => schema(arguments, call_site),
     "select_capability" => select_capability(arguments, call_site),
+    "select_node" => select_node(arguments, call_site),
     "set_inputs" => set_inputs(arguments, call_site),
 });
@@ -1,6 +1,6 @@
 use {
     floria_plugin_sdk::{data::*, *},
-    puccini_plugin_sdk_tosca_2_0::entities::*,
+    puccini_plugin_sdk_tosca_2_0::{data::*, entities::*},
 };
 
 /// The $get_property function is used to retrieve property values of modelable entities in the
@@ -13,6 +13,6 @@ use {
 /// (Documentation copied from
 /// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
 pub fn get_property(arguments: Vec<Expression>, call_site: CallSite) -> DispatchResult {
-    let path_site = call_site.entity()?;
+    let path_site = call_site.site_entity()?;
     follow_tosca_path_to_property_value(path_site, &arguments, true).map(Some)
 }
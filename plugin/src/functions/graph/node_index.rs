@@ -1,4 +1,7 @@
-use floria_plugin_sdk::{data::*, *};
+use {
+    floria_plugin_sdk::{data::*, utils::*, *},
+    puccini_plugin_sdk_tosca_2_0::{data::*, entities::*},
+};
 
 /// This function is used to return the runtime index of the current node representation in the
 /// list of node representations created from the same node template. The first index is 0, which
@@ -8,6 +11,8 @@ use floria_plugin_sdk::{data::*, *};
 ///
 /// (Documentation copied from
 /// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
-pub fn node_index(_arguments: Vec<Expression>, _call_site: CallSite) -> DispatchResult {
-    Ok(None)
+pub fn node_index(arguments: Vec<Expression>, call_site: CallSite) -> DispatchResult {
+    assert_argument_count(&arguments, 0)?;
+    let index = call_site.site_entity()?.tosca_node_index()?;
+    Ok(Some(into_i64(index)?.into()))
 }
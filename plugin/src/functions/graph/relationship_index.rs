@@ -1,4 +1,7 @@
-use floria_plugin_sdk::{data::*, *};
+use {
+    floria_plugin_sdk::{data::*, utils::*, *},
+    puccini_plugin_sdk_tosca_2_0::{data::*, entities::*},
+};
 
 /// This function is used to return the runtime index of the current relationship in the list of
 /// relationships created from the same requirement. The first index is 0. The function should not
@@ -7,6 +10,8 @@ use floria_plugin_sdk::{data::*, *};
 ///
 /// (Documentation copied from
 /// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
-pub fn relationship_index(_arguments: Vec<Expression>, _call_site: CallSite) -> DispatchResult {
-    Ok(None)
+pub fn relationship_index(arguments: Vec<Expression>, call_site: CallSite) -> DispatchResult {
+    assert_argument_count(&arguments, 0)?;
+    let index = call_site.site_entity()?.tosca_relationship_index()?;
+    Ok(Some(into_i64(index)?.into()))
 }
@@ -1,31 +1,46 @@
 use {
     floria_plugin_sdk::{data::*, entities::*, utils::*, *},
-    puccini_plugin_sdk_tosca_2_0::entities::*,
+    puccini_plugin_sdk_tosca_2_0::{data::*, entities::*},
     std::fmt,
 };
 
 /// Select capability. Return its Floria vertex ID.
+///
+/// When more than one capability matches, the relationship index is used to spread the
+/// relationships of a requirement over the candidates. If the requirement is optional and no
+/// capability matches then nothing is returned.
+///
+/// If the target node template is marked with the "select" directive then the nodes in the
+/// inventory are matched instead of the nodes in our service.
 pub fn select_capability(arguments: Vec<Expression>, call_site: CallSite) -> DispatchResult {
     log!("select_capability", "{}", expression_vec_to_string(&arguments));
 
     let constraints = CapabilityConstraints::parse(&arguments)?;
+    let nodes = constraints.nodes(&call_site)?;
 
-    let site = call_site.entity()?;
-    let service = site.tosca_service()?;
-
+    let mut candidates = Vec::default();
     let mut index = 0;
-    for vertex_id in &service.contained_vertex_ids {
+    for vertex_id in &nodes.contained_vertex_ids {
         let node: Vertex = host::get_entity(&vertex_id.clone().into())?.try_into()?;
-        node.assert_tosca(Some(ToscaKind::Node), None)?;
+        if !node.is_tosca(Some(ToscaKind::Node), None) {
+            // Workflows and policies are also contained in services
+            continue;
+        }
+
         let (node_type_matches, node_template_matches, index_matches) = constraints.match_node(&node, index)?;
 
-        if node_type_matches && node_template_matches && index_matches {
+        if node_type_matches
+            && node_template_matches
+            && index_matches
+            && constraints.match_node_filter(constraints.select_node_filter, &node, &call_site)?
+            && constraints.match_node_filter(constraints.node_filter, &node, &call_site)?
+        {
             for vertex_id in &node.contained_vertex_ids {
                 let vertex: Vertex = host::get_entity(&vertex_id.clone().into())?.try_into()?;
                 if vertex.is_tosca(Some(ToscaKind::Capability), None) {
                     if constraints.match_capability(&vertex)? {
                         let id: Id = vertex.id.into();
-                        return Ok(Some(id.to_string().into()));
+                        candidates.push(id);
                     }
                 }
             }
@@ -36,9 +51,45 @@ pub fn select_capability(arguments: Vec<Expression>, call_site: CallSite) -> Dis
         }
     }
 
+    if !candidates.is_empty() {
+        let id = &candidates[constraints.relationship_index % candidates.len()];
+        return Ok(Some(id.to_string().into()));
+    }
+
+    if constraints.optional {
+        return Ok(None);
+    }
+
     Err(format!("TOSCA capability not found: |error|{}|", escape_depiction_markup(constraints)))
 }
 
+/// Select node. Return its Floria vertex ID.
+///
+/// Used for the group members and policy targets that are node templates marked with the "select"
+/// directive. The first node in the inventory that matches is selected.
+pub fn select_node(arguments: Vec<Expression>, call_site: CallSite) -> DispatchResult {
+    log!("select_node", "{}", expression_vec_to_string(&arguments));
+
+    let constraints = CapabilityConstraints::parse(&arguments)?;
+    let nodes = constraints.nodes(&call_site)?;
+
+    for vertex_id in &nodes.contained_vertex_ids {
+        let node: Vertex = host::get_entity(&vertex_id.clone().into())?.try_into()?;
+        if !node.is_tosca(Some(ToscaKind::Node), None) {
+            continue;
+        }
+
+        let (node_type_matches, _, _) = constraints.match_node(&node, 0)?;
+
+        if node_type_matches && constraints.match_node_filter(constraints.select_node_filter, &node, &call_site)? {
+            let id: Id = node.id.into();
+            return Ok(Some(id.to_string().into()));
+        }
+    }
+
+    Err(format!("TOSCA node not found: |error|{}|", escape_depiction_markup(constraints)))
+}
+
 //
 // CapabilityConstraints
 //
@@ -49,7 +100,15 @@ struct CapabilityConstraints<'context> {
     capability_name: Option<&'context str>,
 
     node_type_id: Option<Id>,
-    node_template: Option<(&'context str, usize)>,
+    node_template: Option<(&'context str, Option<usize>)>,
+    node_filter: Option<&'context Expression>,
+
+    select: Option<&'context str>,
+    select_node_filter: Option<&'context Expression>,
+    inventory: Option<&'context str>,
+
+    relationship_index: usize,
+    optional: bool,
 }
 
 impl<'context> CapabilityConstraints<'context> {
@@ -89,10 +148,7 @@ impl<'context> CapabilityConstraints<'context> {
                     if let Some(node_type_name) = argument.into_get("node_type_name") {
                         match node_type_name {
                             Expression::Text(node_type_name) => {
-                                let mut id = Id::parse(EntityKind::Class, node_type_name);
-                                id.directory.insert(0, "node".into());
-                                id.directory.insert(0, "tosca".into());
-                                find_node.node_type_id = Some(id)
+                                find_node.node_type_id = Some(Id::parse(EntityKind::Class, node_type_name))
                             }
 
                             _ => {
@@ -104,20 +160,18 @@ impl<'context> CapabilityConstraints<'context> {
                     if let Some(node_template_name) = argument.into_get("node_template_name") {
                         match node_template_name {
                             Expression::Text(node_template_name) => {
-                                let node_template_index = argument
-                                    .inner
-                                    .get(&"node_template_index".into())
-                                    .ok_or_else(|| "missing node_template_index")?;
-
-                                let node_template_index = match node_template_index {
-                                    Expression::Integer(integer) => *integer as usize,
-                                    Expression::UnsignedInteger(unsigned_integer) => *unsigned_integer as usize,
-                                    _ => {
+                                let node_template_index = match argument.into_get("node_template_index") {
+                                    Some(Expression::Integer(integer)) => Some(*integer as usize),
+                                    Some(Expression::UnsignedInteger(unsigned_integer)) => {
+                                        Some(*unsigned_integer as usize)
+                                    }
+                                    Some(node_template_index) => {
                                         return Err(format!(
                                             "node_template_index not an integer: {}",
                                             node_template_index.type_name()
                                         ));
                                     }
+                                    None => None,
                                 };
 
                                 find_node.node_template = Some((node_template_name, node_template_index));
@@ -131,6 +185,52 @@ impl<'context> CapabilityConstraints<'context> {
                             }
                         }
                     }
+
+                    find_node.node_filter = argument.into_get("node_filter");
+
+                    if let Some(select) = argument.into_get("select") {
+                        match select {
+                            Expression::Text(select) => find_node.select = Some(select),
+                            _ => {
+                                return Err(format!("select not a string: {}", select.type_name()));
+                            }
+                        }
+                    }
+
+                    find_node.select_node_filter = argument.into_get("select_node_filter");
+
+                    if let Some(inventory) = argument.into_get("inventory") {
+                        match inventory {
+                            Expression::Text(inventory) => find_node.inventory = Some(inventory),
+                            _ => {
+                                return Err(format!("inventory not a string: {}", inventory.type_name()));
+                            }
+                        }
+                    }
+
+                    if let Some(relationship_index) = argument.into_get("relationship_index") {
+                        match relationship_index {
+                            Expression::Integer(integer) => find_node.relationship_index = *integer as usize,
+                            Expression::UnsignedInteger(unsigned_integer) => {
+                                find_node.relationship_index = *unsigned_integer as usize
+                            }
+                            _ => {
+                                return Err(format!(
+                                    "relationship_index not an integer: {}",
+                                    relationship_index.type_name()
+                                ));
+                            }
+                        }
+                    }
+
+                    if let Some(optional) = argument.into_get("optional") {
+                        match optional {
+                            Expression::Boolean(optional) => find_node.optional = *optional,
+                            _ => {
+                                return Err(format!("optional not a boolean: {}", optional.type_name()));
+                            }
+                        }
+                    }
                 }
 
                 _ => {
@@ -142,6 +242,23 @@ impl<'context> CapabilityConstraints<'context> {
         Ok(find_node)
     }
 
+    // The inventory for nodes marked with the "select" directive, otherwise our service
+    fn nodes(&self, call_site: &CallSite) -> Result<Vertex, String> {
+        match self.select {
+            Some(select) => match self.inventory {
+                Some(inventory) => Ok(host::get_entity(&Id::parse(EntityKind::Vertex, inventory))?.try_into()?),
+
+                None => Err(format!(
+                    "no inventory for TOSCA |meta|{}| |name|{}| marked with \"select\" directive",
+                    ToscaKind::Node.as_str(),
+                    escape_depiction_markup(select)
+                )),
+            },
+
+            None => call_site.entity()?.tosca_service(),
+        }
+    }
+
     fn match_capability(&self, vertex: &Vertex) -> Result<bool, String> {
         let mut capability_matches = match &self.capability_type_id {
             Some(capability_type_id) => vertex.has_class_id(capability_type_id),
@@ -168,7 +285,7 @@ impl<'context> CapabilityConstraints<'context> {
             match self.node_template {
                 Some((node_template_name, index_)) => {
                     if vertex.is_tosca(Some(ToscaKind::Node), Some(node_template_name)) {
-                        (true, index == index_)
+                        (true, index_.map(|index_| index == index_).unwrap_or(true))
                     } else {
                         (false, false)
                     }
@@ -182,6 +299,24 @@ impl<'context> CapabilityConstraints<'context> {
 
         Ok((node_type_matches, node_template_matches, index_matches))
     }
+
+    // The node filter is evaluated with the candidate node as the site, so that SELF refers to it
+    fn match_node_filter(
+        &self,
+        node_filter: Option<&Expression>,
+        vertex: &Vertex,
+        call_site: &CallSite,
+    ) -> Result<bool, String> {
+        let Some(node_filter) = node_filter else {
+            return Ok(true);
+        };
+
+        push_call_site_entity(Entity::Vertex(vertex.clone()))?;
+        let matches = node_filter.clone().must_evaluate(call_site);
+        pop_call_site_entity()?;
+
+        matches?.cast_bool("node_filter")
+    }
 }
 
 impl<'context> fmt::Display for CapabilityConstraints<'context> {
@@ -213,7 +348,26 @@ impl<'context> fmt::Display for CapabilityConstraints<'context> {
             if separate {
                 write!(formatter, ", ")?;
             }
-            write!(formatter, "node_template: {} {}", node_template, index)?;
+            separate = true;
+            match index {
+                Some(index) => write!(formatter, "node_template: {} {}", node_template, index)?,
+                None => write!(formatter, "node_template: {}", node_template)?,
+            }
+        }
+
+        if self.node_filter.is_some() {
+            if separate {
+                write!(formatter, ", ")?;
+            }
+            separate = true;
+            write!(formatter, "node_filter")?;
+        }
+
+        if let Some(select) = &self.select {
+            if separate {
+                write!(formatter, ", ")?;
+            }
+            write!(formatter, "select: {}", select)?;
         }
 
         Ok(())
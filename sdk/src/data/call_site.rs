@@ -1,5 +1,5 @@
 use {
-    floria_plugin_sdk::{data::*, utils::*, *},
+    floria_plugin_sdk::{data::*, entities::*, utils::*, *},
     std::sync::*,
 };
 
@@ -52,6 +52,45 @@ pub fn pop_call_site_value() -> Result<(), DispatchError> {
     Ok(())
 }
 
+//
+// CallSiteEntity
+//
+
+/// Call site entity.
+pub trait CallSiteEntity {
+    /// Entity.
+    ///
+    /// This is the top of the call site entity stack, if it is not empty, otherwise it is the
+    /// entity of the call site. Functions that evaluate expressions on behalf of another entity,
+    /// such as node filters evaluated for candidate nodes, push that entity to the stack.
+    fn site_entity(&self) -> Result<Entity, DispatchError>;
+}
+
+impl CallSiteEntity for CallSite {
+    fn site_entity(&self) -> Result<Entity, DispatchError> {
+        match entity_stack_top()? {
+            Some(entity) => Ok(entity),
+            None => Ok(self.entity()?),
+        }
+    }
+}
+
+/// Push to the top of the call site entity stack.
+pub fn push_call_site_entity(entity: Entity) -> Result<(), DispatchError> {
+    let mut stack = entity_stack()?.unwrap_or_default();
+    stack.push(entity);
+    set_entity_stack(Some(stack))
+}
+
+/// Remove the top of the call site entity stack.
+pub fn pop_call_site_entity() -> Result<(), DispatchError> {
+    if let Some(mut stack) = entity_stack()? {
+        stack.pop();
+        set_entity_stack(if !stack.is_empty() { Some(stack) } else { None })?;
+    }
+    Ok(())
+}
+
 // Utils
 
 type Static<StaticT> = LazyLock<RwLock<Option<StaticT>>>;
@@ -70,3 +109,18 @@ fn value_stack() -> Result<Option<Vec<Expression>>, DispatchError> {
 fn value_stack_top() -> Result<Option<Expression>, DispatchError> {
     Ok(value_stack()?.and_then(|stack| stack.last().cloned()))
 }
+
+static ENTITY_STACK: Static<Vec<Entity>> = Static::new(|| Default::default());
+
+fn set_entity_stack(stack: Option<Vec<Entity>>) -> Result<(), DispatchError> {
+    *ENTITY_STACK.write().map_escape_depiction_error()? = stack;
+    Ok(())
+}
+
+fn entity_stack() -> Result<Option<Vec<Entity>>, DispatchError> {
+    Ok(ENTITY_STACK.read().map_escape_depiction_error()?.clone())
+}
+
+fn entity_stack_top() -> Result<Option<Entity>, DispatchError> {
+    Ok(entity_stack()?.and_then(|stack| stack.last().cloned()))
+}
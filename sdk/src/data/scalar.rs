@@ -37,12 +37,10 @@ impl Scalar {
     }
 
     /// Constructor.
-    pub fn new_canonical(mut number: Number, unit: &str, schema: ScalarSchema) -> Result<Self, DispatchError> {
-        let factor = schema.unit_factor(unit)?;
-
-        if !factor.is_one() {
-            number = number.mul(factor, false)?;
-        }
+    ///
+    /// Converts the number to the canonical unit.
+    pub fn new_canonical(number: Number, unit: &str, schema: ScalarSchema) -> Result<Self, DispatchError> {
+        let number = schema.to_canonical(number, unit)?;
 
         let number =
             if schema.is_integer() { Number::Integer(number.try_into()?) } else { Number::Float(number.try_into()?) };
@@ -86,7 +84,7 @@ impl Scalar {
 
     /// Canonical number.
     pub fn canonical(&self) -> Result<Number, DispatchError> {
-        if self.is_canonical() { Ok(self.number) } else { self.number.mul(self.schema.canonical_factor, false) }
+        if self.is_canonical() { Ok(self.number) } else { self.schema.to_canonical(self.number, &self.unit) }
     }
 }
 
@@ -57,6 +57,21 @@ impl ScalarSchema {
         find_unit_factor(unit, &self.units, &self.prefixes)
     }
 
+    /// Convert a number in a unit to the canonical unit.
+    pub fn to_canonical(&self, mut number: Number, unit: &str) -> Result<Number, DispatchError> {
+        let factor = self.unit_factor(unit)?;
+
+        if !factor.is_one() {
+            number = number.mul(factor, false)?;
+        }
+
+        if !self.canonical_factor.is_one() {
+            number = number.div(self.canonical_factor, false)?;
+        }
+
+        Ok(number)
+    }
+
     /// True if data kind is integer. Defaults to false.
     pub fn is_integer(&self) -> bool {
         self.data_kind.as_ref().map_or(false, |data_kind| data_kind == INTEGER_DATA_KIND)
@@ -164,21 +179,17 @@ fn find_unit_factor(
     units: &BTreeMap<String, Number>,
     prefixes: &BTreeMap<String, Number>,
 ) -> Result<Number, DispatchError> {
-    if prefixes.is_empty() {
-        for (unit_, unit_factor) in units {
-            if unit == unit_ {
-                return Ok(*unit_factor);
-            }
-        }
-    } else {
-        for (prefix, prefix_factor) in prefixes {
-            for (unit_, unit_factor) in units {
-                let unit_ = prefix.clone() + unit_;
-                if unit == unit_ {
-                    let factor = prefix_factor.mul(*unit_factor, false)?;
-                    return Ok(factor.into());
-                }
-            }
+    // Units are valid without a prefix, too
+    if let Some(unit_factor) = units.get(unit) {
+        return Ok(*unit_factor);
+    }
+
+    for (prefix, prefix_factor) in prefixes {
+        if let Some(unit) = unit.strip_prefix(prefix.as_str())
+            && let Some(unit_factor) = units.get(unit)
+        {
+            let factor = prefix_factor.mul(*unit_factor, false)?;
+            return Ok(factor.into());
         }
     }
 
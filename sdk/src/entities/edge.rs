@@ -1,6 +1,6 @@
 use super::{instance::*, kind::*, vertex::*};
 
-use floria_plugin_sdk::{entities::*, host};
+use floria_plugin_sdk::{entities::*, host, utils::escape_depiction_markup};
 
 //
 // ToscaEdge
@@ -19,6 +19,9 @@ pub trait ToscaEdge {
 
     /// TOSCA relationship's service.
     fn tosca_service(&self) -> Result<Vertex, String>;
+
+    /// TOSCA relationship's index in the list of relationships created from the same requirement.
+    fn tosca_relationship_index(&self) -> Result<usize, String>;
 }
 
 impl ToscaEdge for Edge {
@@ -46,4 +49,31 @@ impl ToscaEdge for Edge {
     fn tosca_service(&self) -> Result<Vertex, String> {
         self.tosca_source_node()?.tosca_service()
     }
+
+    fn tosca_relationship_index(&self) -> Result<usize, String> {
+        let Some(requirement_name) = self.tosca_name() else {
+            return Ok(0);
+        };
+
+        // Relationships are outgoing from the source node in the order in which they were created
+        let node = self.tosca_source_node()?;
+        let mut index = 0;
+        for id in &node.outgoing_edge_ids {
+            if *id == self.id {
+                return Ok(index);
+            }
+
+            let edge: Edge = host::get_entity(&id.clone().into())?.try_into()?;
+            if edge.is_tosca(Some(ToscaKind::Relationship), Some(&requirement_name)) {
+                index += 1;
+            }
+        }
+
+        Err(format!(
+            "TOSCA |meta|{}| |name|{}| not outgoing from its |meta|{}|",
+            ToscaKind::Relationship.as_str(),
+            escape_depiction_markup(self.id()),
+            ToscaKind::Node.as_str()
+        ))
+    }
 }
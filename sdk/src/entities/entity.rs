@@ -1,6 +1,6 @@
 use super::{edge::*, instance::*, kind::*, selector::*, vertex::*};
 
-use floria_plugin_sdk::entities::*;
+use floria_plugin_sdk::{entities::*, utils::escape_depiction_markup};
 
 //
 // ToscaEntity
@@ -14,6 +14,16 @@ pub trait ToscaEntity {
     /// The TOSCA service vertex to which this entity belongs.
     fn into_tosca_service(self) -> Result<Vertex, String>;
 
+    /// The index of the TOSCA node in the list of nodes created from the same node template.
+    ///
+    /// For capabilities and interfaces this is the index of the containing node, and for
+    /// relationships it is the index of the source node.
+    fn tosca_node_index(&self) -> Result<usize, String>;
+
+    /// The index of the TOSCA relationship in the list of relationships created from the same
+    /// requirement.
+    fn tosca_relationship_index(&self) -> Result<usize, String>;
+
     /// A TOSCA node in the service to which this entity belongs.
     fn tosca_node(&self, node_template_name: &str, selector: ToscaSelector) -> Result<Vertex, String> {
         self.tosca_service()?.tosca_node(node_template_name, selector)
@@ -40,4 +50,36 @@ impl ToscaEntity for Entity {
             Entity::Edge(edge) => edge.tosca_service(),
         }
     }
+
+    fn tosca_node_index(&self) -> Result<usize, String> {
+        match self {
+            Entity::Vertex(vertex) => match vertex.tosca_kind() {
+                Some(ToscaKind::Node) => vertex.tosca_node_index(),
+
+                Some(kind @ (ToscaKind::Capability | ToscaKind::Interface)) => {
+                    vertex.must_tosca_containing_node(kind, ToscaKind::Node)?.tosca_node_index()
+                }
+
+                _ => Err(format!(
+                    "|name|{}| not in a TOSCA |meta|{}|",
+                    escape_depiction_markup(vertex.id()),
+                    ToscaKind::Node.as_str()
+                )),
+            },
+
+            Entity::Edge(edge) => edge.tosca_source_node()?.tosca_node_index(),
+        }
+    }
+
+    fn tosca_relationship_index(&self) -> Result<usize, String> {
+        match self {
+            Entity::Edge(edge) => edge.tosca_relationship_index(),
+
+            Entity::Vertex(vertex) => Err(format!(
+                "|name|{}| not a TOSCA |meta|{}|",
+                escape_depiction_markup(vertex.id()),
+                ToscaKind::Relationship.as_str()
+            )),
+        }
+    }
 }
@@ -24,6 +24,24 @@ pub enum ToscaKind {
 
     /// Parameter.
     Parameter,
+
+    /// Workflow.
+    Workflow,
+
+    /// Workflow step.
+    WorkflowStep,
+
+    /// Workflow activity.
+    WorkflowActivity,
+
+    /// Group.
+    Group,
+
+    /// Policy.
+    Policy,
+
+    /// Trigger.
+    Trigger,
 }
 
 impl ToscaKind {
@@ -36,6 +54,12 @@ impl ToscaKind {
             Self::Interface => "interface",
             Self::Relationship => "relationship",
             Self::Parameter => "parameter",
+            Self::Workflow => "workflow",
+            Self::WorkflowStep => "workflow_step",
+            Self::WorkflowActivity => "workflow_activity",
+            Self::Group => "group",
+            Self::Policy => "policy",
+            Self::Trigger => "trigger",
         }
     }
 }
@@ -51,6 +75,12 @@ impl FromStr for ToscaKind {
             "interface" => Ok(Self::Interface),
             "relationship" => Ok(Self::Relationship),
             "parameter" => Ok(Self::Parameter),
+            "workflow" => Ok(Self::Workflow),
+            "workflow_step" => Ok(Self::WorkflowStep),
+            "workflow_activity" => Ok(Self::WorkflowActivity),
+            "group" => Ok(Self::Group),
+            "policy" => Ok(Self::Policy),
+            "trigger" => Ok(Self::Trigger),
             _ => Err(()),
         }
     }
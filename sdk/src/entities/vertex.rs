@@ -36,6 +36,9 @@ pub trait ToscaVertex {
 
     /// TOSCA service's node.
     fn tosca_node(&self, node_template_name: &str, _selector: ToscaSelector) -> Result<Vertex, String>;
+
+    /// TOSCA node's index in the list of nodes created from the same node template.
+    fn tosca_node_index(&self) -> Result<usize, String>;
 }
 
 impl ToscaVertex for Vertex {
@@ -132,6 +135,49 @@ impl ToscaVertex for Vertex {
                     node.tosca_service()
                 }
 
+                ToscaKind::Workflow | ToscaKind::Group | ToscaKind::Policy => {
+                    let service = self.must_tosca_containing_node(kind, ToscaKind::Service)?;
+                    service.assert_tosca(Some(ToscaKind::Service), None)?;
+                    Ok(service)
+                }
+
+                ToscaKind::Trigger => {
+                    let policy = self.must_tosca_containing_node(ToscaKind::Trigger, ToscaKind::Policy)?;
+                    policy.tosca_service()
+                }
+
+                ToscaKind::WorkflowStep => {
+                    let workflow = self.must_tosca_containing_node(ToscaKind::WorkflowStep, ToscaKind::Workflow)?;
+                    workflow.tosca_service()
+                }
+
+                // Activities are contained in workflow steps or in policy triggers
+                ToscaKind::WorkflowActivity => match self.tosca_containing_node()? {
+                    Some(container) => match container.tosca_kind() {
+                        Some(ToscaKind::WorkflowStep | ToscaKind::Trigger) => container.tosca_service(),
+
+                        container_kind => Err(format!(
+                            "TOSCA |meta|{}| |name|{}| contained in {}, not in |meta|{}| or |meta|{}|",
+                            kind.as_str(),
+                            escape_depiction_markup(self.id()),
+                            match container_kind {
+                                Some(container_kind) => format!("|meta|{}|", container_kind.as_str()),
+                                None => "non-TOSCA vertex".into(),
+                            },
+                            ToscaKind::WorkflowStep.as_str(),
+                            ToscaKind::Trigger.as_str(),
+                        )),
+                    },
+
+                    None => Err(format!(
+                        "TOSCA |meta|{}| |name|{}| not contained in |meta|{}| or |meta|{}|",
+                        kind.as_str(),
+                        escape_depiction_markup(self.id()),
+                        ToscaKind::WorkflowStep.as_str(),
+                        ToscaKind::Trigger.as_str(),
+                    )),
+                },
+
                 _ => Err(format!(
                     "|name|{}| not a TOSCA |meta|{}|, |meta|{}|, |meta|{}|, or |meta|{}|",
                     escape_depiction_markup(self.id()),
@@ -157,4 +203,33 @@ impl ToscaVertex for Vertex {
 
         Err("not found".into())
     }
+
+    fn tosca_node_index(&self) -> Result<usize, String> {
+        self.assert_tosca(Some(ToscaKind::Node), None)?;
+
+        let Some(node_template_name) = self.tosca_name() else {
+            return Ok(0);
+        };
+
+        // Nodes are contained in the service in the order in which they were created
+        let service = self.tosca_service()?;
+        let mut index = 0;
+        for id in &service.contained_vertex_ids {
+            if *id == self.id {
+                return Ok(index);
+            }
+
+            let vertex: Vertex = host::get_entity(&id.clone().into())?.try_into()?;
+            if vertex.is_tosca(Some(ToscaKind::Node), Some(&node_template_name)) {
+                index += 1;
+            }
+        }
+
+        Err(format!(
+            "TOSCA |meta|{}| |name|{}| not contained in its |meta|{}|",
+            ToscaKind::Node.as_str(),
+            escape_depiction_markup(self.id()),
+            ToscaKind::Service.as_str()
+        ))
+    }
 }
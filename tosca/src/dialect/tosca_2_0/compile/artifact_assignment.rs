@@ -1,6 +1,6 @@
 use super::super::{super::super::grammar::*, dialect::*, entities::*};
 
-use {compris::annotate::*, problemo::*};
+use {compris::annotate::*, kutil::std::immutable::*, problemo::*};
 
 impl<AnnotatedT> ArtifactAssignment<AnnotatedT>
 where
@@ -27,6 +27,11 @@ where
         vertex_template.template.metadata.set_tosca_version(self.artifact_version.as_ref());
         vertex_template.template.metadata.set_tosca_custom_metadata(&self.metadata);
 
+        if let Some(file) = &self.file {
+            let url = artifact_url::<AnnotatedT>(file, self.repository.as_ref(), context)?;
+            vertex_template.template.metadata.set_tosca_metadata("url", url);
+        }
+
         for (name, value_assignment) in &self.properties {
             vertex_template
                 .template
@@ -37,3 +42,39 @@ where
         Ok(())
     }
 }
+
+// Artifact files that name a repository are located relative to the repository URL, otherwise relative
+// files in an archive (e.g. a CSAR) are located relative to the archive root
+//
+// Placeholders are expanded here, like they are for imports, so that plugins and artifact URLs carry
+// the repository credentials when they are read
+pub(crate) fn artifact_url<AnnotatedT>(
+    file: &ByteString,
+    repository: Option<&FullName>,
+    context: &mut CompilationContext,
+) -> Result<ByteString, Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    let url = match repository {
+        Some(repository) => {
+            let (repository, _source) = give_unwrap!(
+                context.catalog.entity::<RepositoryDefinition<AnnotatedT>>(REPOSITORY, repository, context.source_id),
+                &mut context.problems,
+                file.clone()
+            );
+            repository.file_url(file)
+        }
+
+        None => match context.source_id {
+            SourceID::URL(url) => match join_archive_root_url(url, file) {
+                Some(url) => url.into(),
+                None => file.clone(),
+            },
+
+            _ => file.clone(),
+        },
+    };
+
+    Ok(give_unwrap!(expand_url_placeholders(&url), &mut context.problems, url).into())
+}
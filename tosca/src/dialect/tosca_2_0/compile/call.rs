@@ -3,11 +3,15 @@ use super::{
     plugin::*,
 };
 
-use {compris::annotate::*, problemo::*, std::mem::*};
+use {
+    compris::{annotate::*, errors::*},
+    problemo::*,
+    std::mem::*,
+};
 
 impl<AnnotatedT> Call<AnnotatedT> {
     /// Compile to a Floria expression.
-    pub fn compile(self, context: &mut CompilationContext) -> Result<floria::Expression, Problem>
+    pub fn compile(mut self, context: &mut CompilationContext) -> Result<floria::Expression, Problem>
     where
         AnnotatedT: 'static + Annotated + Clone + Default,
     {
@@ -16,31 +20,66 @@ impl<AnnotatedT> Call<AnnotatedT> {
             &mut context.problems.with_fallback_annotations(self.annotations())
         );
 
-        // TODO: find signature
+        let signature = self.check_signature(function, context)?;
+
+        if context.fold_constants
+            && let Some(value) = self.fold(context)?
+        {
+            return Ok(value.into());
+        }
 
         let mut plugin = {
-            if let Some(plugin) = function.floria_plugin(&mut context.with_source(&source.source_id))? {
+            // Prefer the selected signature's implementation
+            let plugin = match signature {
+                Some(signature) if signature.implementation.is_some() => {
+                    signature.floria_plugin(&mut context.with_source(&source.source_id))?
+                }
+
+                _ => function.floria_plugin(&mut context.with_source(&source.source_id))?,
+            };
+
+            if let Some(plugin) = plugin {
                 plugin
             } else {
+                if signature.or(function.signatures.first()).is_some_and(|signature| signature.implementation.is_some())
+                {
+                    context.problems.with_fallback_annotations(self.annotations()).give(MalformedError::as_problem(
+                        "function implementation",
+                        "not a Wasm artifact definition",
+                    ))?;
+                }
+
                 return Ok(floria::Expression::Undefined);
             }
         };
 
-        // TODO: other artifact types?
-
         let function = take(&mut plugin.function).unwrap_or(self.function.name.0);
 
         let Some(plugin_id) = plugin.get_or_create(None, context)? else {
             return Ok(floria::Expression::Undefined);
         };
 
-        let mut arguments = Vec::with_capacity(self.arguments.len());
-        for argument in self.arguments {
-            let argument =
-                give_unwrap!(argument.compile(context), &mut context.problems, floria::Expression::Undefined);
-            arguments.push(argument);
-        }
+        // The arguments have already been folded (above) so we won't fold them again
+        let fold_constants = replace(&mut context.fold_constants, false);
+        let arguments = compile_arguments(self.arguments, context);
+        context.fold_constants = fold_constants;
+        let arguments = arguments?;
 
         Ok(floria::Call::new(plugin_id, function, arguments, self.kind)?.into())
     }
 }
+
+fn compile_arguments<AnnotatedT>(
+    arguments: Vec<Expression<AnnotatedT>>,
+    context: &mut CompilationContext,
+) -> Result<Vec<floria::Expression>, Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    let mut compiled_arguments = Vec::with_capacity(arguments.len());
+    for argument in arguments {
+        let argument = give_unwrap!(argument.compile(context), &mut context.problems, floria::Expression::Undefined);
+        compiled_arguments.push(argument);
+    }
+    Ok(compiled_arguments)
+}
@@ -0,0 +1,194 @@
+use super::super::{super::super::grammar::*, data::*, entities::*, functions::*};
+
+use {
+    compris::{annotate::*, normal::*},
+    problemo::*,
+    std::mem::*,
+};
+
+/// Built-in functions that can be folded.
+///
+/// These are the functions that we can [evaluate natively](evaluate_native).
+pub const FOLDABLE_FUNCTIONS: &[&str] = NATIVE_FUNCTIONS;
+
+impl<AnnotatedT> Call<AnnotatedT> {
+    /// Fold a call to a pure built-in function into its value.
+    ///
+    /// The arguments are folded first and, whether or not this call can be folded, the nested
+    /// calls that can be are replaced with their values. Thus compiling the arguments afterwards
+    /// does not fold (or give problems for) them again. Evaluation errors, such as division by
+    /// zero, are given as problems.
+    ///
+    /// Returns [None] if the call cannot be folded.
+    pub fn fold(&mut self, context: &mut CompilationContext) -> Result<Option<Variant<AnnotatedT>>, Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        let mut arguments = Some(Vec::with_capacity(self.arguments.len()));
+        for argument in &mut self.arguments {
+            match argument.fold(context)? {
+                Some(argument) => {
+                    if let Some(arguments) = &mut arguments {
+                        arguments.push(argument);
+                    }
+                }
+
+                None => arguments = None,
+            }
+        }
+
+        let Some(arguments) = arguments else {
+            return Ok(None);
+        };
+
+        if !FOLDABLE_FUNCTIONS.iter().any(|function| self.function.name.0 == *function) {
+            return Ok(None);
+        }
+
+        // Make sure it's the built-in function and not a user-defined one with the same name
+        match context.catalog.entity::<FunctionDefinition<AnnotatedT>>(FUNCTION, &self.function, context.source_id) {
+            Ok((function, _source)) if function.internal => {}
+            _ => return Ok(None),
+        }
+
+        match evaluate_native(&self.function.name.0, arguments) {
+            Ok(value) => Ok(value),
+
+            Err(reason) => {
+                context
+                    .problems
+                    .with_fallback_annotations(self.annotations())
+                    .give(EvaluationError::as_problem(&self.function, reason))?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl<AnnotatedT> Expression<AnnotatedT> {
+    /// Fold into a literal value.
+    ///
+    /// Lists and maps are folded if all their items are. Calls are folded if they are foldable
+    /// and are then replaced with their values, including nested calls within expressions that
+    /// cannot be folded as a whole.
+    ///
+    /// Returns [None] if the expression cannot be folded.
+    pub fn fold(&mut self, context: &mut CompilationContext) -> Result<Option<Variant<AnnotatedT>>, Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        Ok(match self {
+            Self::Simple(simple) => Some(simple.clone()),
+
+            Self::List(list) => {
+                let mut items = Some(Vec::with_capacity(list.len()));
+                for item in list {
+                    match item.fold(context)? {
+                        Some(item) => {
+                            if let Some(items) = &mut items {
+                                items.push(item);
+                            }
+                        }
+
+                        None => items = None,
+                    }
+                }
+                items.map(|items| items.into_iter().collect::<List<_>>().into())
+            }
+
+            Self::Map(map) => {
+                let mut folded = Some(Map::default());
+                for (mut key, mut value) in take(map) {
+                    match (key.fold(context)?, value.fold(context)?) {
+                        (Some(folded_key), Some(folded_value)) => {
+                            if let Some(folded) = &mut folded {
+                                folded.into_insert(folded_key, folded_value);
+                            }
+                        }
+
+                        _ => folded = None,
+                    }
+                    map.insert(key, value);
+                }
+                folded.map(|folded| folded.into())
+            }
+
+            Self::Call(call) => {
+                let value = call.fold(context)?;
+                if let Some(value) = &value {
+                    *self = Self::Simple(value.clone());
+                }
+                value
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Variant = compris::normal::Variant<WithoutAnnotations>;
+
+    fn evaluate_ok(function: &str, arguments: Vec<Variant>) -> Option<Variant> {
+        evaluate_native(function, arguments).expect("evaluation")
+    }
+
+    fn evaluate_err(function: &str, arguments: Vec<Variant>) -> String {
+        evaluate_native(function, arguments).err().expect("evaluation error")
+    }
+
+    #[test]
+    fn sum() {
+        assert!(evaluate_ok("sum", vec![1i64.into(), 2i64.into(), 3i64.into()]) == Some(6i64.into()));
+        assert!(evaluate_ok("sum", vec![1.5.into(), 2i64.into()]) == Some(3.5.into()));
+        assert_eq!(evaluate_err("sum", vec![i64::MAX.into(), 1i64.into()]), OVERFLOW);
+    }
+
+    #[test]
+    fn difference_and_product() {
+        assert!(evaluate_ok("difference", vec![5i64.into(), 7i64.into()]) == Some((-2i64).into()));
+        assert!(evaluate_ok("difference", vec![5i64.into(), 1.5.into()]) == Some(3.5.into()));
+        assert!(evaluate_ok("product", vec![2i64.into(), 3i64.into(), 4i64.into()]) == Some(24i64.into()));
+        assert!(evaluate_ok("product", vec![2i64.into(), 0.25.into()]) == Some(0.5.into()));
+        assert_eq!(evaluate_err("product", vec![i64::MAX.into(), 2i64.into()]), OVERFLOW);
+        assert_eq!(evaluate_err("difference", vec![i64::MIN.into(), 1i64.into()]), OVERFLOW);
+    }
+
+    #[test]
+    fn quotient_and_remainder() {
+        assert!(evaluate_ok("quotient", vec![7i64.into(), 2i64.into()]) == Some(3.5.into()));
+        assert_eq!(evaluate_err("quotient", vec![1i64.into(), 0i64.into()]), DIVISION_BY_ZERO);
+        assert_eq!(evaluate_err("quotient", vec![1i64.into(), 0.0.into()]), DIVISION_BY_ZERO);
+
+        assert!(evaluate_ok("remainder", vec![7i64.into(), 3i64.into()]) == Some(1i64.into()));
+        assert_eq!(evaluate_err("remainder", vec![7i64.into(), 0i64.into()]), DIVISION_BY_ZERO);
+        assert_eq!(evaluate_err("remainder", vec![i64::MIN.into(), (-1i64).into()]), OVERFLOW);
+    }
+
+    #[test]
+    fn rounding() {
+        assert!(evaluate_ok("round", vec![2.5.into()]) == Some(3i64.into()));
+        assert!(evaluate_ok("floor", vec![2.7.into()]) == Some(2i64.into()));
+        assert!(evaluate_ok("ceil", vec![2.1.into()]) == Some(3i64.into()));
+        assert_eq!(evaluate_err("round", vec![1e300.into()]), OVERFLOW);
+        assert_eq!(evaluate_err("floor", vec![f64::NAN.into()]), OVERFLOW);
+    }
+
+    #[test]
+    fn argument_errors() {
+        assert_eq!(evaluate_err("sum", vec![]), "expected at least 1 arguments, got 0");
+        assert_eq!(evaluate_err("difference", vec![1i64.into()]), "expected 2 arguments, got 1");
+        assert_eq!(
+            evaluate_err("sum", vec![String::from("a").into()]),
+            "argument is string, expected integer or float or scalar"
+        );
+        assert_eq!(evaluate_err("sum", vec![1i64.into(), true.into()]), "argument is boolean, expected integer");
+    }
+
+    #[test]
+    fn left_to_plugin() {
+        assert!(evaluate_ok("sum", vec![1u64.into(), 2u64.into()]).is_none());
+        assert!(evaluate_ok("matches", vec![String::from("a").into(), String::from("a").into()]).is_none());
+    }
+}
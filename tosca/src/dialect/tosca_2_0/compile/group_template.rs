@@ -0,0 +1,71 @@
+use super::{
+    super::{super::super::grammar::*, dialect::*, entities::*},
+    value_assignment::*,
+};
+
+use {compris::annotate::*, problemo::*, std::collections::*};
+
+impl<AnnotatedT> GroupTemplate<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    ///
+    /// Members are compiled as edge templates to their node template vertex templates, or to the
+    /// nodes selected from the inventory for node templates marked with the "select" directive.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        name: Name,
+        node_template_selectors: &BTreeMap<Name, floria::VertexSelector>,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.class_ids.add_tosca_type(GROUP_TYPE, GROUP_TYPE_NAME, &self.type_name, context)?;
+
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, GROUP_TEMPLATE_NAME);
+        vertex_template.template.metadata.set_tosca_name(name);
+        vertex_template.template.metadata.set_tosca_description(self.description.as_ref());
+        vertex_template.template.metadata.set_tosca_custom_metadata(&self.metadata);
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            &self.properties,
+            "",
+            PROPERTY_NAME,
+            true,
+            context,
+        )?;
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            &self.attributes,
+            "",
+            ATTRIBUTE_NAME,
+            false,
+            context,
+        )?;
+
+        // Members
+
+        let group_id = vertex_template.template.id.clone();
+
+        for member in &self.members {
+            // Undeclared members have already been reported during completion
+            if let Some(node_template_selector) = node_template_selectors.get(member) {
+                let mut floria_member = floria::EdgeTemplate::new_with_name(
+                    context.directory.clone(),
+                    member.to_floria_name_contained(GROUP_MEMBER_NAME, &group_id.name),
+                    group_id.clone(),
+                    node_template_selector.clone(),
+                )?;
+
+                floria_member.template.metadata.set_tosca_entity_static(DIALECT_ID, GROUP_MEMBER_NAME);
+                floria_member.template.metadata.set_tosca_name(member.clone());
+
+                give_unwrap!(context.store.add_edge_template(floria_member), &mut context.problems);
+            }
+        }
+
+        Ok(())
+    }
+}
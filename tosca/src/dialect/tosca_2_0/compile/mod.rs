@@ -2,10 +2,19 @@ mod artifact_assignment;
 mod call;
 mod capability_assignment;
 mod expression;
+mod fold;
+mod group_template;
 mod interface_assignment;
 mod node_template;
 mod operation_assignment;
 mod plugin;
+mod policy_template;
 mod requirement_assignment;
 mod service_template;
+mod signature;
+mod substitution_mapping;
 mod value_assignment;
+mod workflow_definition;
+
+#[allow(unused_imports)]
+pub use fold::*;
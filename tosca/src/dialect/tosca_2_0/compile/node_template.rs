@@ -1,9 +1,16 @@
 use super::{
     super::{super::super::grammar::*, dialect::*, entities::*},
+    plugin::*,
+    requirement_assignment::*,
     value_assignment::*,
 };
 
-use {compris::annotate::*, problemo::*};
+use {
+    compris::{annotate::*, normal::*},
+    kutil::std::immutable::*,
+    problemo::*,
+    std::collections::*,
+};
 
 impl<AnnotatedT> NodeTemplate<AnnotatedT>
 where
@@ -28,6 +35,7 @@ where
         vertex_template.template.metadata.set_tosca_description(self.description.as_ref());
         vertex_template.template.metadata.set_tosca_custom_metadata(&self.metadata);
         vertex_template.template.metadata.set_tosca_directives(&self.directives);
+        vertex_template.template.metadata.set_tosca_count(self.count.unwrap_or(1));
 
         compile_value_assignments(
             &mut vertex_template.template.property_templates,
@@ -51,4 +59,74 @@ where
 
         Ok(())
     }
+
+    /// Compile the arguments for selecting the node from the inventory.
+    ///
+    /// The "select_capability" finder matches the inventory's nodes by node type and node filter.
+    /// The node type is matched by its Floria class in the inventory's directory.
+    pub fn compile_select(
+        &self,
+        name: &Name,
+        context: &mut CompilationContext,
+    ) -> Result<BTreeMap<floria::Expression, floria::Expression>, Problem>
+    where
+        AnnotatedT: 'static,
+    {
+        let mut arguments = BTreeMap::default();
+
+        insert_argument(&mut arguments, "select", name.clone().as_byte_string());
+
+        if let Some(inventory) = context.inventory.clone() {
+            insert_argument(&mut arguments, "inventory", inventory.to_string());
+
+            // The inventory's classes are in its own directory
+            if let Some(type_name) = &self.type_name
+                && let Some(id) =
+                    tosca_type_class_id_in(NODE_TYPE, NODE_TYPE_NAME, type_name, inventory.directory, context)?
+            {
+                if give_unwrap!(context.store.get_class(&id), &mut context.problems).is_some() {
+                    insert_argument(&mut arguments, "node_type_name", id.to_string());
+                } else {
+                    // Selecting without the type would match any node in the inventory
+                    context.problems.give(
+                        UndeclaredError::as_problem("node type in inventory", type_name)
+                            .with_annotations_from_field(self, "type_name"),
+                    )?;
+                }
+            }
+        }
+
+        if let Some(node_filter) = &self.node_filter {
+            // Lazy, because it is evaluated by the finder for each candidate node
+            if let Some(node_filter) =
+                node_filter.clone().into_lazy().compile(context).give_ok(&mut context.problems)?
+            {
+                arguments.insert(Variant::<WithoutAnnotations>::from("select_node_filter").into(), node_filter);
+            }
+        }
+
+        Ok(arguments)
+    }
+}
+
+/// Floria selector for the node selected from the inventory.
+///
+/// Uses a finder that calls "select_node" with the arguments compiled by
+/// [NodeTemplate::compile_select].
+pub(crate) fn select_node_vertex_selector(
+    select: &BTreeMap<floria::Expression, floria::Expression>,
+    context: &mut CompilationContext,
+) -> Result<Option<floria::VertexSelector>, Problem> {
+    let Some(plugin_id) = Plugin::get_or_create_implicit(context)? else {
+        return Ok(None);
+    };
+
+    let finder = floria::Call::new(
+        plugin_id,
+        ByteString::from_static("select_node"),
+        vec![select.clone().into()],
+        floria::CallKind::Normal,
+    )?;
+
+    Ok(Some(floria::VertexSelector::new_finder(finder)))
 }
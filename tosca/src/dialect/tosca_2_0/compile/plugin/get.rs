@@ -1,5 +1,8 @@
 use super::{
-    super::super::{super::super::grammar::*, dialect::*, entities::*},
+    super::{
+        super::{super::super::grammar::*, dialect::*, entities::*},
+        artifact_assignment::*,
+    },
     plugin::*,
 };
 
@@ -25,12 +28,22 @@ where
             &mut context.problems.with_fallback_annotations_from_field(self, "type_name")
         );
 
-        // TODO: more than internal
-        Ok(if artifact_type.internal {
+        // Any artifact type derived from Wasm, including user-defined ones
+        let wasm = artifact_type.internal
+            || context.catalog.is_type_derived_from_in::<ArtifactType<AnnotatedT>, AnnotatedT>(
+                ARTIFACT_TYPE,
+                &self.type_name,
+                context.source_id,
+                &WASM_ARTIFACT_TYPE.into(),
+                &PUCCINI_SOURCE_ID,
+            );
+
+        Ok(if wasm {
             let global = self.properties.get_boolean_value_assignment("global").unwrap_or_default();
             let function = self.properties.get_text_value_assignment("function");
             let event = self.properties.get_text_value_assignment("event");
-            Some(Plugin::new(self.file.clone(), global, function, event))
+            let url = artifact_url::<AnnotatedT>(&self.file, self.repository.as_ref(), context)?;
+            Some(Plugin::new(url, global, function, event))
         } else {
             None
         })
@@ -66,6 +79,18 @@ where
     }
 }
 
+impl<AnnotatedT> GetFloriaPlugin for WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn floria_plugin(&self, context: &mut CompilationContext) -> Result<Option<Plugin>, Problem> {
+        match &self.implementation {
+            Some(implementation_definition) => implementation_definition.floria_plugin(context),
+            None => Ok(None),
+        }
+    }
+}
+
 impl<AnnotatedT> GetFloriaPlugin for FunctionSignature<AnnotatedT>
 where
     AnnotatedT: 'static + Annotated + Clone + Default,
@@ -0,0 +1,133 @@
+use super::{
+    super::{super::super::grammar::*, dialect::*, entities::*},
+    value_assignment::*,
+    workflow_definition::*,
+};
+
+use {compris::annotate::*, kutil::std::immutable::*, problemo::*, std::collections::*};
+
+impl<AnnotatedT> PolicyTemplate<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    ///
+    /// Targets are compiled as edge templates to their node template or group vertex templates, or
+    /// to the nodes selected from the inventory for node templates marked with the "select"
+    /// directive.
+    /// Triggers are compiled as contained vertex templates, with their action activities as vertex
+    /// templates contained in them, in sequence.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        name: Name,
+        node_template_selectors: &BTreeMap<Name, floria::VertexSelector>,
+        group_ids: &BTreeMap<Name, floria::ID>,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.class_ids.add_tosca_type(POLICY_TYPE, POLICY_TYPE_NAME, &self.type_name, context)?;
+
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, POLICY_TEMPLATE_NAME);
+        vertex_template.template.metadata.set_tosca_name(name);
+        vertex_template.template.metadata.set_tosca_description(self.description.as_ref());
+        vertex_template.template.metadata.set_tosca_custom_metadata(&self.metadata);
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            &self.properties,
+            "",
+            PROPERTY_NAME,
+            true,
+            context,
+        )?;
+
+        let policy_id = vertex_template.template.id.clone();
+
+        // Targets
+
+        for target in &self.targets {
+            // Undeclared targets have already been reported during completion
+            let target_selector = node_template_selectors
+                .get(target)
+                .cloned()
+                .or_else(|| group_ids.get(target).map(|group_id| floria::VertexSelector::new_vertex(group_id.clone())));
+
+            if let Some(target_selector) = target_selector {
+                let mut floria_target = floria::EdgeTemplate::new_with_name(
+                    context.directory.clone(),
+                    target.to_floria_name_contained(POLICY_TARGET_NAME, &policy_id.name),
+                    policy_id.clone(),
+                    target_selector,
+                )?;
+
+                floria_target.template.metadata.set_tosca_entity_static(DIALECT_ID, POLICY_TARGET_NAME);
+                floria_target.template.metadata.set_tosca_name(target.clone());
+
+                give_unwrap!(context.store.add_edge_template(floria_target), &mut context.problems);
+            }
+        }
+
+        // Triggers
+
+        for (trigger_name, trigger) in &self.triggers {
+            let mut floria_trigger = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                trigger_name.to_floria_name_contained(TRIGGER_NAME, &policy_id.name),
+                Some(policy_id.clone()),
+            )?;
+
+            trigger.compile(&mut floria_trigger, trigger_name.clone(), context)?;
+
+            vertex_template.contained_vertex_template_ids.push(floria_trigger.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_trigger), &mut context.problems);
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> TriggerDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        name: Name,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, TRIGGER_NAME);
+        vertex_template.template.metadata.set_tosca_name(name);
+        vertex_template.template.metadata.set_tosca_description(self.description.as_ref());
+        vertex_template.template.metadata.set_tosca_metadata("event", self.event.clone());
+
+        if let Some(condition) = &self.condition {
+            vertex_template
+                .template
+                .property_templates
+                .insert(ByteString::from_static("condition"), compile_condition(condition, context)?);
+        }
+
+        // Action
+
+        let trigger_id = vertex_template.template.id.clone();
+
+        for (index, activity) in self.action.iter().enumerate() {
+            let mut floria_activity = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                index.to_floria_name_contained(WORKFLOW_ACTIVITY_NAME, &trigger_id.name),
+                Some(trigger_id.clone()),
+            )?;
+
+            activity.compile(&mut floria_activity, context)?;
+
+            vertex_template.contained_vertex_template_ids.push(floria_activity.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_activity), &mut context.problems);
+        }
+
+        Ok(())
+    }
+}
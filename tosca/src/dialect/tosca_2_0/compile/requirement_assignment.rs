@@ -1,9 +1,15 @@
 use super::{
     super::{super::super::grammar::*, dialect::*, entities::*},
+    plugin::*,
     value_assignment::*,
 };
 
-use {compris::annotate::*, problemo::*};
+use {
+    compris::{annotate::*, errors::*, normal::*},
+    kutil::std::immutable::*,
+    problemo::*,
+    std::collections::*,
+};
 
 impl<AnnotatedT> RequirementAssignment<AnnotatedT>
 where
@@ -49,165 +55,180 @@ where
         Ok(())
     }
 
-    // /// Compile to Floria.
-    // pub fn compile_to_floria<StoreT, ProblemReceiverT>(
-    //     &self,
-    //     context: CompileToFloriaContext<OldCatalog<'_, AnnotatedT>, StoreT>,
-    //     requirement_name: &str,
-    //     node_template_id: floria::ID,
-    //     node_type: &NodeType<AnnotatedT>,
-    //     errors: &mut ProblemReceiverT,
-    // ) -> Result<Option<floria::ID>, ToscaError<AnnotatedT>>
-    // where
-    //     StoreT: floria::Store,
-    //     ProblemReceiverT: ProblemReceiver<ToscaError<AnnotatedT>>,
-    // {
-    //     let finder = match self.new_finder(context.catalog, context.index) {
-    //         Ok(finder) => finder,
-    //         Err(error) => {
-    //             errors.give(error)?;
-    //             return Ok(None);
-    //         }
-    //     };
-
-    //     let mut floria_namespace = context.floria_directory.clone();
-    //     floria_namespace.push(requirement_name.into());
-
-    //     let mut floria_relationship_template = match floria::RelationshipTemplate::new(
-    //         floria_namespace.clone(),
-    //         node_template_id,
-    //         floria::NodeSelector::new_finder(finder),
-    //         context.store,
-    //     ) {
-    //         Ok(floria_relationship_template) => floria_relationship_template,
-    //         Err(error) => {
-    //             errors.give(error)?;
-    //             return Ok(None);
-    //         }
-    //     };
-
-    //     floria_relationship_template.template.metadata.set_tosca_entity("RequirementAssignment");
-    //     floria_relationship_template.template.metadata.set_tosca_directives(&self.directives);
-
-    //     if let Some(relationship_assignment) = &self.relationship {
-    //         match node_type.requirements.get_first(&requirement_name.into()) {
-    //             Some(requirement_definition) => {
-    //                 // Properties and attributes
-    //                 floria_relationship_template.template.property_templates =
-    //                     relationship_assignment.properties.compile_to_floria_as_properties(
-    //                         &requirement_definition.relationship.properties,
-    //                         context.catalog,
-    //                         context.index,
-    //                         errors,
-    //                     )?;
-    //                 floria_relationship_template.template.property_templates.extend(
-    //                     relationship_assignment.attributes.compile_to_floria_as_attributes(
-    //                         &requirement_definition.relationship.attributes,
-    //                         context.catalog,
-    //                         context.index,
-    //                         errors,
-    //                     )?,
-    //                 );
-
-    //                 context.catalog.relationship_types.add_floria_group_ids(
-    //                     &mut floria_relationship_template.template.group_ids,
-    //                     &"relationship".into(),
-    //                     context.index.index.get(&requirement_definition.relationship.type_name).unwrap(),
-    //                 );
-    //             }
-
-    //             None => tracing::warn!("requirement definition not found: {}", requirement_name),
-    //         }
-    //     }
-
-    //     let id = floria_relationship_template.template.id.clone();
-    //     if let Err(error) = context.store.add_relationship_template(floria_relationship_template) {
-    //         errors.give(error)?;
-    //         return Ok(None);
-    //     }
-
-    //     Ok(Some(id))
-    // }
-
-    // fn new_finder(
-    //     &self,
-    //     catalog: &OldCatalog<'_, AnnotatedT>,
-    //     index: &Index,
-    // ) -> Result<floria::Call, ToscaError<AnnotatedT>> {
-    //     let mut finder = floria::Call::new(get_dispatch_name("select_capability"), Default::default());
-
-    //     let mut argument = Map::default();
-
-    //     if let Some(capability) = self.get_capability(catalog)? {
-    //         match capability {
-    //             RequirementAssignmentCapability::TypeName(type_name) => {
-    //                 argument.into_insert("capability_type_name", type_name.to_string());
-    //             }
-
-    //             RequirementAssignmentCapability::Name(name) => {
-    //                 argument.into_insert("capability_name", name.to_string());
-    //             }
-    //         }
-    //     }
-
-    //     if let Some(node) = self.get_node(catalog, index)? {
-    //         match node {
-    //             RequirementAssignmentNode::TypeName(type_name) => {
-    //                 argument.into_insert("node_type_name", type_name.to_string());
-    //             }
-
-    //             RequirementAssignmentNode::Template((template_name, index)) => {
-    //                 argument.into_insert("node_template_name", template_name.to_string());
-    //                 argument.into_insert("node_template_index", index);
-    //             }
-    //         }
-    //     }
-
-    //     if !argument.inner.is_empty() {
-    //         finder.arguments.push(floria::Expression::Literal(argument.into()));
-    //     }
-
-    //     Ok(finder)
-    // }
-
-    // fn get_capability(
-    //     &self,
-    //     _catalog: &OldCatalog<'_, AnnotatedT>,
-    // ) -> Result<Option<RequirementAssignmentCapability>, ToscaError<AnnotatedT>> {
-    //     // TODO: capability type name or capability name
-    //     Ok(self.capability.as_ref().map(|name| RequirementAssignmentCapability::Name(name.name.clone())))
-    // }
-
-    // fn get_node(
-    //     &self,
-    //     catalog: &OldCatalog<'_, AnnotatedT>,
-    //     index: &Index,
-    // ) -> Result<Option<RequirementAssignmentNode>, ToscaError<AnnotatedT>> {
-    //     match &self.node {
-    //         Some(node) => {
-    //             match node.index {
-    //                 // node type name or node template name
-    //                 // TODO
-    //                 None => match index.index.get(&node.full_name) {
-    //                     Some(node_id) => match catalog.node_types.has_id(node_id) {
-    //                         true => Ok(Some(RequirementAssignmentNode::TypeName(node.full_name.clone()))),
-
-    //                         false => Err(UnknownTypeError::new(
-    //                             node.full_name.to_string(),
-    //                             "RequirementAssignment.get_node".into(),
-    //                         )
-    //                         .into()),
-    //                     },
-
-    //                     None => Ok(None),
-    //                 },
-
-    //                 // node template name and index
-    //                 Some(index) => Ok(Some(RequirementAssignmentNode::Template((node.full_name.name.clone(), index)))),
-    //             }
-    //         }
-
-    //         None => Ok(None),
-    //     }
-    // }
+    /// Floria selector for the target capability vertex.
+    ///
+    /// If the target is a named capability of a node template with a single node then we can
+    /// select its vertex directly. Otherwise we use a finder that calls "select_capability" to
+    /// match the node (type or template), the capability (type or name), and the node filter at
+    /// instantiation. If the target node template is marked with the "select" directive then the
+    /// node is matched in the inventory instead.
+    pub fn floria_vertex_selector(
+        &self,
+        relationship_index: usize,
+        targets: &RequirementTargets,
+        context: &mut CompilationContext,
+    ) -> Result<Option<floria::VertexSelector>, Problem>
+    where
+        AnnotatedT: 'static,
+    {
+        let mut arguments = BTreeMap::default();
+
+        match &self.target_node {
+            Some(RequirementAssignmentNode::Template((node_template_name, index))) => {
+                let Some(target) = targets.get(node_template_name) else {
+                    context.problems.give(
+                        UndeclaredError::as_problem("node template", node_template_name)
+                            .with_annotations_from_field(self, "node"),
+                    )?;
+                    return Ok(None);
+                };
+
+                if target.count == 0 {
+                    // An optional requirement is simply not satisfied
+                    if !self.optional {
+                        context.problems.give(
+                            MalformedError::as_problem(
+                                "requirement",
+                                format!("target node template {:?} has a count of 0", node_template_name.to_string()),
+                            )
+                            .with_annotations_from_field(self, "node"),
+                        )?;
+                    }
+                    return Ok(None);
+                }
+
+                if let Some(index) = index
+                    && (*index as u64 >= target.count)
+                {
+                    context.problems.give(
+                        MalformedError::as_problem(
+                            "requirement",
+                            format!(
+                                "target node template {:?} has a count of {}: index {}",
+                                node_template_name.to_string(),
+                                target.count,
+                                index
+                            ),
+                        )
+                        .with_annotations_from_field(self, "node"),
+                    )?;
+                    return Ok(None);
+                }
+
+                if let Some(select) = &target.select {
+                    // The node will be selected from the inventory
+                    arguments.extend(select.clone());
+                } else {
+                    if let Some(RequirementAssignmentCapability::Name(capability_name)) = &self.target_capability {
+                        let Some(capability_id) = target.capability_ids.get(capability_name) else {
+                            context.problems.give(
+                                UndeclaredError::as_problem("capability", capability_name)
+                                    .with_annotations_from_field(self, "capability"),
+                            )?;
+                            return Ok(None);
+                        };
+
+                        if self.node_filter.is_none() && (target.count == 1) && (index.unwrap_or_default() == 0) {
+                            return Ok(Some(floria::VertexSelector::new_vertex(capability_id.clone())));
+                        }
+                    }
+
+                    insert_argument(&mut arguments, "node_template_name", node_template_name.clone().as_byte_string());
+                    if let Some(index) = index {
+                        insert_argument(&mut arguments, "node_template_index", *index as u64);
+                    }
+                }
+            }
+
+            Some(RequirementAssignmentNode::TypeName(type_name)) => {
+                if let Some(id) = tosca_type_class_id(NODE_TYPE, NODE_TYPE_NAME, type_name, context)? {
+                    insert_argument(&mut arguments, "node_type_name", id.to_string());
+                }
+            }
+
+            None => {}
+        }
+
+        match &self.target_capability {
+            Some(RequirementAssignmentCapability::TypeName(type_name)) => {
+                if let Some(id) = tosca_type_class_id(CAPABILITY_TYPE, CAPABILITY_TYPE_NAME, type_name, context)? {
+                    insert_argument(&mut arguments, "capability_type_name", id.to_string());
+                }
+            }
+
+            Some(RequirementAssignmentCapability::Name(capability_name)) => {
+                insert_argument(&mut arguments, "capability_name", capability_name.clone().as_byte_string());
+            }
+
+            None => {}
+        }
+
+        if let Some(node_filter) = &self.node_filter {
+            // Lazy, because it is evaluated by the finder for each candidate node
+            if let Some(node_filter) =
+                node_filter.clone().into_lazy().compile(context).give_ok(&mut context.problems)?
+            {
+                arguments.insert(Variant::<WithoutAnnotations>::from("node_filter").into(), node_filter);
+            }
+        }
+
+        if relationship_index != 0 {
+            insert_argument(&mut arguments, "relationship_index", relationship_index as u64);
+        }
+
+        if self.optional {
+            insert_argument(&mut arguments, "optional", true);
+        }
+
+        let Some(plugin_id) = Plugin::get_or_create_implicit(context)? else {
+            return Ok(None);
+        };
+
+        let finder = floria::Call::new(
+            plugin_id,
+            ByteString::from_static("select_capability"),
+            vec![arguments.into()],
+            floria::CallKind::Normal,
+        )?;
+
+        Ok(Some(floria::VertexSelector::new_finder(finder)))
+    }
+}
+
+//
+// RequirementTarget
+//
+
+/// Compiled node template that can be the target of a requirement.
+#[derive(Clone, Debug, Default)]
+pub struct RequirementTarget {
+    /// Node count.
+    pub count: u64,
+
+    /// Capability vertex template IDs.
+    pub capability_ids: BTreeMap<Name, floria::ID>,
+
+    /// Arguments for selecting the node from the inventory, for node templates marked with the
+    /// "select" directive.
+    pub select: Option<BTreeMap<floria::Expression, floria::Expression>>,
+}
+
+//
+// RequirementTargets
+//
+
+/// Map of [RequirementTarget] by node template name.
+pub type RequirementTargets = BTreeMap<Name, RequirementTarget>;
+
+// Utils
+
+pub(crate) fn insert_argument<ValueT>(
+    arguments: &mut BTreeMap<floria::Expression, floria::Expression>,
+    key: &'static str,
+    value: ValueT,
+) where
+    Variant<WithoutAnnotations>: From<ValueT>,
+{
+    arguments
+        .insert(Variant::<WithoutAnnotations>::from(key).into(), Variant::<WithoutAnnotations>::from(value).into());
 }
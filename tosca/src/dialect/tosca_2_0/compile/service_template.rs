@@ -4,11 +4,14 @@ use super::{
         dialect::{Dialect as Dialect2_0, *},
         entities::*,
     },
+    node_template::*,
     plugin::*,
+    requirement_assignment::*,
+    substitution_mapping::*,
     value_assignment::*,
 };
 
-use {compris::annotate::*, floria::AddEventHandler, kutil::std::immutable::*, problemo::*};
+use {compris::annotate::*, floria::AddEventHandler, kutil::std::immutable::*, problemo::*, std::collections::*};
 
 impl Dialect2_0 {
     /// Compile service template to Floria.
@@ -16,6 +19,27 @@ impl Dialect2_0 {
         &self,
         context: &mut CompilationContext,
     ) -> Result<Option<floria::ID>, Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        Ok(self
+            .compile_service_template_with::<AnnotatedT>(None, &Vec::default(), context)?
+            .map(|(service_template_id, _substitution)| service_template_id))
+    }
+
+    /// Compile service template to Floria.
+    ///
+    /// When compiled as a substitution, the substituted node template's properties are mapped to
+    /// inputs. Note that only the compiled properties are copied, so their expressions are
+    /// evaluated in the substituting service. The returned [Substitution] has the vertex template
+    /// IDs for the mapped capabilities and requirements. Mapped requirements are not compiled here,
+    /// because they are satisfied by the substituted node template's requirements.
+    pub(crate) fn compile_service_template_with<AnnotatedT>(
+        &self,
+        substituted_properties: Option<&BTreeMap<ByteString, floria::Property>>,
+        substituted_source_ids: &Vec<SourceID>,
+        context: &mut CompilationContext,
+    ) -> Result<Option<(floria::ID, Substitution)>, Problem>
     where
         AnnotatedT: 'static + Annotated + Clone + Default,
     {
@@ -32,12 +56,34 @@ impl Dialect2_0 {
 
         let service_template_kind_name = self.implementation.entity_kinds.represent(SERVICE_TEMPLATE);
 
+        let mut substitution_mappings = None;
+
         match source.entity::<ServiceTemplate<AnnotatedT>>(
             SERVICE_TEMPLATE,
             &service_template_kind_name,
             &Default::default(),
         ) {
-            Ok(service_template) => service_template.compile(&mut floria_service_template, context)?,
+            Ok(service_template) => {
+                service_template.compile(&mut floria_service_template, context)?;
+
+                if let Some(substituted_properties) = substituted_properties
+                    && let Some(mappings) = &service_template.substitution_mappings
+                {
+                    for (name, mapping) in &mappings.properties {
+                        if let Some(property) = substituted_properties.get(&name.0) {
+                            let mut input = property.clone();
+                            input.metadata.set_tosca_entity_static(DIALECT_ID, PARAMETER_NAME);
+                            floria_service_template
+                                .template
+                                .property_templates
+                                .insert(format!("input:{}", mapping.parameter).into(), input);
+                        }
+                    }
+
+                    substitution_mappings = Some(mappings);
+                }
+            }
+
             Err(problem) => {
                 if problem.has_error_type::<UndeclaredError>() {
                     return Ok(None);
@@ -113,6 +159,17 @@ impl Dialect2_0 {
         let node_template_kind_name = self.implementation.entity_kinds.represent(NODE_TEMPLATE);
         let policy_template_kind_name = self.implementation.entity_kinds.represent(POLICY_TEMPLATE);
 
+        // Requirements, groups, and policies are compiled after all node templates, so that their
+        // targets are known
+        let mut requirement_targets = RequirementTargets::default();
+        let mut requirement_sources = Vec::default();
+        let mut node_template_selectors = BTreeMap::default();
+        let mut group_class_ids = BTreeMap::<_, Vec<_>>::default();
+        let mut group_templates = Vec::default();
+        let mut policy_templates = Vec::default();
+        let mut substitutes = Vec::default();
+
+        // Sorting puts group templates before node templates
         let mut entity_names: Vec<_> = source.entity_names().collect();
         entity_names.sort();
         for (entity_kind, name) in entity_names {
@@ -125,18 +182,27 @@ impl Dialect2_0 {
                         "compiling"
                     );
 
-                    if let Some(_group_template) = source
+                    if let Some(group_template) = source
                         .entity::<GroupTemplate<AnnotatedT>>(GROUP_TEMPLATE, &group_template_kind_name, name)
                         .give_ok(&mut context.problems)?
                     {
-                        let floria_group_template = floria::Class::new_with_name(
+                        // The class is for membership; the vertex template is compiled after all
+                        // node templates
+                        let mut floria_group_class = floria::Class::new_with_name(
                             context.directory.clone(),
                             name.to_floria_name(GROUP_TEMPLATE_NAME),
                         )?;
 
-                        // TODO
+                        floria_group_class.metadata.set_tosca_entity_static(DIALECT_ID, GROUP_TEMPLATE_NAME);
+                        floria_group_class.metadata.set_tosca_name(name.clone());
+
+                        for member in &group_template.members {
+                            group_class_ids.entry(member.clone()).or_default().push(floria_group_class.id.clone());
+                        }
+
+                        give_unwrap!(context.store.add_class(floria_group_class), &mut context.problems);
 
-                        give_unwrap!(context.store.add_class(floria_group_template), &mut context.problems);
+                        group_templates.push((name, group_template));
                     }
                 }
 
@@ -152,90 +218,148 @@ impl Dialect2_0 {
                         .entity::<NodeTemplate<AnnotatedT>>(NODE_TEMPLATE, &node_template_kind_name, name)
                         .give_ok(&mut context.problems)?
                     {
-                        let floria_id = name.to_floria_name(NODE_TEMPLATE_NAME);
-
-                        let mut floria_node_template = floria::VertexTemplate::new_with_name(
-                            context.directory.clone(),
-                            floria_id.clone(),
-                            Some(floria_service_template_id.clone()),
-                        )?;
-
-                        node_template.compile(&mut floria_node_template, name.clone(), context)?;
-
-                        // Capabilities
-                        for (name, capability) in &node_template.capabilities {
-                            let mut floria_capability = floria::VertexTemplate::new_with_name(
-                                context.directory.clone(),
-                                name.to_floria_name_contained(CAPABILITY_NAME, &floria_id),
-                                Some(floria_node_template.template.id.clone()),
-                            )?;
-
-                            capability.compile(&mut floria_capability, name.clone(), context)?;
-
-                            floria_node_template
-                                .contained_vertex_template_ids
-                                .push(floria_capability.template.id.clone());
-
-                            give_unwrap!(context.store.add_vertex_template(floria_capability), &mut context.problems);
+                        // Instead of being created, the node is selected from the inventory
+                        if node_template.is_select() {
+                            let select = node_template.compile_select(name, context)?;
+
+                            // Group members and policy targets are selected, too
+                            if let Some(selector) = select_node_vertex_selector(&select, context)? {
+                                node_template_selectors.insert(name.clone(), selector);
+                            }
+
+                            requirement_targets.insert(
+                                name.clone(),
+                                RequirementTarget {
+                                    count: 1,
+                                    capability_ids: Default::default(),
+                                    select: Some(select),
+                                },
+                            );
+                            continue;
                         }
 
-                        // Requirements
-                        for (name, requirement) in &node_template.requirements {
-                            // TODO
-                            let node_selector =
-                                floria::VertexSelector::new_vertex(floria_node_template.template.id.clone());
-
-                            let mut floria_requirement = floria::EdgeTemplate::new_with_name(
-                                context.directory.clone(),
-                                name.to_floria_name_contained(REQUIREMENT_NAME, &floria_id),
-                                floria_node_template.template.id.clone(),
-                                node_selector,
-                            )?;
-
-                            requirement.compile(&mut floria_requirement, name.clone(), context)?;
-
-                            give_unwrap!(context.store.add_edge_template(floria_requirement), &mut context.problems);
+                        // Each node is its own vertex template, so that Floria instantiates all
+                        // of them (also in substitutions) in order of their node indexes
+                        let count = node_template.count.unwrap_or(1);
+                        if count == 0 {
+                            requirement_targets.insert(name.clone(), RequirementTarget { count, ..Default::default() });
+                            continue;
                         }
 
-                        // Interfaces
-                        for (name, interface) in &node_template.interfaces {
-                            let mut floria_interface = floria::VertexTemplate::new_with_name(
-                                context.directory.clone(),
-                                name.to_floria_name_contained(INTERFACE_NAME, &floria_id),
-                                Some(floria_node_template.template.id.clone()),
-                            )?;
-
-                            interface.compile(&mut floria_interface, context)?;
-
-                            floria_node_template
-                                .contained_vertex_template_ids
-                                .push(floria_interface.template.id.clone());
+                        for index in 0..count {
+                            let floria_id = node_floria_name(name, index);
 
-                            give_unwrap!(context.store.add_vertex_template(floria_interface), &mut context.problems);
-                        }
-
-                        // Artifacts
-                        for (name, artifact) in &node_template.artifacts {
-                            let mut floria_artifact = floria::VertexTemplate::new_with_name(
+                            let mut floria_node_template = floria::VertexTemplate::new_with_name(
                                 context.directory.clone(),
-                                name.to_floria_name_contained(ARTIFACT_NAME, &floria_id),
-                                Some(floria_node_template.template.id.clone()),
+                                floria_id.clone(),
+                                Some(floria_service_template_id.clone()),
                             )?;
 
-                            artifact.compile(&mut floria_artifact, context)?;
-
-                            floria_node_template
+                            node_template.compile(&mut floria_node_template, name.clone(), context)?;
+
+                            if let Some(group_class_ids) = group_class_ids.get(name) {
+                                floria_node_template.template.class_ids.extend(group_class_ids.iter().cloned());
+                            }
+
+                            // Groups and policies target the first node
+                            if index == 0 {
+                                node_template_selectors.insert(
+                                    name.clone(),
+                                    floria::VertexSelector::new_vertex(floria_node_template.template.id.clone()),
+                                );
+                            }
+
+                            if node_template.is_substitute() {
+                                substitutes.push((
+                                    name,
+                                    index,
+                                    node_template,
+                                    floria_node_template.template.id.clone(),
+                                    floria_node_template.template.property_templates.clone(),
+                                ));
+                            }
+
+                            // Capabilities
+                            let mut capability_ids = BTreeMap::default();
+                            for (name, capability) in &node_template.capabilities {
+                                let mut floria_capability = floria::VertexTemplate::new_with_name(
+                                    context.directory.clone(),
+                                    name.to_floria_name_contained(CAPABILITY_NAME, &floria_id),
+                                    Some(floria_node_template.template.id.clone()),
+                                )?;
+
+                                capability.compile(&mut floria_capability, name.clone(), context)?;
+
+                                floria_node_template
+                                    .contained_vertex_template_ids
+                                    .push(floria_capability.template.id.clone());
+                                capability_ids.insert(name.clone(), floria_capability.template.id.clone());
+
+                                give_unwrap!(
+                                    context.store.add_vertex_template(floria_capability),
+                                    &mut context.problems
+                                );
+                            }
+
+                            // Named capabilities are selected directly only for a single node
+                            if index == 0 {
+                                requirement_targets
+                                    .insert(name.clone(), RequirementTarget { count, capability_ids, select: None });
+                            }
+
+                            requirement_sources.push((
+                                name,
+                                node_template,
+                                floria_node_template.template.id.clone(),
+                                floria_id.clone(),
+                            ));
+
+                            // Interfaces
+                            for (name, interface) in &node_template.interfaces {
+                                let mut floria_interface = floria::VertexTemplate::new_with_name(
+                                    context.directory.clone(),
+                                    name.to_floria_name_contained(INTERFACE_NAME, &floria_id),
+                                    Some(floria_node_template.template.id.clone()),
+                                )?;
+
+                                interface.compile(&mut floria_interface, context)?;
+
+                                floria_node_template
+                                    .contained_vertex_template_ids
+                                    .push(floria_interface.template.id.clone());
+
+                                give_unwrap!(
+                                    context.store.add_vertex_template(floria_interface),
+                                    &mut context.problems
+                                );
+                            }
+
+                            // Artifacts
+                            for (name, artifact) in &node_template.artifacts {
+                                let mut floria_artifact = floria::VertexTemplate::new_with_name(
+                                    context.directory.clone(),
+                                    name.to_floria_name_contained(ARTIFACT_NAME, &floria_id),
+                                    Some(floria_node_template.template.id.clone()),
+                                )?;
+
+                                artifact.compile(&mut floria_artifact, context)?;
+
+                                floria_node_template
+                                    .contained_vertex_template_ids
+                                    .push(floria_artifact.template.id.clone());
+
+                                give_unwrap!(context.store.add_vertex_template(floria_artifact), &mut context.problems);
+                            }
+
+                            floria_service_template
                                 .contained_vertex_template_ids
-                                .push(floria_artifact.template.id.clone());
+                                .push(floria_node_template.template.id.clone());
 
-                            give_unwrap!(context.store.add_vertex_template(floria_artifact), &mut context.problems);
+                            give_unwrap!(
+                                context.store.add_vertex_template(floria_node_template),
+                                &mut context.problems
+                            );
                         }
-
-                        floria_service_template
-                            .contained_vertex_template_ids
-                            .push(floria_node_template.template.id.clone());
-
-                        give_unwrap!(context.store.add_vertex_template(floria_node_template), &mut context.problems);
                     }
                 }
 
@@ -247,33 +371,166 @@ impl Dialect2_0 {
                         "compiling"
                     );
 
-                    if let Some(_policy_template) = source
-                        .entity::<PolicyTemplate<WithAnnotations>>(POLICY_TEMPLATE, &policy_template_kind_name, name)
+                    if let Some(policy_template) = source
+                        .entity::<PolicyTemplate<AnnotatedT>>(POLICY_TEMPLATE, &policy_template_kind_name, name)
                         .give_ok(&mut context.problems)?
                     {
-                        let floria_policy_template = floria::VertexTemplate::new_with_name(
-                            context.directory.clone(),
-                            name.to_floria_name(POLICY_TEMPLATE_NAME),
-                            Some(floria_service_template_id.clone()),
-                        )?;
+                        policy_templates.push((name, policy_template));
+                    }
+                }
 
-                        // TODO
+                _ => {}
+            }
+        }
 
-                        floria_service_template
-                            .contained_vertex_template_ids
-                            .push(floria_policy_template.template.id.clone());
+        // Mapped capabilities and requirements of the node template we are substituting
+        let mut substitution = Substitution::default();
+        if let Some(substitution_mappings) = substitution_mappings {
+            for (name, mapping) in &substitution_mappings.capabilities {
+                if let Some(target) = requirement_targets.get(&mapping.node_template)
+                    && let Some(capability_id) = target.capability_ids.get(&mapping.name)
+                {
+                    substitution.capability_ids.insert(name.clone(), capability_id.clone());
+                }
+            }
 
-                        give_unwrap!(context.store.add_vertex_template(floria_policy_template), &mut context.problems);
-                    }
+            for (name, mapping) in &substitution_mappings.requirements {
+                if let Some((_, _, node_template_id, _)) = requirement_sources
+                    .iter()
+                    .find(|(node_template_name, ..)| **node_template_name == mapping.node_template)
+                {
+                    substitution.requirement_source_ids.insert(name.clone(), node_template_id.clone());
+                    substitution.mapped_requirements.insert((mapping.node_template.clone(), mapping.name.clone()));
                 }
+            }
+        }
 
-                _ => {}
+        // Substitutions
+        // Requirements and capabilities of substituted node templates are rewired into the
+        // substituting topology according to its mappings
+        let mut substituted_requirement_source_ids = BTreeMap::<_, BTreeMap<_, _>>::default();
+        for (name, index, node_template, node_template_id, properties) in substitutes {
+            if let Some((substitution_id, node_substitution)) =
+                self.compile_substitution(name, index, node_template, &properties, substituted_source_ids, context)?
+            {
+                let floria_id = node_template_id.name.clone();
+
+                let mut floria_substitution = floria::EdgeTemplate::new_with_name(
+                    context.directory.clone(),
+                    SERVICE_TEMPLATE_NAME.to_floria_name_contained(SUBSTITUTION_NAME, &floria_id),
+                    node_template_id,
+                    floria::VertexSelector::new_vertex(substitution_id),
+                )?;
+
+                floria_substitution.template.metadata.set_tosca_entity_static(DIALECT_ID, SUBSTITUTION_NAME);
+
+                give_unwrap!(context.store.add_edge_template(floria_substitution), &mut context.problems);
+
+                if index == 0
+                    && let Some(target) = requirement_targets.get_mut(name)
+                {
+                    target.capability_ids.extend(node_substitution.capability_ids);
+                }
+
+                substituted_requirement_source_ids.insert(floria_id, node_substitution.requirement_source_ids);
+            }
+        }
+
+        // Requirements
+        for (node_template_name, node_template, floria_node_template_id, floria_id) in requirement_sources {
+            let substituted_requirement_source_ids = substituted_requirement_source_ids.get(&floria_id);
+
+            // The relationship index counts relationships created from the same requirement
+            let mut relationship_indexes = BTreeMap::<_, usize>::default();
+
+            for (name, requirement) in &node_template.requirements {
+                // Mapped requirements are satisfied by the requirements of the node template we
+                // are substituting
+                if substitution.mapped_requirements.contains(&(node_template_name.clone(), name.clone())) {
+                    continue;
+                }
+
+                // The relationships of a substituted node template's mapped requirement start at
+                // the mapped node in the substituting topology
+                let source_id = substituted_requirement_source_ids
+                    .and_then(|source_ids| source_ids.get(name))
+                    .unwrap_or(&floria_node_template_id);
+
+                for _ in 0..requirement.count {
+                    let relationship_index = {
+                        let next_relationship_index = relationship_indexes.entry(name.clone()).or_default();
+                        let relationship_index = *next_relationship_index;
+                        *next_relationship_index += 1;
+                        relationship_index
+                    };
+
+                    let Some(vertex_selector) =
+                        requirement.floria_vertex_selector(relationship_index, &requirement_targets, context)?
+                    else {
+                        continue;
+                    };
+
+                    let mut floria_name = name.to_floria_name_contained(REQUIREMENT_NAME, &floria_id);
+                    if relationship_index != 0 {
+                        floria_name =
+                            format!("{}{}{}", floria_name, FLORIA_ID_NAME_DELIMITER, relationship_index).into();
+                    }
+
+                    let mut floria_requirement = floria::EdgeTemplate::new_with_name(
+                        context.directory.clone(),
+                        floria_name,
+                        source_id.clone(),
+                        vertex_selector,
+                    )?;
+
+                    requirement.compile(&mut floria_requirement, name.clone(), context)?;
+
+                    give_unwrap!(context.store.add_edge_template(floria_requirement), &mut context.problems);
+                }
             }
         }
 
+        // Groups
+        let mut group_ids = BTreeMap::default();
+        for (name, group_template) in group_templates {
+            let mut floria_group_template = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                name.to_floria_name(GROUP_TEMPLATE_NAME),
+                Some(floria_service_template_id.clone()),
+            )?;
+
+            group_template.compile(&mut floria_group_template, name.clone(), &node_template_selectors, context)?;
+
+            floria_service_template.contained_vertex_template_ids.push(floria_group_template.template.id.clone());
+            group_ids.insert(name.clone(), floria_group_template.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_group_template), &mut context.problems);
+        }
+
+        // Policies
+        for (name, policy_template) in policy_templates {
+            let mut floria_policy_template = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                name.to_floria_name(POLICY_TEMPLATE_NAME),
+                Some(floria_service_template_id.clone()),
+            )?;
+
+            policy_template.compile(
+                &mut floria_policy_template,
+                name.clone(),
+                &node_template_selectors,
+                &group_ids,
+                context,
+            )?;
+
+            floria_service_template.contained_vertex_template_ids.push(floria_policy_template.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_policy_template), &mut context.problems);
+        }
+
         give_unwrap!(context.store.add_vertex_template(floria_service_template), &mut context.problems);
 
-        Ok(Some(floria_service_template_id.clone()))
+        Ok(Some((floria_service_template_id, substitution)))
     }
 }
 
@@ -320,6 +577,32 @@ where
             context,
         )?;
 
+        if let Some(substitution_mappings) = &self.substitution_mappings {
+            substitution_mappings.compile(vertex_template, context)?;
+        }
+
+        // Workflows
+        for (name, workflow) in &self.workflows {
+            let mut floria_workflow = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                name.to_floria_name(WORKFLOW_NAME),
+                Some(vertex_template.template.id.clone()),
+            )?;
+
+            workflow.compile(&mut floria_workflow, name.clone(), context)?;
+
+            vertex_template.contained_vertex_template_ids.push(floria_workflow.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_workflow), &mut context.problems);
+        }
+
         Ok(())
     }
 }
+
+// Floria name of a node created from a node template, e.g. "node:worker" for the first and
+// "node:worker:1" for the second
+fn node_floria_name(name: &Name, index: u64) -> ByteString {
+    let floria_name = name.to_floria_name(NODE_TEMPLATE_NAME);
+    if index == 0 { floria_name } else { format!("{}{}{}", floria_name, FLORIA_ID_NAME_DELIMITER, index).into() }
+}
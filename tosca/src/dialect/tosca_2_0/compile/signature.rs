@@ -0,0 +1,115 @@
+use super::super::{super::super::grammar::*, data::*, dialect::*, entities::*};
+
+use {
+    compris::{annotate::*, normal::*},
+    problemo::*,
+};
+
+impl<AnnotatedT> Call<AnnotatedT> {
+    /// Select the function signature and check the arguments against it.
+    ///
+    /// The data kinds of literal arguments, and of calls with a known result, must be accepted by
+    /// the argument schemas.
+    ///
+    /// Returns the selected signature, or [None] if the function has no signatures or if none of
+    /// them accepts the number of arguments (in which case a problem is given).
+    pub fn check_signature<'function>(
+        &self,
+        function: &'function FunctionDefinition<AnnotatedT>,
+        context: &mut CompilationContext,
+    ) -> Result<Option<&'function FunctionSignature<AnnotatedT>>, Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        if function.signatures.is_empty() {
+            return Ok(None);
+        }
+
+        let Some(signature) = function.select_signature(self.arguments.len()) else {
+            context
+                .problems
+                .with_fallback_annotations(self.annotations())
+                .give(WrongArgumentsError::as_problem(&self.function, self.arguments.len()))?;
+            return Ok(None);
+        };
+
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if let Some(schema) = signature.argument_schema(index)
+                && let Some(expected_data_kind) = schema.data_kind
+                && let Some(data_kind) = argument.data_kind(context)
+                && !expected_data_kind.accepts(data_kind)
+            {
+                context.problems.with_fallback_annotations(argument.annotations().or(self.annotations())).give(
+                    WrongTypeError::as_problem(
+                        format!("argument {} of {}", index + 1, self.function),
+                        data_kind,
+                        vec![expected_data_kind.to_string()],
+                    ),
+                )?;
+            }
+        }
+
+        Ok(Some(signature))
+    }
+
+    /// Check that the data kind of the result is accepted by a data type.
+    ///
+    /// Does nothing if either is unknown.
+    pub fn check_result(&self, type_name: &FullName, context: &mut CompilationContext) -> Result<(), Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        if let Some(data_kind) = self.result_data_kind(context)
+            && let Ok((data_type, _source)) =
+                context.catalog.entity::<DataType<AnnotatedT>>(DATA_TYPE, type_name, context.source_id)
+            && let Some(expected_data_kind) = data_type.data_kind
+            && !expected_data_kind.accepts(data_kind)
+        {
+            context.problems.with_fallback_annotations(self.annotations()).give(WrongTypeError::as_problem(
+                format!("result of {}", self.function),
+                data_kind,
+                vec![expected_data_kind.to_string()],
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Data kind of the result, according to the selected signature.
+    pub fn result_data_kind(&self, context: &CompilationContext) -> Option<DataKind>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        let (function, _source) = context
+            .catalog
+            .entity::<FunctionDefinition<AnnotatedT>>(FUNCTION, &self.function, context.source_id)
+            .ok()?;
+        function.select_signature(self.arguments.len())?.result.as_ref()?.data_kind
+    }
+}
+
+impl<AnnotatedT> Expression<AnnotatedT> {
+    /// Data kind, if known.
+    ///
+    /// For calls this is the data kind of the result.
+    pub fn data_kind(&self, context: &CompilationContext) -> Option<DataKind>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        match self {
+            Expression::Simple(simple) => match simple {
+                Variant::Null(_) => Some(DataKind::Nil),
+                Variant::Integer(_) | Variant::UnsignedInteger(_) => Some(DataKind::Integer),
+                Variant::Float(_) => Some(DataKind::Float),
+                Variant::Boolean(_) => Some(DataKind::Boolean),
+                Variant::Text(_) => Some(DataKind::String),
+                Variant::Blob(_) => Some(DataKind::Bytes),
+                _ => None,
+            },
+
+            Expression::List(_) => Some(DataKind::List),
+            Expression::Map(_) => Some(DataKind::Map),
+            Expression::Call(call) => call.result_data_kind(context),
+        }
+    }
+}
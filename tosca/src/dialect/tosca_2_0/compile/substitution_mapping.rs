@@ -0,0 +1,222 @@
+use super::{
+    super::{
+        super::super::grammar::*,
+        dialect::{Dialect as Dialect2_0, *},
+        entities::*,
+    },
+    workflow_definition::*,
+};
+
+use {
+    compris::{annotate::*, normal::*},
+    kutil::std::immutable::*,
+    problemo::*,
+    std::{collections::*, iter},
+};
+
+impl<AnnotatedT> SubstitutionMapping<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    ///
+    /// The mappings are compiled into the `substitution` metadata of the service template vertex
+    /// template. Capability and requirement mappings are also used to rewire the substituted node
+    /// template's relationships into our topology (see [Substitution]).
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        let mut substitution = Map::<WithoutAnnotations>::default();
+
+        substitution.into_insert("node_type", ByteString::from(self.node_type.to_string()));
+        substitution.into_insert("properties", parameter_mappings(&self.properties));
+        substitution.into_insert("attributes", parameter_mappings(&self.attributes));
+        substitution.into_insert("capabilities", node_template_mappings(&self.capabilities));
+        substitution.into_insert("requirements", node_template_mappings(&self.requirements));
+
+        let mut interfaces = Map::<WithoutAnnotations>::default();
+        for (interface_name, mapping) in &self.interfaces {
+            let mut operations = Map::<WithoutAnnotations>::default();
+            for (operation_name, workflow_name) in mapping {
+                operations.into_insert(operation_name.0.clone(), workflow_name.0.clone());
+            }
+            interfaces.into_insert(interface_name.0.clone(), operations);
+        }
+        substitution.into_insert("interfaces", interfaces);
+
+        vertex_template.template.metadata.set_tosca_metadata("substitution", substitution);
+
+        if let Some(substitution_filter) = &self.substitution_filter {
+            vertex_template.template.property_templates.insert(
+                ByteString::from_static("substitution_filter"),
+                compile_condition(substitution_filter, context)?,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Dialect2_0 {
+    /// Compile the service template that substitutes a node template.
+    ///
+    /// The substituting service template is looked for among the sources in the catalog: its
+    /// substitution mapping's node type must be the same as or derived from the node template's
+    /// type. It is compiled into a Floria directory nested under the node template's name (and
+    /// its node index, for nodes after the first).
+    ///
+    /// Returns the substituting service template vertex template ID and its [Substitution], or
+    /// [None] if no substituting service template was found.
+    pub fn compile_substitution<AnnotatedT>(
+        &self,
+        name: &Name,
+        index: u64,
+        node_template: &NodeTemplate<AnnotatedT>,
+        node_template_properties: &BTreeMap<ByteString, floria::Property>,
+        substituted_source_ids: &Vec<SourceID>,
+        context: &mut CompilationContext,
+    ) -> Result<Option<(floria::ID, Substitution)>, Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        let Some(type_name) = &node_template.type_name else {
+            return Ok(None);
+        };
+
+        let Some(source_id) = self.find_substitution::<AnnotatedT>(type_name, substituted_source_ids, context) else {
+            tracing::debug!(source = context.source_id.to_string(), name = name.to_string(), "no substitution found");
+            return Ok(None);
+        };
+
+        tracing::debug!(
+            source = context.source_id.to_string(),
+            name = name.to_string(),
+            substitution = source_id.to_string(),
+            "compiling substitution"
+        );
+
+        let segment = if index == 0 {
+            name.clone().into()
+        } else {
+            format!("{}{}{}", name, FLORIA_ID_NAME_DELIMITER, index).into()
+        };
+
+        let directory =
+            floria::Directory::new(context.directory.segments.iter().cloned().chain(iter::once(segment)).collect())?;
+
+        // Guard against a service template (indirectly) substituting its own node templates
+        let mut substituted_source_ids = substituted_source_ids.clone();
+        substituted_source_ids.push(context.source_id.clone());
+
+        let mut substitution_context = CompilationContext::new(
+            &source_id,
+            context.catalog,
+            &directory,
+            context.store.clone(),
+            context.problems.clone(),
+        )
+        .with_inventory(context.inventory.clone())
+        .with_fold_constants(context.fold_constants);
+
+        self.compile_service_template_with::<AnnotatedT>(
+            Some(node_template_properties),
+            &substituted_source_ids,
+            &mut substitution_context,
+        )
+    }
+
+    // Sorted by source ID, so that the choice is deterministic
+    fn find_substitution<AnnotatedT>(
+        &self,
+        type_name: &FullName,
+        substituted_source_ids: &Vec<SourceID>,
+        context: &CompilationContext,
+    ) -> Option<SourceID>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+    {
+        let service_template_kind_name = self.implementation.entity_kinds.represent(SERVICE_TEMPLATE);
+
+        let mut source_ids: Vec<_> = context
+            .catalog
+            .sources
+            .values()
+            .filter(|source| {
+                (source.dialect_id == DIALECT_ID)
+                    && (source.source_id != *context.source_id)
+                    && !substituted_source_ids.contains(&source.source_id)
+            })
+            .filter_map(|source| {
+                let service_template = source
+                    .entity::<ServiceTemplate<AnnotatedT>>(
+                        SERVICE_TEMPLATE,
+                        &service_template_kind_name,
+                        &Default::default(),
+                    )
+                    .ok()?;
+                let substitution_mappings = service_template.substitution_mappings.as_ref()?;
+
+                context
+                    .catalog
+                    .is_type_derived_from_in::<NodeType<AnnotatedT>, AnnotatedT>(
+                        NODE_TYPE,
+                        &substitution_mappings.node_type,
+                        &source.source_id,
+                        type_name,
+                        context.source_id,
+                    )
+                    .then(|| source.source_id.clone())
+            })
+            .collect();
+
+        source_ids.sort();
+        source_ids.into_iter().next()
+    }
+}
+
+//
+// Substitution
+//
+
+/// Compiled substitution mappings of a substituting service template.
+#[derive(Clone, Debug, Default)]
+pub struct Substitution {
+    /// Vertex template IDs of the mapped capabilities by the substituted node type's capability
+    /// name.
+    ///
+    /// Requirements that target these capabilities of the substituted node template will target
+    /// them instead.
+    pub capability_ids: BTreeMap<Name, floria::ID>,
+
+    /// Vertex template IDs of the nodes of the mapped requirements by the substituted node type's
+    /// requirement name.
+    ///
+    /// Relationships created for these requirements of the substituted node template will start at
+    /// these nodes instead.
+    pub requirement_source_ids: BTreeMap<Name, floria::ID>,
+
+    /// Mapped requirements as (node template name, requirement name).
+    pub mapped_requirements: BTreeSet<(Name, Name)>,
+}
+
+// Utils
+
+fn parameter_mappings(mappings: &BTreeMap<Name, ParameterMapping>) -> Map<WithoutAnnotations> {
+    let mut map = Map::default();
+    for (name, mapping) in mappings {
+        map.into_insert(name.0.clone(), mapping.parameter.0.clone());
+    }
+    map
+}
+
+fn node_template_mappings(mappings: &BTreeMap<Name, NodeTemplateMapping>) -> Map<WithoutAnnotations> {
+    let mut map = Map::default();
+    for (name, mapping) in mappings {
+        let mapping: Vec<Variant<WithoutAnnotations>> =
+            vec![mapping.node_template.0.clone().into(), mapping.name.0.clone().into()];
+        map.into_insert(name.0.clone(), mapping);
+    }
+    map
+}
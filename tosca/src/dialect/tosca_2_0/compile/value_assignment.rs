@@ -1,4 +1,4 @@
-use super::super::{super::super::grammar::*, dialect::*, entities::*};
+use super::super::{super::super::grammar::*, data::*, dialect::*, entities::*};
 
 use {compris::annotate::*, kutil::std::immutable::*, problemo::*, std::collections::*};
 
@@ -40,6 +40,12 @@ impl<AnnotatedT> ValueAssignment<AnnotatedT> {
             None => None,
         };
 
+        if let Some(Expression::Call(call)) = &self.expression
+            && let Some(type_name) = &self.type_name
+        {
+            call.check_result(type_name, context)?;
+        }
+
         let (updater, value) = match &self.expression {
             Some(expression) => {
                 match expression.clone().into_eager().compile(context).give_ok(&mut context.problems)? {
@@ -0,0 +1,235 @@
+use super::{
+    super::{super::super::grammar::*, data::*, dialect::*, entities::*},
+    plugin::*,
+    value_assignment::*,
+};
+
+use {
+    compris::{annotate::*, normal::*},
+    floria::AddEventHandler,
+    kutil::std::immutable::*,
+    problemo::*,
+    std::{collections::*, mem::*},
+};
+
+impl<AnnotatedT> WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    ///
+    /// Steps are compiled as contained vertex templates, with `on_success` and `on_failure` as
+    /// edge templates between them. Activities are compiled as vertex templates contained in their
+    /// steps, in sequence. An external implementation is compiled as an event handler, the same
+    /// way operation implementations are.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        name: Name,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, WORKFLOW_NAME);
+        vertex_template.template.metadata.set_tosca_name(name.clone());
+        vertex_template.template.metadata.set_tosca_description(self.description.as_ref());
+        vertex_template.template.metadata.set_tosca_custom_metadata(&self.metadata);
+
+        let initial_steps: Vec<Variant<WithoutAnnotations>> =
+            self.initial_steps().into_iter().map(|name| name.clone().as_byte_string().into()).collect();
+        if !initial_steps.is_empty() {
+            vertex_template.template.metadata.set_tosca_metadata("initial_steps", initial_steps);
+        }
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            &self.input_assignments,
+            "input",
+            PARAMETER_NAME,
+            true,
+            context,
+        )?;
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            &self.outputs,
+            "output",
+            PARAMETER_NAME,
+            false,
+            context,
+        )?;
+
+        if let Some(precondition) = &self.precondition {
+            vertex_template
+                .template
+                .property_templates
+                .insert(ByteString::from_static("precondition"), compile_condition(precondition, context)?);
+        }
+
+        // Implementation
+
+        if let Some(mut plugin) = self.floria_plugin(context)? {
+            if let Some(event) = take(&mut plugin.event) {
+                let function = take(&mut plugin.function).unwrap_or_else(|| name.clone().into());
+                if let Some(plugin_id) = plugin.get_or_create(None, context)? {
+                    let handler = floria::FunctionName::new(plugin_id, function)?;
+                    vertex_template.template.event_handlers.add_event_handler(event, handler);
+                }
+            }
+        }
+
+        // Steps
+
+        let workflow_id = vertex_template.template.id.clone();
+        let floria_workflow_name = name.to_floria_name(WORKFLOW_NAME);
+        let mut floria_steps = BTreeMap::default();
+
+        for (step_name, step) in &self.steps {
+            let mut floria_step = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                step_name.to_floria_name_contained(WORKFLOW_STEP_NAME, &floria_workflow_name),
+                Some(workflow_id.clone()),
+            )?;
+
+            step.compile(&mut floria_step, step_name.clone(), context)?;
+
+            vertex_template.contained_vertex_template_ids.push(floria_step.template.id.clone());
+            floria_steps.insert(step_name.clone(), floria_step);
+        }
+
+        for (step_name, step) in &self.steps {
+            let source_vertex_id = floria_steps[step_name].template.id.clone();
+
+            for (field, next_step_name) in step.next_steps() {
+                if let Some(next_step) = floria_steps.get(next_step_name) {
+                    let mut floria_transition = floria::EdgeTemplate::new_with_name(
+                        context.directory.clone(),
+                        next_step_name.to_floria_name_contained(field, &source_vertex_id.name),
+                        source_vertex_id.clone(),
+                        floria::VertexSelector::new_vertex(next_step.template.id.clone()),
+                    )?;
+
+                    floria_transition.template.metadata.set_tosca_entity_static(DIALECT_ID, field);
+                    floria_transition.template.metadata.set_tosca_name(next_step_name.clone());
+
+                    give_unwrap!(context.store.add_edge_template(floria_transition), &mut context.problems);
+                }
+            }
+        }
+
+        for floria_step in floria_steps.into_values() {
+            give_unwrap!(context.store.add_vertex_template(floria_step), &mut context.problems);
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> WorkflowStepDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        name: Name,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, WORKFLOW_STEP_NAME);
+        vertex_template.template.metadata.set_tosca_name(name);
+        vertex_template.template.metadata.set_tosca_metadata("target", self.target.clone().as_byte_string());
+
+        if let Some(target_relationship) = &self.target_relationship {
+            vertex_template
+                .template
+                .metadata
+                .set_tosca_metadata("target_relationship", target_relationship.clone().as_byte_string());
+        }
+
+        if let Some(operation_host) = &self.operation_host {
+            vertex_template.template.metadata.set_tosca_metadata("operation_host", operation_host.clone());
+        }
+
+        if let Some(filter) = &self.filter {
+            vertex_template
+                .template
+                .property_templates
+                .insert(ByteString::from_static("filter"), compile_condition(filter, context)?);
+        }
+
+        // Activities
+
+        let step_id = vertex_template.template.id.clone();
+
+        for (index, activity) in self.activities.iter().enumerate() {
+            let mut floria_activity = floria::VertexTemplate::new_with_name(
+                context.directory.clone(),
+                index.to_floria_name_contained(WORKFLOW_ACTIVITY_NAME, &step_id.name),
+                Some(step_id.clone()),
+            )?;
+
+            activity.compile(&mut floria_activity, context)?;
+
+            vertex_template.contained_vertex_template_ids.push(floria_activity.template.id.clone());
+
+            give_unwrap!(context.store.add_vertex_template(floria_activity), &mut context.problems);
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> ActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Compile to Floria.
+    pub fn compile(
+        &self,
+        vertex_template: &mut floria::VertexTemplate,
+        context: &mut CompilationContext,
+    ) -> Result<(), Problem> {
+        vertex_template.template.metadata.set_tosca_entity_static(DIALECT_ID, WORKFLOW_ACTIVITY_NAME);
+        vertex_template.template.metadata.set_tosca_metadata("activity", ByteString::from_static(self.keyname()));
+
+        let inputs = match self {
+            Self::Delegate(workflow) | Self::Inline(workflow) => {
+                vertex_template
+                    .template
+                    .metadata
+                    .set_tosca_metadata("workflow", workflow.workflow.clone().as_byte_string());
+                &workflow.inputs
+            }
+
+            Self::SetState(state) => {
+                vertex_template.template.metadata.set_tosca_metadata("state", state.clone());
+                return Ok(());
+            }
+
+            Self::CallOperation(call_operation) => {
+                vertex_template.template.metadata.set_tosca_metadata("operation", call_operation.operation.clone());
+                &call_operation.inputs
+            }
+        };
+
+        compile_value_assignments(
+            &mut vertex_template.template.property_templates,
+            inputs,
+            "input",
+            PARAMETER_NAME,
+            true,
+            context,
+        )
+    }
+}
+
+// Conditions are evaluated on demand by the orchestrator, so they are not read-only
+pub(crate) fn compile_condition<AnnotatedT>(
+    condition: &Expression<AnnotatedT>,
+    context: &mut CompilationContext,
+) -> Result<floria::Property, Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    let (_preparer, updater, value) = ValueAssignment::from(condition.clone()).floria_property_fields(context)?;
+    Ok(floria::Property::new(false, None, updater, value))
+}
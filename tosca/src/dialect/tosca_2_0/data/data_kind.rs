@@ -72,6 +72,23 @@ impl DataKind {
             DataKind::Struct => "struct",
         }
     }
+
+    /// Whether values of a data kind are acceptable for this data kind.
+    ///
+    /// Timestamps, versions, and scalars can be represented as strings, structs as maps, and
+    /// floats as integers.
+    pub fn accepts(&self, data_kind: DataKind) -> bool {
+        if *self == data_kind {
+            return true;
+        }
+
+        match self {
+            DataKind::Float => data_kind == DataKind::Integer,
+            DataKind::Timestamp | DataKind::Version | DataKind::Scalar => data_kind == DataKind::String,
+            DataKind::Struct => data_kind == DataKind::Map,
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for DataKind {
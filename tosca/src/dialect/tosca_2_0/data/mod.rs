@@ -1,7 +1,8 @@
 mod data_kind;
 mod expression;
+mod scalar_factor;
 mod schema;
 mod version;
 
 #[allow(unused_imports)]
-pub use {data_kind::*, expression::*, schema::*, version::*};
+pub use {data_kind::*, expression::*, scalar_factor::*, schema::*, version::*};
@@ -0,0 +1,145 @@
+use {
+    compris::normal::*,
+    kutil::std::immutable::*,
+    std::{collections::*, fmt},
+};
+
+//
+// ScalarFactor
+//
+
+/// Scalar unit or prefix multiplier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScalarFactor {
+    /// Integer.
+    Integer(i64),
+
+    /// Float.
+    Float(f64),
+}
+
+impl ScalarFactor {
+    /// From variant.
+    ///
+    /// Returns [None] if the variant is not a number.
+    pub fn from_variant<AnnotatedT>(variant: &Variant<AnnotatedT>) -> Option<Self> {
+        match variant {
+            Variant::Integer(integer) => Some(Self::Integer(integer.inner)),
+            Variant::UnsignedInteger(unsigned_integer) => Some(match i64::try_from(unsigned_integer.inner) {
+                Ok(integer) => Self::Integer(integer),
+                Err(_) => Self::Float(unsigned_integer.inner as f64),
+            }),
+            Variant::Float(float) => Some(Self::Float(float.inner.into())),
+            _ => None,
+        }
+    }
+
+    /// True if integer.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Self::Integer(_))
+    }
+
+    /// True if one.
+    pub fn is_one(&self) -> bool {
+        match self {
+            Self::Integer(integer) => *integer == 1,
+            Self::Float(float) => *float == 1.,
+        }
+    }
+
+    /// Multiply.
+    ///
+    /// Integer overflow results in a float.
+    pub fn mul(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => match left.checked_mul(right) {
+                Some(product) => Self::Integer(product),
+                None => Self::Float(left as f64 * right as f64),
+            },
+
+            _ => Self::Float(self.as_f64() * other.as_f64()),
+        }
+    }
+
+    /// As float.
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Integer(integer) => *integer as f64,
+            Self::Float(float) => *float,
+        }
+    }
+}
+
+impl fmt::Display for ScalarFactor {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(integer) => fmt::Display::fmt(integer, formatter),
+            Self::Float(float) => fmt::Display::fmt(float, formatter),
+        }
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for ScalarFactor
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        match self {
+            Self::Integer(integer) => integer.into(),
+            Self::Float(float) => float.into(),
+        }
+    }
+}
+
+//
+// ScalarFactors
+//
+
+/// Scalar factors by unit.
+pub type ScalarFactors = BTreeMap<ByteString, ScalarFactor>;
+
+/// Scalar factors from a units or prefixes map.
+///
+/// Entries that are not numbers are skipped.
+pub fn scalar_factors<AnnotatedT>(map: &BTreeMap<ByteString, Variant<AnnotatedT>>) -> ScalarFactors {
+    map.iter()
+        .filter_map(|(key, value)| ScalarFactor::from_variant(value).map(|factor| (key.clone(), factor)))
+        .collect()
+}
+
+/// Expand units with prefixes.
+///
+/// Every unit is included without a prefix, too, and every prefix is prepended to every unit, with
+/// the factors multiplied. Unit strings that can be produced by more than one unit/prefix
+/// combination are ambiguous. They are returned separately and are *not* included in the expanded
+/// units.
+pub fn expand_scalar_units(units: &ScalarFactors, prefixes: &ScalarFactors) -> (ScalarFactors, Vec<ByteString>) {
+    let mut expanded = ScalarFactors::default();
+    let mut ambiguous = Vec::default();
+
+    let mut add = |unit: ByteString, factor| {
+        if ambiguous.contains(&unit) {
+            return;
+        }
+
+        if expanded.contains_key(&unit) {
+            expanded.remove(&unit);
+            ambiguous.push(unit);
+        } else {
+            expanded.insert(unit, factor);
+        }
+    };
+
+    for (unit, unit_factor) in units {
+        add(unit.clone(), *unit_factor);
+
+        for (prefix, prefix_factor) in prefixes {
+            // The empty prefix is the unit itself
+            if !prefix.is_empty() {
+                add(format!("{}{}", prefix, unit).into(), prefix_factor.mul(*unit_factor));
+            }
+        }
+    }
+
+    (expanded, ambiguous)
+}
@@ -0,0 +1,171 @@
+use super::{
+    super::{super::super::grammar::*, entities::*},
+    entity_kind::*,
+};
+
+use {compris::annotate::*, kutil::std::immutable::*, problemo::*};
+
+impl super::Dialect {
+    /// Check the artifact files declared in a source.
+    ///
+    /// Covers the artifacts of node types and node templates, the inline implementation artifacts
+    /// of operations and notifications (in interface types, in node and relationship types and
+    /// templates, and in requirements), of workflows, and of function signatures (e.g. Wasm
+    /// artifacts), except for those located in a repository. The check returns a problem for an
+    /// invalid file, which is given with the annotations of the artifact's `file` field.
+    ///
+    /// Should be called before completion, otherwise inherited artifacts would be checked, too.
+    pub fn check_artifact_files<AnnotatedT, CheckT, ProblemReceiverT>(
+        source: &Source,
+        check: CheckT,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<(), Problem>
+    where
+        AnnotatedT: 'static + Annotated + Clone + Default,
+        CheckT: Fn(&ByteString) -> Option<Problem>,
+        ProblemReceiverT: ProblemReceiver,
+    {
+        for (name, entity) in &source.entities {
+            if name.entity_kind == NODE_TYPE {
+                let node_type = give_unwrap!(entity.downcast_ref_checked::<NodeType<AnnotatedT>>(), problems);
+                for artifact in node_type.artifacts.values() {
+                    check_artifact_definition(artifact, &check, problems)?;
+                }
+                check_interface_definitions(&node_type.interfaces, &check, problems)?;
+                for (_name, requirement) in &node_type.requirements {
+                    check_interface_definitions(&requirement.relationship.interfaces, &check, problems)?;
+                }
+            } else if name.entity_kind == NODE_TEMPLATE {
+                let node_template = give_unwrap!(entity.downcast_ref_checked::<NodeTemplate<AnnotatedT>>(), problems);
+                for artifact in node_template.artifacts.values() {
+                    if artifact.repository.is_none()
+                        && let Some(file) = &artifact.file
+                        && let Some(problem) = check(file)
+                    {
+                        problems.give(problem.with_annotations_from_field(artifact, "file"))?;
+                    }
+                }
+                check_interface_assignments(&node_template.interfaces, &check, problems)?;
+                for (_name, requirement) in &node_template.requirements {
+                    if let Some(relationship) = &requirement.relationship {
+                        check_interface_assignments(&relationship.interfaces, &check, problems)?;
+                    }
+                }
+            } else if name.entity_kind == RELATIONSHIP_TYPE {
+                let relationship_type =
+                    give_unwrap!(entity.downcast_ref_checked::<RelationshipType<AnnotatedT>>(), problems);
+                check_interface_definitions(&relationship_type.interfaces, &check, problems)?;
+            } else if name.entity_kind == RELATIONSHIP_TEMPLATE {
+                let relationship_template =
+                    give_unwrap!(entity.downcast_ref_checked::<RelationshipTemplate<AnnotatedT>>(), problems);
+                check_interface_assignments(&relationship_template.interfaces, &check, problems)?;
+            } else if name.entity_kind == INTERFACE_TYPE {
+                let interface_type = give_unwrap!(entity.downcast_ref_checked::<InterfaceType<AnnotatedT>>(), problems);
+                for operation in interface_type.operations.values() {
+                    check_implementation(operation.implementation.as_ref(), &check, problems)?;
+                }
+                for notification in interface_type.notifications.values() {
+                    check_implementation(notification.implementation.as_ref(), &check, problems)?;
+                }
+            } else if name.entity_kind == SERVICE_TEMPLATE {
+                let service_template =
+                    give_unwrap!(entity.downcast_ref_checked::<ServiceTemplate<AnnotatedT>>(), problems);
+                for workflow in service_template.workflows.values() {
+                    check_implementation(workflow.implementation.as_ref(), &check, problems)?;
+                }
+            } else if name.entity_kind == FUNCTION {
+                let function = give_unwrap!(entity.downcast_ref_checked::<FunctionDefinition<AnnotatedT>>(), problems);
+                for signature in &function.signatures {
+                    check_implementation(signature.implementation.as_ref(), &check, problems)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_artifact_definition<AnnotatedT, CheckT, ProblemReceiverT>(
+    artifact: &ArtifactDefinition<AnnotatedT>,
+    check: &CheckT,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+    CheckT: Fn(&ByteString) -> Option<Problem>,
+    ProblemReceiverT: ProblemReceiver,
+{
+    if artifact.repository.is_none()
+        && let Some(problem) = check(&artifact.file)
+    {
+        problems.give(problem.with_annotations_from_field(artifact, "file"))?;
+    }
+
+    Ok(())
+}
+
+// Only inline artifact definitions; named artifacts are checked where they are declared
+fn check_implementation<AnnotatedT, CheckT, ProblemReceiverT>(
+    implementation: Option<&ImplementationDefinition<AnnotatedT>>,
+    check: &CheckT,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+    CheckT: Fn(&ByteString) -> Option<Problem>,
+    ProblemReceiverT: ProblemReceiver,
+{
+    if let Some(implementation) = implementation {
+        for artifact in implementation.primary.iter().chain(implementation.dependencies.iter()) {
+            if let ImplementationDefinitionArtifact::Definition(artifact) = artifact {
+                check_artifact_definition(artifact, check, problems)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_interface_definitions<AnnotatedT, CheckT, ProblemReceiverT>(
+    interfaces: &InterfaceDefinitions<AnnotatedT>,
+    check: &CheckT,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+    CheckT: Fn(&ByteString) -> Option<Problem>,
+    ProblemReceiverT: ProblemReceiver,
+{
+    for interface in interfaces.values() {
+        for operation in interface.operations.values() {
+            check_implementation(operation.implementation.as_ref(), check, problems)?;
+        }
+        for notification in interface.notifications.values() {
+            check_implementation(notification.implementation.as_ref(), check, problems)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_interface_assignments<AnnotatedT, CheckT, ProblemReceiverT>(
+    interfaces: &InterfaceAssignments<AnnotatedT>,
+    check: &CheckT,
+    problems: &mut ProblemReceiverT,
+) -> Result<(), Problem>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+    CheckT: Fn(&ByteString) -> Option<Problem>,
+    ProblemReceiverT: ProblemReceiver,
+{
+    for interface in interfaces.values() {
+        for operation in interface.operations.values() {
+            check_implementation(operation.implementation.as_ref(), check, problems)?;
+        }
+        for notification in interface.notifications.values() {
+            check_implementation(notification.implementation.as_ref(), check, problems)?;
+        }
+    }
+
+    Ok(())
+}
@@ -34,6 +34,7 @@ impl Dialect {
     {
         catalog.add_dialect_ref(Self::default().into());
         catalog.add_sources(Self::built_in_sources::<AnnotatedT>()?);
+        Self::register_built_in_profiles(&mut catalog.profiles);
         Ok(())
     }
 }
@@ -125,6 +125,27 @@ pub const ATTRIBUTE_NAME: &str = "attribute";
 /// Parameter name.
 pub const PARAMETER_NAME: &str = "parameter";
 
+/// Workflow name.
+pub const WORKFLOW_NAME: &str = "workflow";
+
+/// Workflow step name.
+pub const WORKFLOW_STEP_NAME: &str = "workflow_step";
+
+/// Workflow activity name.
+pub const WORKFLOW_ACTIVITY_NAME: &str = "workflow_activity";
+
+/// Trigger name.
+pub const TRIGGER_NAME: &str = "trigger";
+
+/// Group member name.
+pub const GROUP_MEMBER_NAME: &str = "member";
+
+/// Policy target name.
+pub const POLICY_TARGET_NAME: &str = "target";
+
+/// Substitution name.
+pub const SUBSTITUTION_NAME: &str = "substitution";
+
 impl super::Dialect {
     /// TOSCA 2.0 supported entity kinds.
     pub fn entity_kinds() -> EntityKinds {
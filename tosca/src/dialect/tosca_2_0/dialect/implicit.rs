@@ -164,7 +164,7 @@ impl super::Dialect {
     where
         AnnotatedT: 'static + Annotated + Clone + Default,
     {
-        for function in ["apply", "assert", "schema", "select_capability"] {
+        for function in ["apply", "assert", "schema", "select_capability", "select_node"] {
             source.add_entity(
                 FUNCTION,
                 function.into(),
@@ -1,7 +1,9 @@
+mod artifacts;
 mod dialect;
 mod entity_kind;
 mod implicit;
+mod profiles;
 mod source;
 
 #[allow(unused_imports)]
-pub use {dialect::*, entity_kind::*, implicit::*};
+pub use {dialect::*, entity_kind::*, implicit::*, profiles::*};
@@ -0,0 +1,16 @@
+use super::super::super::super::grammar::*;
+
+/// TOSCA Simple Profile name.
+pub const SIMPLE_PROFILE_NAME: &str = "org.oasis-open.simple:2.0";
+
+/// TOSCA Simple Profile normative types, as TOSCA YAML.
+pub const SIMPLE_PROFILE: &str = include_str!("profiles/simple.yaml");
+
+impl super::Dialect {
+    /// Register the built-in profiles.
+    ///
+    /// Unlike the built-in sources these are loaded only when imported.
+    pub fn register_built_in_profiles(profiles: &mut ProfileRegistry) {
+        profiles.register_embedded(SIMPLE_PROFILE_NAME.into(), SIMPLE_PROFILE);
+    }
+}
@@ -4,7 +4,7 @@ use super::{
 };
 
 use {
-    compris::{annotate::*, normal::*, resolve::*},
+    compris::{annotate::*, errors::*, normal::*, resolve::*},
     problemo::*,
     std::mem::*,
 };
@@ -27,15 +27,40 @@ impl super::Dialect {
             return Ok(());
         };
 
+        source.profile = file.profile;
+
         for import in file.imports {
-            if let Some(url) = import.url {
+            if let Some(url) = &import.url {
+                let url = match &import.repository {
+                    Some(repository_name) => match file.repositories.get(repository_name) {
+                        Some(repository) => repository.file_url(url),
+
+                        None => {
+                            problems.give(
+                                UndeclaredError::as_problem("repository", repository_name)
+                                    .with_annotations_from_field(&import, "repository"),
+                            )?;
+                            continue;
+                        }
+                    },
+
+                    None => url.clone(),
+                };
+
                 source.add_dependency(
                     SourceID::URL(url),
                     import.namespace.map(|namespace| namespace.into()).unwrap_or_default(),
                 );
-            } else if let Some(profile) = import.profile {
+            } else if let Some(profile) = &import.profile {
+                if import.repository.is_some() {
+                    problems.give(
+                        MalformedError::as_problem("import", "\"repository\" can only be used with \"url\"")
+                            .with_annotations_from_field(&import, "repository"),
+                    )?;
+                }
+
                 source.add_dependency(
-                    SourceID::Profile(profile),
+                    SourceID::Profile(profile.clone()),
                     import.namespace.map(|namespace| namespace.into()).unwrap_or_default(),
                 );
             }
@@ -1,4 +1,9 @@
-use super::{super::super::super::grammar::*, artifact_definition::*, value_assignment::*};
+use super::{
+    super::{super::super::grammar::*, dialect::*},
+    artifact_definition::*,
+    repository_definition::*,
+    value_assignment::*,
+};
 
 use {
     compris::{annotate::*, depict::*, resolve::*},
@@ -39,7 +44,7 @@ where
     /// file URI within the repository.
     #[resolve]
     #[depict(option, as(depict))]
-    pub repository: Option<Name>,
+    pub repository: Option<FullName>,
 
     /// The optional description for the artifact definition.
     #[resolve]
@@ -120,6 +125,15 @@ where
             complete_optional_field!(checksum_algorithm, self, artifact_definition);
         }
 
+        // The repository must be declared
+        let (_repository, _repository_namespace) = completed_entity_from_optional_full_name_field!(
+            REPOSITORY,
+            RepositoryDefinition,
+            self,
+            repository,
+            context
+        );
+
         Ok(())
     }
 }
@@ -132,7 +146,7 @@ where
         ArtifactAssignment {
             type_name: self.type_name.to_namespace(namespace),
             file: Some(self.file.clone()),
-            repository: self.repository.clone(),
+            repository: self.repository.to_namespace(namespace),
             description: self.description.clone(),
             metadata: self.metadata.clone(),
             artifact_version: self.artifact_version.clone(),
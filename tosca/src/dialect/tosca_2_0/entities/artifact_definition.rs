@@ -1,6 +1,7 @@
 use super::{
     super::{super::super::grammar::*, dialect::*},
     artifact_type::*,
+    repository_definition::*,
     value_assignment::*,
 };
 
@@ -51,7 +52,7 @@ where
     /// file URI within the repository.
     #[resolve]
     #[depict(option, as(depict))]
-    pub repository: Option<Name>,
+    pub repository: Option<FullName>,
 
     /// The optional description for the artifact definition.
     #[resolve]
@@ -124,6 +125,15 @@ where
             complete_optional_field!(checksum_algorithm, self, parent);
         }
 
+        // The repository must be declared
+        let (_repository, _repository_namespace) = completed_entity_from_optional_full_name_field!(
+            REPOSITORY,
+            RepositoryDefinition,
+            self,
+            repository,
+            context
+        );
+
         let (artifact_type, artifact_type_namespace) =
             completed_entity_from_full_name_field!(ARTIFACT_TYPE, ArtifactType, self, type_name, context);
 
@@ -149,7 +159,7 @@ where
         Self {
             type_name: self.type_name.to_namespace(namespace),
             file: self.file.clone(),
-            repository: self.repository.clone(),
+            repository: self.repository.to_namespace(namespace),
             description: self.description.clone(),
             metadata: self.metadata.clone(),
             artifact_version: self.artifact_version.clone(),
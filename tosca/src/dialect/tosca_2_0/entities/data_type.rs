@@ -134,6 +134,120 @@ where
     pub fn new_internal(data_kind: DataKind) -> Self {
         Self { data_kind: Some(data_kind), internal: true, ..Default::default() }
     }
+
+    /// Scalar units expanded with prefixes.
+    ///
+    /// Multipliers that are not numbers and ambiguous units are skipped.
+    pub fn scalar_expanded_units(&self) -> ScalarFactors {
+        self.scalar_expanded_units_and_ambiguous().0
+    }
+
+    /// Scalar canonical unit.
+    ///
+    /// If "canonical_unit" is not set it is the single unit with a multiplier of one.
+    pub fn scalar_resolved_canonical_unit(&self, expanded_units: &ScalarFactors) -> Option<ByteString> {
+        match &self.scalar_canonical_unit {
+            Some(canonical_unit) => Some(canonical_unit.clone()),
+            None => {
+                let mut ones = expanded_units.iter().filter(|(_, factor)| factor.is_one()).map(|(unit, _)| unit);
+                match (ones.next(), ones.next()) {
+                    (Some(unit), None) => Some(unit.clone()),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    fn scalar_expanded_units_and_ambiguous(&self) -> (ScalarFactors, Vec<ByteString>) {
+        let units = self.scalar_units.as_ref().map(scalar_factors).unwrap_or_default();
+        let prefixes = self.scalar_prefixes.as_ref().map(scalar_factors).unwrap_or_default();
+        expand_scalar_units(&units, &prefixes)
+    }
+
+    fn complete_scalar_units(&self, context: &mut CompletionContext) -> Result<(), Problem> {
+        let integer = matches!(self.scalar_data_kind, Some(DataKind::Integer));
+
+        // Multipliers
+
+        for (name, map) in [("units", &self.scalar_units), ("prefixes", &self.scalar_prefixes)] {
+            let Some(map) = map else {
+                continue;
+            };
+
+            if map.is_empty() {
+                context
+                    .problems
+                    .give(MalformedError::as_problem(name, "empty").with_annotations_from_field(self, name))?;
+            }
+
+            for (key, value) in map {
+                match ScalarFactor::from_variant(value) {
+                    Some(factor) => {
+                        if integer && !factor.is_integer() {
+                            context.problems.give(
+                                MalformedError::as_problem(
+                                    name,
+                                    format!("multiplier not integer for integer data type: {}", key),
+                                )
+                                .with_annotations_from(value),
+                            )?;
+                        }
+                    }
+
+                    None => {
+                        context.problems.give(
+                            MalformedError::as_problem(name, format!("multiplier not integer or float: {}", key))
+                                .with_annotations_from(value),
+                        )?;
+                    }
+                }
+            }
+        }
+
+        if let Some(units) = &self.scalar_units
+            && !units.values().filter_map(ScalarFactor::from_variant).any(|factor| factor.is_one())
+        {
+            context.problems.give(
+                MalformedError::as_problem("units", "no unit with multiplier of one")
+                    .with_annotations_from_field(self, "units"),
+            )?;
+        }
+
+        // Combinations
+
+        let (expanded_units, ambiguous) = self.scalar_expanded_units_and_ambiguous();
+
+        for unit in ambiguous {
+            context.problems.give(
+                MalformedError::as_problem("prefixes", format!("ambiguous unit: {}", unit))
+                    .with_annotations_from_field(self, "prefixes"),
+            )?;
+        }
+
+        // Canonical unit
+
+        match &self.scalar_canonical_unit {
+            Some(canonical_unit) => {
+                if !expanded_units.contains_key(canonical_unit) {
+                    context.problems.give(
+                        MalformedError::as_problem("canonical_unit", format!("not a unit: {}", canonical_unit))
+                            .with_annotations_from_field(self, "canonical_unit"),
+                    )?;
+                }
+            }
+
+            None => {
+                if expanded_units.values().filter(|factor| factor.is_one()).count() > 1 {
+                    context.problems.give(
+                        MissingRequiredKeyError::as_problem(Variant::<WithoutAnnotations>::from("canonical_unit"))
+                            .with_annotations_from(self),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<AnnotatedT> Entity for DataType<AnnotatedT>
@@ -184,10 +298,6 @@ where
             self.scalar_data_kind = data_type.data_kind;
         }
 
-        // TODO: units has to map text -> numbers
-        // TODO: prefixes has to map text -> numbers
-        // TODO: units*prefixes combinations have to be unique (unambiguous)
-
         if !self.internal
             && let Some(kind) = self.data_kind
         {
@@ -216,6 +326,8 @@ where
                             .with_annotations_from_field(self, "data_type"),
                     )?;
                 }
+
+                self.complete_scalar_units(context)?;
             }
 
             // Invalid keys
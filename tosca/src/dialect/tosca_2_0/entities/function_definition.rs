@@ -73,6 +73,11 @@ where
             ..Default::default()
         }
     }
+
+    /// Select the first signature that accepts a number of arguments.
+    pub fn select_signature(&self, arguments: usize) -> Option<&FunctionSignature<AnnotatedT>> {
+        self.signatures.iter().find(|signature| signature.accepts(arguments))
+    }
 }
 
 impl<AnnotatedT> Entity for FunctionDefinition<AnnotatedT>
@@ -74,6 +74,37 @@ where
             ..Default::default()
         }
     }
+
+    /// Whether the signature accepts a number of arguments.
+    ///
+    /// If no arguments are defined the signature accepts no arguments, or any number of arguments
+    /// if it is variadic.
+    pub fn accepts(&self, arguments: usize) -> bool {
+        let required = self.arguments.len();
+        let optional = self.optional_arguments.len();
+
+        if (required == 0) && (optional == 0) {
+            return self.variadic || (arguments == 0);
+        }
+
+        (arguments >= required) && (self.variadic || (arguments <= required + optional))
+    }
+
+    /// The schema of the argument at an index.
+    ///
+    /// For variadic signatures the last defined argument is repeated.
+    pub fn argument_schema(&self, index: usize) -> Option<&SchemaDefinition<AnnotatedT>> {
+        let required = self.arguments.len();
+        if index < required {
+            return self.arguments.get(index);
+        }
+
+        if let Some(schema) = self.optional_arguments.get(index - required) {
+            return Some(schema);
+        }
+
+        if self.variadic { self.optional_arguments.last().or_else(|| self.arguments.last()) } else { None }
+    }
 }
 
 impl<AnnotatedT> Subentity<Self> for FunctionSignature<AnnotatedT>
@@ -1,6 +1,8 @@
 use super::{
     super::{super::super::grammar::*, dialect::*},
     group_type::*,
+    node_template::*,
+    node_type::*,
     value_assignment::*,
 };
 
@@ -93,15 +95,75 @@ where
         complete_subentity_map_field!(property, properties, self, group_type, group_type_namespace, true, context);
         complete_subentity_map_field!(attribute, attributes, self, group_type, group_type_namespace, true, context);
 
-        if let Some(group_type) = group_type {
-            validate_entities_types(&self.members, &group_type.members, context)?;
-        }
+        let member_types = group_type.and_then(|group_type| group_type.members.to_namespace(group_type_namespace));
+        self.validate_members(member_types.as_ref(), context)?;
 
         self.completion_state = CompletionState::Complete;
         Ok(())
     }
 }
 
+impl<AnnotatedT> GroupTemplate<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    // Members must be node templates of one of the member types (if specified)
+    fn validate_members(
+        &self,
+        member_types: Option<&Vec<FullName>>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        for member in &self.members {
+            let full_name = member.clone().into();
+
+            let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems);
+            if !source.has_entity(NODE_TEMPLATE, &full_name) {
+                context.problems.give(
+                    UndeclaredError::as_problem("node template", member).with_annotations_from_field(self, "members"),
+                )?;
+                continue;
+            }
+
+            let Some(member_types) = member_types else {
+                continue;
+            };
+
+            let Some(type_name) = context
+                .catalog
+                .completed_entity::<NodeTemplate<AnnotatedT>, _>(
+                    NODE_TEMPLATE,
+                    &full_name,
+                    context.source_id,
+                    &mut context.problems.with_fallback_annotations_from_field(self, "members"),
+                )?
+                .and_then(|(node_template, _source)| node_template.type_name.clone())
+            else {
+                continue;
+            };
+
+            if !member_types.iter().any(|member_type| {
+                context.catalog.is_type_derived_from::<NodeType<AnnotatedT>, AnnotatedT>(
+                    NODE_TYPE,
+                    &type_name,
+                    member_type,
+                    context.source_id,
+                )
+            }) {
+                context.problems.give(
+                    WrongTypeError::as_problem(
+                        format!("member {}", member),
+                        type_name,
+                        member_types.iter().map(|member_type| member_type.to_string()).collect(),
+                    )
+                    .with_annotations_from_field(self, "members"),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 //
 // GroupTemplates
 //
@@ -35,19 +35,23 @@ mod requirement_assignment;
 mod requirement_definition;
 mod schema_definition;
 mod service_template;
+mod substitution_mapping;
 mod trigger_definition;
 mod value_assignment;
+mod workflow_activity_definition;
 mod workflow_definition;
+mod workflow_step_definition;
 
 #[allow(unused_imports)]
 pub use {
-    artifact_assignment::*, artifact_definition::*, artifact_type::*, attribute_definition::*,
-    capability_assignment::*, capability_definition::*, capability_type::*, data_type::*, file::*,
-    function_definition::*, function_signature::*, group_template::*, group_type::*, implementation_definition::*,
-    import::*, interface_assignment::*, interface_definition::*, interface_type::*, node_template::*, node_type::*,
-    notification_assignment::*, notification_definition::*, operation_assignment::*, operation_definition::*,
-    parameter_definition::*, policy_template::*, policy_type::*, property_definition::*, relationship_assignment::*,
-    relationship_definition::*, relationship_template::*, relationship_type::*, repository_definition::*,
-    requirement_assignment::*, requirement_definition::*, schema_definition::*, service_template::*,
-    trigger_definition::*, value_assignment::*, workflow_definition::*,
+    artifact_assignment::*, artifact_definition::*, artifact_type::*, attribute_definition::*, capability_assignment::*,
+    capability_definition::*, capability_type::*, data_type::*, file::*, function_definition::*, function_signature::*,
+    group_template::*, group_type::*, implementation_definition::*, import::*, interface_assignment::*,
+    interface_definition::*, interface_type::*, node_template::*, node_type::*, notification_assignment::*,
+    notification_definition::*, operation_assignment::*, operation_definition::*, parameter_definition::*,
+    policy_template::*, policy_type::*, property_definition::*, relationship_assignment::*, relationship_definition::*,
+    relationship_template::*, relationship_type::*, repository_definition::*, requirement_assignment::*,
+    requirement_definition::*, schema_definition::*, service_template::*, substitution_mapping::*,
+    trigger_definition::*, value_assignment::*, workflow_activity_definition::*, workflow_definition::*,
+    workflow_step_definition::*,
 };
@@ -1,15 +1,16 @@
 use super::{
-    super::{super::super::grammar::*, dialect::*},
+    super::{super::super::grammar::*, data::*, dialect::*},
     artifact_assignment::*,
     capability_assignment::*,
     interface_assignment::*,
     node_type::*,
     requirement_assignment::*,
+    requirement_definition::*,
     value_assignment::*,
 };
 
 use {
-    compris::{annotate::*, depict::*, normal::*, resolve::*},
+    compris::{annotate::*, depict::*, errors::*, normal::*, resolve::*},
     depiction::*,
     kutil::std::immutable::*,
     problemo::*,
@@ -96,7 +97,7 @@ where
     /// existing node if this node template is marked with the "select" directive.
     #[resolve]
     #[depict(option, as(depict))]
-    pub node_filter: Option<Variant<AnnotatedT>>,
+    pub node_filter: Option<Expression<AnnotatedT>>,
 
     /// The optional (symbolic) name of another node template from which to copy all keynames and
     /// values into this node template.
@@ -170,11 +171,84 @@ where
         complete_subentity_map_field!(interface, interfaces, self, node_type, node_type_namespace, true, context);
         complete_subentity_map_field!(artifact, artifacts, self, node_type, node_type_namespace, true, context);
 
+        if let Some(node_type) = &node_type {
+            self.validate_requirement_counts(&node_type.requirements, context)?;
+        }
+
         self.completion_state = CompletionState::Complete;
         Ok(())
     }
 }
 
+impl<AnnotatedT> NodeTemplate<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// True if marked with the "substitute" directive.
+    pub fn is_substitute(&self) -> bool {
+        self.directives.iter().any(|directive| &directive[..] == SUBSTITUTE_DIRECTIVE)
+    }
+
+    /// True if marked with the "select" directive.
+    pub fn is_select(&self) -> bool {
+        self.directives.iter().any(|directive| &directive[..] == SELECT_DIRECTIVE)
+    }
+
+    // The sums of the counts of the requirement assignments must be within the count ranges of
+    // their definitions, both for all assignments and for the non-optional assignments
+    fn validate_requirement_counts(
+        &self,
+        requirement_definitions: &RequirementDefinitions<AnnotatedT>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        for (name, requirement_definition) in requirement_definitions.iter() {
+            let count_range = requirement_definition.count_range();
+
+            let mut count = 0;
+            let mut non_optional_count = 0;
+            for (requirement_name, requirement) in self.requirements.iter() {
+                if requirement_name == name {
+                    count += requirement.count;
+                    if !requirement.optional {
+                        non_optional_count += requirement.count;
+                    }
+                }
+            }
+
+            if !count_range.contains(count) {
+                context.problems.give(
+                    MalformedError::as_problem(
+                        "requirements",
+                        format!("count of {:?} is not within count range {}: {}", name.to_string(), count_range, count),
+                    )
+                    .with_annotations_from_field(self, "requirements"),
+                )?;
+            } else if !count_range.contains(non_optional_count) {
+                context.problems.give(
+                    MalformedError::as_problem(
+                        "requirements",
+                        format!(
+                            "non-optional count of {:?} is not within count range {}: {}",
+                            name.to_string(),
+                            count_range,
+                            non_optional_count
+                        ),
+                    )
+                    .with_annotations_from_field(self, "requirements"),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Substitute directive.
+pub const SUBSTITUTE_DIRECTIVE: &str = "substitute";
+
+/// Select directive.
+pub const SELECT_DIRECTIVE: &str = "select";
+
 //
 // NodeTemplates
 //
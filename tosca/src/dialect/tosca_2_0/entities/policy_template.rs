@@ -1,5 +1,9 @@
 use super::{
     super::{super::super::grammar::*, dialect::*},
+    group_template::*,
+    group_type::*,
+    node_template::*,
+    node_type::*,
     policy_type::*,
     trigger_definition::*,
     value_assignment::*,
@@ -91,17 +95,113 @@ where
             completed_entity_from_full_name_field!(POLICY_TYPE, PolicyType, self, type_name, context);
 
         complete_subentity_map_field!(property, properties, self, policy_type, policy_type_namespace, true, context);
-        complete_subentity_map_field!(trigger, triggers, self, policy_type, policy_type_namespace, true, context);
+        complete_subentity_map_field!(trigger, triggers, self, policy_type, policy_type_namespace, false, context);
 
-        if let Some(policy_type) = policy_type {
-            validate_entities_types(&self.targets, &policy_type.targets, context)?;
-        }
+        let target_types = policy_type.and_then(|policy_type| policy_type.targets.to_namespace(policy_type_namespace));
+        self.validate_targets(target_types.as_ref(), context)?;
 
         self.completion_state = CompletionState::Complete;
         Ok(())
     }
 }
 
+impl<AnnotatedT> PolicyTemplate<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    // Targets must be node templates or groups of one of the target types (if specified)
+    fn validate_targets(
+        &self,
+        target_types: Option<&Vec<FullName>>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        for target in &self.targets {
+            let full_name = target.clone().into();
+
+            let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems);
+            let is_node_template = source.has_entity(NODE_TEMPLATE, &full_name);
+            let is_group_template = !is_node_template && source.has_entity(GROUP_TEMPLATE, &full_name);
+
+            if !is_node_template && !is_group_template {
+                context.problems.give(
+                    UndeclaredError::as_problem("node template or group", target)
+                        .with_annotations_from_field(self, "targets"),
+                )?;
+                continue;
+            }
+
+            let Some(target_types) = target_types else {
+                continue;
+            };
+
+            let problems = &mut context.problems.with_fallback_annotations_from_field(self, "targets");
+
+            let (type_name, is_of_target_type) = if is_node_template {
+                let Some(type_name) = context
+                    .catalog
+                    .completed_entity::<NodeTemplate<AnnotatedT>, _>(
+                        NODE_TEMPLATE,
+                        &full_name,
+                        context.source_id,
+                        problems,
+                    )?
+                    .and_then(|(node_template, _source)| node_template.type_name.clone())
+                else {
+                    continue;
+                };
+
+                let is_of_target_type = target_types.iter().any(|target_type| {
+                    context.catalog.is_type_derived_from::<NodeType<AnnotatedT>, AnnotatedT>(
+                        NODE_TYPE,
+                        &type_name,
+                        target_type,
+                        context.source_id,
+                    )
+                });
+
+                (type_name, is_of_target_type)
+            } else {
+                let Some(type_name) = context
+                    .catalog
+                    .completed_entity::<GroupTemplate<AnnotatedT>, _>(
+                        GROUP_TEMPLATE,
+                        &full_name,
+                        context.source_id,
+                        problems,
+                    )?
+                    .map(|(group_template, _source)| group_template.type_name.clone())
+                else {
+                    continue;
+                };
+
+                let is_of_target_type = target_types.iter().any(|target_type| {
+                    context.catalog.is_type_derived_from::<GroupType<AnnotatedT>, AnnotatedT>(
+                        GROUP_TYPE,
+                        &type_name,
+                        target_type,
+                        context.source_id,
+                    )
+                });
+
+                (type_name, is_of_target_type)
+            };
+
+            if !is_of_target_type {
+                context.problems.give(
+                    WrongTypeError::as_problem(
+                        format!("target {}", target),
+                        type_name,
+                        target_types.iter().map(|target_type| target_type.to_string()).collect(),
+                    )
+                    .with_annotations_from_field(self, "targets"),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 //
 // PolicyTemplates
 //
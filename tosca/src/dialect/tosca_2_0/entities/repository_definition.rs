@@ -46,6 +46,19 @@ where
     completion_state: CompletionState,
 }
 
+impl<AnnotatedT> RepositoryDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// URL of a file in the repository.
+    ///
+    /// Credential placeholders in the repository URL are kept as is. They are expanded with
+    /// [expand_url_placeholders] when the URL is read or compiled.
+    pub fn file_url(&self, file: &str) -> ByteString {
+        join_url(&self.url, file).into()
+    }
+}
+
 impl<AnnotatedT> Entity for RepositoryDefinition<AnnotatedT>
 where
     AnnotatedT: 'static + Annotated + Clone + Default,
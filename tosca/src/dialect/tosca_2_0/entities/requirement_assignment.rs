@@ -1,7 +1,11 @@
-use super::{super::super::super::grammar::*, relationship_assignment::*, requirement_definition::*};
+use super::{
+    super::{super::super::grammar::*, data::*, dialect::*},
+    relationship_assignment::*,
+    requirement_definition::*,
+};
 
 use {
-    compris::{annotate::*, depict::*, normal::*, resolve::*},
+    compris::{annotate::*, depict::*, errors::*, normal::*, resolve::*},
     depiction::*,
     kutil::std::immutable::*,
     problemo::*,
@@ -93,7 +97,7 @@ where
     /// type-compatible target node that can fulfill the requirement at runtime.
     #[resolve]
     #[depict(option, as(depict))]
-    pub node_filter: Option<Variant<AnnotatedT>>,
+    pub node_filter: Option<Expression<AnnotatedT>>,
 
     /// An optional list of directive values to provide processing instructions to
     /// orchestrators and tooling.
@@ -118,6 +122,14 @@ where
     #[depict(skip)]
     pub metadata: Metadata<AnnotatedT>,
 
+    /// Target node, as classified during completion.
+    #[depict(skip)]
+    pub target_node: Option<RequirementAssignmentNode>,
+
+    /// Target capability, as classified during completion.
+    #[depict(skip)]
+    pub target_capability: Option<RequirementAssignmentCapability>,
+
     #[resolve(annotations)]
     #[depict(skip)]
     pub(crate) annotations: StructAnnotations,
@@ -134,16 +146,33 @@ where
         requirement_definition_namespace: Option<&Namespace>,
         context: &mut CompletionContext,
     ) -> Result<(), Problem> {
-        // TODO: validate node (template) adheres to parent's node (type)
-
-        // TODO: validate capability adheres to parent's capability type
-
         if let Some(requirement_definition) = requirement_definition {
+            complete_optional_field_to!(node, self, requirement_definition, || requirement_definition
+                .node
+                .to_namespace(requirement_definition_namespace)
+                .map(|full_name| IndexedFullName::new(full_name, None)));
+            complete_optional_field_to!(capability, self, requirement_definition, || Some(
+                requirement_definition.capability.to_namespace(requirement_definition_namespace)
+            ));
+            complete_namespaced_field!(
+                node_filter,
+                self,
+                requirement_definition,
+                requirement_definition_namespace,
+                context
+            );
             complete_optional_field_to!(relationship, self, requirement_definition, || Some(
                 requirement_definition.relationship.to_namespace(requirement_definition_namespace)
             ));
         }
 
+        // TODO: validate node (template) adheres to parent's node (type)
+
+        // TODO: validate capability adheres to parent's capability type
+
+        self.target_node = self.complete_target_node(context)?;
+        self.target_capability = self.complete_target_capability(context)?;
+
         if let Some(relationship) = &mut self.relationship {
             relationship.complete(
                 name,
@@ -157,16 +186,85 @@ where
     }
 }
 
+impl<AnnotatedT> RequirementAssignment<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    // The node can be the name of a node template (in our source) or the name of a node type
+    fn complete_target_node(
+        &self,
+        context: &mut CompletionContext,
+    ) -> Result<Option<RequirementAssignmentNode>, Problem> {
+        let Some(node) = &self.node else {
+            return Ok(None);
+        };
+
+        let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems, None);
+
+        if source.has_entity(NODE_TEMPLATE, &node.full_name) {
+            return Ok(Some(RequirementAssignmentNode::Template((node.full_name.name.clone(), node.index))));
+        }
+
+        if source.try_lookup(NODE_TYPE, &node.full_name).is_some() {
+            if node.index.is_some() {
+                context.problems.give(
+                    MalformedError::as_problem("node", "index can only be used with a node template")
+                        .with_annotations_from_field(self, "node"),
+                )?;
+            }
+
+            return Ok(Some(RequirementAssignmentNode::TypeName(node.full_name.clone())));
+        }
+
+        context.problems.give(
+            UndeclaredError::as_problem("node template or node type", &node.full_name)
+                .with_annotations_from_field(self, "node"),
+        )?;
+
+        Ok(None)
+    }
+
+    // The capability can be the name of a capability type or the name of a capability in the
+    // target node
+    fn complete_target_capability(
+        &self,
+        context: &mut CompletionContext,
+    ) -> Result<Option<RequirementAssignmentCapability>, Problem> {
+        let Some(capability) = &self.capability else {
+            return Ok(None);
+        };
+
+        let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems, None);
+
+        if source.try_lookup(CAPABILITY_TYPE, capability).is_some() {
+            return Ok(Some(RequirementAssignmentCapability::TypeName(capability.clone())));
+        }
+
+        if capability.namespace.is_empty() {
+            return Ok(Some(RequirementAssignmentCapability::Name(capability.name.clone())));
+        }
+
+        context.problems.give(
+            UndeclaredError::as_problem("capability type", capability).with_annotations_from_field(self, "capability"),
+        )?;
+
+        Ok(None)
+    }
+}
+
 impl<AnnotatedT> ToNamespace<RequirementAssignment<AnnotatedT>> for RequirementDefinition<AnnotatedT>
 where
     AnnotatedT: Annotated + Clone + Default,
 {
     fn to_namespace(&self, namespace: Option<&Namespace>) -> RequirementAssignment<AnnotatedT> {
         RequirementAssignment {
-            node: self.node.clone().map(|full_name| IndexedFullName::new(full_name, None)),
-            capability: Some(self.capability.clone()),
+            node: self.node.to_namespace(namespace).map(|full_name| IndexedFullName::new(full_name, None)),
+            capability: Some(self.capability.to_namespace(namespace)),
             relationship: Some(self.relationship.to_namespace(namespace)),
-            node_filter: self.node_filter.clone(),
+            node_filter: self.node_filter.to_namespace(namespace),
+            // Implicit assignments establish only as many relationships as the definition requires
+            // (e.g. none for the default count range of [ 0, UNBOUNDED ])
+            count: self.count_range().lower,
             description: self.description.clone(),
             metadata: self.metadata.clone(),
             annotations: self.annotations.clone_fields(&["node", "capability", "relationship", "node_filter"]),
@@ -175,13 +273,12 @@ where
     }
 }
 
-// TODO: unused
-
 //
 // RequirementAssignmentCapability
 //
 
 /// Requirement assignment capability.
+#[derive(Clone, Debug)]
 pub enum RequirementAssignmentCapability {
     /// Capability type name.
     TypeName(FullName),
@@ -195,12 +292,13 @@ pub enum RequirementAssignmentCapability {
 //
 
 /// Requirement assignment node.
+#[derive(Clone, Debug)]
 pub enum RequirementAssignmentNode {
     /// Node type name.
     TypeName(FullName),
 
-    /// Node template.
-    Template((Name, usize)),
+    /// Node template name and optional index.
+    Template((Name, Option<usize>)),
 }
 
 //
@@ -1,7 +1,10 @@
-use super::{super::super::super::grammar::*, relationship_definition::*};
+use super::{
+    super::{super::super::grammar::*, data::*},
+    relationship_definition::*,
+};
 
 use {
-    compris::{annotate::*, depict::*, normal::*, resolve::*},
+    compris::{annotate::*, depict::*, errors::*, resolve::*},
     depiction::*,
     kutil::std::immutable::*,
     problemo::*,
@@ -58,14 +61,14 @@ where
     /// type-compatible target node that can fulfill this requirement at runtime.
     #[resolve]
     #[depict(option, as(depict))]
-    pub node_filter: Option<Variant<AnnotatedT>>,
+    pub node_filter: Option<Expression<AnnotatedT>>,
 
     /// The optional minimum required and maximum allowed number of relationships created by the
     /// requirement. If this key is not specified, the implied default of [ 0, UNBOUNDED ] will be
     /// used. Note: the value UNBOUNDED is also supported to represent any positive integer.
     #[resolve]
-    #[depict(as(depict))]
-    pub count_range: Range,
+    #[depict(option, as(depict))]
+    pub count_range: Option<Range>,
 
     #[resolve(annotations)]
     #[depict(skip)]
@@ -94,16 +97,45 @@ where
             complete_optional_field_to!(node, self, requirement_definition, || requirement_definition
                 .node
                 .to_namespace(requirement_definition_namespace));
-            complete_optional_field!(node_filter, self, requirement_definition);
+            complete_namespaced_field!(
+                node_filter,
+                self,
+                requirement_definition,
+                requirement_definition_namespace,
+                context
+            );
             validate_type_name(&self.capability, &requirement_definition.capability, context)?;
-        }
 
-        // TODO: validate that count range is within parent count range?
+            if let Some(count_range) = &self.count_range
+                && let Some(parent_count_range) = &requirement_definition.count_range
+                && !count_range.is_within(parent_count_range)
+            {
+                context.problems.give(
+                    MalformedError::as_problem(
+                        "count_range",
+                        format!("not within parent count range: {}", parent_count_range),
+                    )
+                    .with_annotations_from_field(self, "count_range"),
+                )?;
+            }
+
+            complete_optional_field!(count_range, self, requirement_definition);
+        }
 
         Ok(())
     }
 }
 
+impl<AnnotatedT> RequirementDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// Count range, with the implied default of [ 0, UNBOUNDED ].
+    pub fn count_range(&self) -> Range {
+        self.count_range.clone().unwrap_or_default()
+    }
+}
+
 impl<AnnotatedT> ToNamespace<Self> for RequirementDefinition<AnnotatedT>
 where
     AnnotatedT: Annotated + Clone + Default,
@@ -115,7 +147,7 @@ where
             relationship: self.relationship.to_namespace(namespace),
             node: self.node.to_namespace(namespace),
             capability: self.capability.to_namespace(namespace),
-            node_filter: self.node_filter.clone(),
+            node_filter: self.node_filter.to_namespace(namespace),
             count_range: self.count_range.clone(),
             annotations: self.annotations.clone(),
         }
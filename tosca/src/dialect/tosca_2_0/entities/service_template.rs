@@ -1,10 +1,19 @@
 use super::{
-    super::super::super::grammar::*, group_template::*, node_template::*, parameter_definition::*, policy_template::*,
-    relationship_template::*, value_assignment::*, workflow_definition::*,
+    super::{super::super::grammar::*, dialect::*},
+    data_type::*,
+    group_template::*,
+    node_template::*,
+    node_type::*,
+    parameter_definition::*,
+    policy_template::*,
+    relationship_template::*,
+    substitution_mapping::*,
+    value_assignment::*,
+    workflow_definition::*,
 };
 
 use {
-    compris::{annotate::*, depict::*, normal::*, resolve::*},
+    compris::{annotate::*, depict::*, resolve::*},
     depiction::*,
     kutil::std::immutable::*,
     problemo::*,
@@ -79,12 +88,12 @@ where
     /// templates declared within the service template.
     #[resolve]
     #[depict(option, as(depict))]
-    pub substitution_mappings: Option<Variant<AnnotatedT>>,
+    pub substitution_mappings: Option<SubstitutionMapping<AnnotatedT>>,
 
     /// An optional map of workflow definitions for the service template.
     #[resolve]
     #[depict(iter(kv), as(depict), key_style(string))]
-    pub workflows: WorkflowDefinitions,
+    pub workflows: WorkflowDefinitions<AnnotatedT>,
 
     /// Input assignments.
     #[depict(skip)]
@@ -140,7 +149,169 @@ where
         complete_subentity_map("input", &mut self.input_assignments, Some(&self.inputs), None, false, context)?;
         complete_subentity_map("output", &mut self.output_assignments, Some(&self.outputs), None, false, context)?;
 
+        complete_subentity_map_field!(
+            workflow,
+            workflows,
+            self,
+            Option::<ServiceTemplate<_>>::None,
+            None,
+            false,
+            context
+        );
+
+        self.validate_workflow_activities(context)?;
+
+        complete_subentity_field!(substitution_mappings, self, Option::<ServiceTemplate<_>>::None, None, context);
+
+        self.validate_substitution_mappings(context)?;
+
         self.completion_state = CompletionState::Complete;
         Ok(())
     }
 }
+
+impl<AnnotatedT> ServiceTemplate<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    // Delegate and inline activities must refer to declared workflows and their inputs
+    fn validate_workflow_activities(&self, context: &mut CompletionContext) -> Result<(), Problem> {
+        for workflow in self.workflows.values() {
+            for step in workflow.steps.values() {
+                for activity in step.activities.iter().filter_map(|activity| activity.workflow()) {
+                    match self.workflows.get(&activity.workflow) {
+                        Some(activity_workflow) => {
+                            for name in activity.inputs.keys() {
+                                if !activity_workflow.inputs.contains_key(name) {
+                                    context.problems.give(
+                                        UndeclaredError::as_problem("input", name)
+                                            .with_annotations_from_field(activity, "inputs"),
+                                    )?;
+                                }
+                            }
+                        }
+
+                        None => context.problems.give(
+                            UndeclaredError::as_problem("workflow", &activity.workflow)
+                                .with_annotations_from_field(activity, "workflow"),
+                        )?,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Substitution mappings must refer to declared inputs, outputs, and workflows of compatible
+    // types
+    fn validate_substitution_mappings(&self, context: &mut CompletionContext) -> Result<(), Problem> {
+        let Some(substitution_mappings) = &self.substitution_mappings else {
+            return Ok(());
+        };
+
+        // Undeclared node types have already been reported
+        // Note that the node type's property and attribute types are in its own source's namespace
+        let node_type = context
+            .catalog
+            .entity::<NodeType<AnnotatedT>>(NODE_TYPE, &substitution_mappings.node_type, context.source_id)
+            .ok()
+            .map(|(node_type, source)| (node_type, source.source_id.clone()));
+
+        for (name, mapping) in &substitution_mappings.properties {
+            match self.inputs.get(&mapping.parameter) {
+                Some(input) => {
+                    if let Some((node_type, node_type_source_id)) = &node_type
+                        && let Some(property) = node_type.properties.get(name)
+                    {
+                        self.validate_mapped_parameter_type(
+                            input,
+                            &property.type_name,
+                            node_type_source_id,
+                            &mapping.parameter,
+                            substitution_mappings,
+                            "properties",
+                            context,
+                        )?;
+                    }
+                }
+
+                None => context.problems.give(
+                    UndeclaredError::as_problem("input", &mapping.parameter)
+                        .with_annotations_from_field(substitution_mappings, "properties"),
+                )?,
+            }
+        }
+
+        for (name, mapping) in &substitution_mappings.attributes {
+            match self.outputs.get(&mapping.parameter) {
+                Some(output) => {
+                    if let Some((node_type, node_type_source_id)) = &node_type
+                        && let Some(attribute) = node_type.attributes.get(name)
+                    {
+                        self.validate_mapped_parameter_type(
+                            output,
+                            &attribute.type_name,
+                            node_type_source_id,
+                            &mapping.parameter,
+                            substitution_mappings,
+                            "attributes",
+                            context,
+                        )?;
+                    }
+                }
+
+                None => context.problems.give(
+                    UndeclaredError::as_problem("output", &mapping.parameter)
+                        .with_annotations_from_field(substitution_mappings, "attributes"),
+                )?,
+            }
+        }
+
+        for mapping in substitution_mappings.interfaces.values() {
+            for workflow in mapping.values() {
+                if !self.workflows.contains_key(workflow) {
+                    context.problems.give(
+                        UndeclaredError::as_problem("workflow", workflow)
+                            .with_annotations_from_field(substitution_mappings, "interfaces"),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // The parameter's data type must be the same as or derived from the mapped data type
+    fn validate_mapped_parameter_type(
+        &self,
+        parameter: &ParameterDefinition<AnnotatedT>,
+        data_type_name: &FullName,
+        data_type_source_id: &SourceID,
+        parameter_name: &Name,
+        substitution_mappings: &SubstitutionMapping<AnnotatedT>,
+        field: &'static str,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        if let Some(parameter_type_name) = &parameter.type_name
+            && !context.catalog.is_type_derived_from_in::<DataType<AnnotatedT>, AnnotatedT>(
+                DATA_TYPE,
+                parameter_type_name,
+                context.source_id,
+                data_type_name,
+                data_type_source_id,
+            )
+        {
+            context.problems.give(
+                WrongTypeError::as_problem(
+                    format!("parameter {}", parameter_name),
+                    parameter_type_name.to_string(),
+                    vec![data_type_name.to_string()],
+                )
+                .with_annotations_from_field(substitution_mappings, field),
+            )?;
+        }
+
+        Ok(())
+    }
+}
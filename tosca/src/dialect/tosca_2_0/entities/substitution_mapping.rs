@@ -0,0 +1,384 @@
+use super::{
+    super::{super::super::grammar::*, dialect::*},
+    capability_type::*,
+    node_template::*,
+    node_type::*,
+};
+
+use {
+    compris::{annotate::*, depict::*, errors::*, normal::*, resolve::*},
+    depiction::*,
+    problemo::*,
+    std::{collections::*, fmt, io},
+};
+
+//
+// SubstitutionMapping
+//
+
+/// A substitution mapping allows a given service template to be used as an implementation of
+/// abstract node templates of a specific node type. This allows the consumption of complex
+/// systems using a simplified vision.
+///
+/// (Documentation copied from
+/// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
+#[derive(Clone, Debug, Default, Depict, Resolve)]
+#[depict(tag = tag::source_and_span)]
+#[resolve(annotated_parameter=AnnotatedT)]
+pub struct SubstitutionMapping<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// The mandatory name of the node type being substituted.
+    #[resolve(required)]
+    #[depict(as(depict))]
+    pub node_type: FullName,
+
+    /// The optional filter that further constrains the abstract node templates for which this
+    /// substitution mapping can be used.
+    #[resolve]
+    #[depict(option, as(depict))]
+    pub substitution_filter: Option<Expression<AnnotatedT>>,
+
+    /// The optional map of properties mapping allowing to map properties of the node_type to
+    /// inputs of the service template.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub properties: BTreeMap<Name, ParameterMapping>,
+
+    /// The optional map of attribute mappings allowing to map outputs from the service template to
+    /// attributes of the node_type.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub attributes: BTreeMap<Name, ParameterMapping>,
+
+    /// The optional map of capabilities mapping.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub capabilities: BTreeMap<Name, NodeTemplateMapping>,
+
+    /// The optional map of requirements mapping.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub requirements: BTreeMap<Name, NodeTemplateMapping>,
+
+    /// The optional map of interface mappings allows to map an interface and operations of the
+    /// node type to implementations that could be either workflows or node template interfaces
+    /// operations.
+    ///
+    /// Puccini note: Operations can only be mapped to workflows.
+    #[resolve]
+    #[depict(iter(kv), as(debug), key_as(display), key_style(name))]
+    pub interfaces: BTreeMap<Name, InterfaceMapping>,
+
+    #[resolve(annotations)]
+    #[depict(skip)]
+    pub(crate) annotations: StructAnnotations,
+}
+
+impl<AnnotatedT> Subentity<Self> for SubstitutionMapping<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn complete(
+        &mut self,
+        _name: Option<&Name>,
+        _parent: Option<&Self>,
+        _parent_namespace: Option<&Namespace>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        let (node_type, node_type_namespace) =
+            completed_entity_from_full_name_field!(NODE_TYPE, NodeType, self, node_type, context);
+
+        let Some(node_type) = node_type else {
+            return Ok(());
+        };
+
+        for name in self.properties.keys() {
+            if !node_type.properties.contains_key(name) {
+                context.problems.give(
+                    UndeclaredError::as_problem("property", name).with_annotations_from_field(self, "properties"),
+                )?;
+            }
+        }
+
+        for name in self.attributes.keys() {
+            if !node_type.attributes.contains_key(name) {
+                context.problems.give(
+                    UndeclaredError::as_problem("attribute", name).with_annotations_from_field(self, "attributes"),
+                )?;
+            }
+        }
+
+        for (name, mapping) in &self.capabilities {
+            let Some(capability_definition) = node_type.capabilities.get(name) else {
+                context.problems.give(
+                    UndeclaredError::as_problem("capability", name).with_annotations_from_field(self, "capabilities"),
+                )?;
+                continue;
+            };
+
+            let Some(node_template) = self.mapped_node_template(mapping, "capabilities", context)? else {
+                continue;
+            };
+
+            let Some(capability) = node_template.capabilities.get(&mapping.name) else {
+                context.problems.give(
+                    UndeclaredError::as_problem("capability", mapping)
+                        .with_annotations_from_field(self, "capabilities"),
+                )?;
+                continue;
+            };
+
+            // The mapped capability must be compatible with the substituted node type's capability
+            let expected_type_name = capability_definition.type_name.to_namespace(node_type_namespace);
+            if !context.catalog.is_type_derived_from::<CapabilityType<AnnotatedT>, AnnotatedT>(
+                CAPABILITY_TYPE,
+                &capability.type_name,
+                &expected_type_name,
+                context.source_id,
+            ) {
+                context.problems.give(
+                    WrongTypeError::as_problem(
+                        format!("capability {}", mapping),
+                        capability.type_name.to_string(),
+                        vec![expected_type_name.to_string()],
+                    )
+                    .with_annotations_from_field(self, "capabilities"),
+                )?;
+            }
+        }
+
+        for (name, mapping) in &self.requirements {
+            if !node_type.requirements.contains_name(name) {
+                context.problems.give(
+                    UndeclaredError::as_problem("requirement", name).with_annotations_from_field(self, "requirements"),
+                )?;
+                continue;
+            }
+
+            if let Some(node_template) = self.mapped_node_template(mapping, "requirements", context)?
+                && !node_template.requirements.contains_name(&mapping.name)
+            {
+                context.problems.give(
+                    UndeclaredError::as_problem("requirement", mapping)
+                        .with_annotations_from_field(self, "requirements"),
+                )?;
+            }
+        }
+
+        for (name, mapping) in &self.interfaces {
+            let Some(interface_definition) = node_type.interfaces.get(name) else {
+                context.problems.give(
+                    UndeclaredError::as_problem("interface", name).with_annotations_from_field(self, "interfaces"),
+                )?;
+                continue;
+            };
+
+            for operation_name in mapping.keys() {
+                if !interface_definition.operations.contains_key(operation_name) {
+                    context.problems.give(
+                        UndeclaredError::as_problem("operation", format!("{}.{}", name, operation_name))
+                            .with_annotations_from_field(self, "interfaces"),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> SubstitutionMapping<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    // The mapped node template must be declared in the same service template
+    fn mapped_node_template(
+        &self,
+        mapping: &NodeTemplateMapping,
+        field: &'static str,
+        context: &mut CompletionContext,
+    ) -> Result<Option<NodeTemplate<AnnotatedT>>, Problem> {
+        let full_name = mapping.node_template.clone().into();
+
+        let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems, None);
+        if !source.has_entity(NODE_TEMPLATE, &full_name) {
+            context.problems.give(
+                UndeclaredError::as_problem("node template", &mapping.node_template)
+                    .with_annotations_from_field(self, field),
+            )?;
+            return Ok(None);
+        }
+
+        Ok(context
+            .catalog
+            .completed_entity::<NodeTemplate<AnnotatedT>, _>(
+                NODE_TEMPLATE,
+                &full_name,
+                context.source_id,
+                &mut context.problems.with_fallback_annotations_from_field(self, field),
+            )?
+            .map(|(node_template, _source)| node_template.clone()))
+    }
+}
+
+impl<AnnotatedT> ToNamespace<Self> for SubstitutionMapping<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn to_namespace(&self, _namespace: Option<&Namespace>) -> Self {
+        self.clone()
+    }
+}
+
+//
+// ParameterMapping
+//
+
+/// Maps a property of the substituted node type to an input of the service template, or an
+/// attribute of the substituted node type to an output of the service template.
+///
+/// The notation is a single-item list: `[ <parameter_name> ]`.
+#[derive(Clone, Debug)]
+pub struct ParameterMapping {
+    /// Input or output name.
+    pub parameter: Name,
+}
+
+impl<AnnotatedT> Resolve<ParameterMapping> for Variant<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn resolve_with_problems<ProblemReceiverT>(self, problems: &mut ProblemReceiverT) -> ResolveResult<ParameterMapping>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(mut names) = resolve_names(self, "parameter mapping", 1, problems)? else {
+            return Ok(None);
+        };
+
+        Ok(names.pop().map(|parameter| ParameterMapping { parameter }))
+    }
+}
+
+impl Depict for ParameterMapping {
+    fn depict<WriteT>(&self, writer: &mut WriteT, context: &DepictionContext) -> io::Result<()>
+    where
+        WriteT: io::Write,
+    {
+        self.parameter.depict(writer, context)
+    }
+}
+
+impl fmt::Display for ParameterMapping {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.parameter, formatter)
+    }
+}
+
+//
+// NodeTemplateMapping
+//
+
+/// Maps a capability or requirement of the substituted node type to a capability or requirement
+/// of a node template in the service template.
+///
+/// The notation is a two-item list: `[ <node_template_name>, <capability_or_requirement_name> ]`.
+#[derive(Clone, Debug)]
+pub struct NodeTemplateMapping {
+    /// Node template name.
+    pub node_template: Name,
+
+    /// Capability or requirement name.
+    pub name: Name,
+}
+
+impl<AnnotatedT> Resolve<NodeTemplateMapping> for Variant<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn resolve_with_problems<ProblemReceiverT>(
+        self,
+        problems: &mut ProblemReceiverT,
+    ) -> ResolveResult<NodeTemplateMapping>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let Some(mut names) = resolve_names(self, "node template mapping", 2, problems)? else {
+            return Ok(None);
+        };
+
+        let name = names.pop().expect("second item");
+        let node_template = names.pop().expect("first item");
+        Ok(Some(NodeTemplateMapping { node_template, name }))
+    }
+}
+
+impl Depict for NodeTemplateMapping {
+    fn depict<WriteT>(&self, writer: &mut WriteT, context: &DepictionContext) -> io::Result<()>
+    where
+        WriteT: io::Write,
+    {
+        self.node_template.depict(writer, context)?;
+        context.theme.write_delimiter(writer, '.')?;
+        context.theme.write_name(writer, &self.name.0)
+    }
+}
+
+impl fmt::Display for NodeTemplateMapping {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}.{}", self.node_template, self.name)
+    }
+}
+
+//
+// InterfaceMapping
+//
+
+/// Maps operation names of an interface of the substituted node type to workflow names.
+pub type InterfaceMapping = BTreeMap<Name, Name>;
+
+// Resolve a list of names of a specific length
+fn resolve_names<AnnotatedT, ProblemReceiverT>(
+    variant: Variant<AnnotatedT>,
+    what: &str,
+    length: usize,
+    problems: &mut ProblemReceiverT,
+) -> ResolveResult<Vec<Name>>
+where
+    AnnotatedT: Annotated + Clone + Default,
+    ProblemReceiverT: ProblemReceiver,
+{
+    let Variant::List(list) = &variant else {
+        problems
+            .give(IncompatibleVariantTypeError::as_problem_from(&variant, &["list"]).with_annotations_from(&variant))?;
+        return Ok(None);
+    };
+
+    if list.inner.len() != length {
+        problems.give(
+            MalformedError::as_problem(what, format!("list length is not {}", length)).with_annotations_from(&variant),
+        )?;
+        return Ok(None);
+    }
+
+    let mut names = Vec::with_capacity(length);
+    for item in &list.inner {
+        let Variant::Text(text) = item else {
+            problems.give(MalformedError::as_problem(what, "item is not text").with_annotations_from(item))?;
+            return Ok(None);
+        };
+
+        match text.inner.parse() {
+            Ok(name) => names.push(name),
+
+            Err(error) => {
+                problems.give(MalformedError::as_problem(what, error.to_string()).with_annotations_from(item))?;
+                return Ok(None);
+            }
+        }
+    }
+
+    Ok(Some(names))
+}
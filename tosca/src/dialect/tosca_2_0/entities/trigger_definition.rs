@@ -1,7 +1,10 @@
-use super::super::super::super::grammar::*;
+use super::{
+    super::{super::super::grammar::*, data::*},
+    workflow_activity_definition::*,
+};
 
 use {
-    compris::{annotate::*, depict::*, normal::*, resolve::*},
+    compris::{annotate::*, depict::*, resolve::*},
     depiction::*,
     kutil::std::immutable::*,
     problemo::*,
@@ -37,14 +40,14 @@ where
     /// performed. Note: this is optional since sometimes the event occurrence itself is enough
     /// to trigger the action.
     #[resolve]
-    #[depict(as(depict))]
-    pub condition: Variant<AnnotatedT>,
+    #[depict(option, as(depict))]
+    pub condition: Option<Expression<AnnotatedT>>,
 
     /// The list of sequential activities to be performed when the event is triggered, and the
     /// condition is met (i.e., evaluates to true).
     #[resolve]
     #[depict(iter(item), as(depict))]
-    pub action: Vec<Variant<AnnotatedT>>,
+    pub action: ActivityDefinitions<AnnotatedT>,
 
     #[resolve(annotations)]
     #[depict(skip)]
@@ -53,16 +56,32 @@ where
 
 impl<AnnotatedT> Subentity<Self> for TriggerDefinition<AnnotatedT>
 where
-    AnnotatedT: Annotated + Clone + Default,
+    AnnotatedT: 'static + Annotated + Clone + Default,
 {
     fn complete(
         &mut self,
         _name: Option<&Name>,
-        _parent: Option<&Self>,
-        _parent_namespace: Option<&Namespace>,
-        _context: &mut CompletionContext,
+        parent: Option<&Self>,
+        parent_namespace: Option<&Namespace>,
+        context: &mut CompletionContext,
     ) -> Result<(), Problem> {
-        // TODO
+        if let Some(parent) = parent {
+            complete_optional_field!(description, self, parent);
+            complete_namespaced_field!(condition, self, parent, parent_namespace, context);
+
+            if self.action.is_empty() {
+                self.action = parent.action.to_namespace(parent_namespace);
+                self.annotations.clone_field_from("action", &parent.annotations);
+            }
+        }
+
+        problems_with_fallback_annotations_from_field!(problems, context.problems, self, "action", {
+            let context = context_with_problems!(context, problems);
+            for activity in &mut self.action {
+                activity.complete(None, None, None, context)?;
+            }
+        });
+
         Ok(())
     }
 }
@@ -71,8 +90,14 @@ impl<AnnotatedT> ToNamespace<Self> for TriggerDefinition<AnnotatedT>
 where
     AnnotatedT: Annotated + Clone + Default,
 {
-    fn to_namespace(&self, _namespace: Option<&Namespace>) -> Self {
-        self.clone()
+    fn to_namespace(&self, namespace: Option<&Namespace>) -> Self {
+        Self {
+            description: self.description.clone(),
+            event: self.event.clone(),
+            condition: self.condition.to_namespace(namespace),
+            action: self.action.to_namespace(namespace),
+            annotations: self.annotations.clone(),
+        }
     }
 }
 
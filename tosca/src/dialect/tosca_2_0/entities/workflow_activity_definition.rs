@@ -0,0 +1,331 @@
+use super::{super::super::super::grammar::*, value_assignment::*};
+
+use {
+    compris::{annotate::*, depict::*, errors::*, normal::*, resolve::*},
+    depiction::*,
+    kutil::std::immutable::*,
+    problemo::*,
+};
+
+//
+// ActivityDefinition
+//
+
+/// An activity defines an operation to be performed in a TOSCA workflow step or in an action body
+/// of a policy trigger. Activity definitions can be of the following types:
+///
+/// * Delegate workflow activity definition: Defines the name of the delegate workflow and
+///   optional input assignments. This activity requires the target to be provided by the
+///   orchestrator (no-op node or relationship).
+/// * Set state activity definition: Sets the state of a node.
+/// * Call operation activity definition: Calls an operation defined on a TOSCA interface of a
+///   node, relationship or group. The operation name uses the `<interface_name>.<operation_name>`
+///   notation. Optionally, assignments for the operation inputs can also be provided.
+/// * Inline workflow activity definition: Inlines another workflow defined in the service (allowing
+///   reusability). The definition includes the name of a workflow to be inlined and optional
+///   workflow input assignments.
+///
+/// (Documentation copied from
+/// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
+#[derive(Clone, Debug, Depict)]
+pub enum ActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// Delegate workflow.
+    #[depict(as(depict))]
+    Delegate(WorkflowActivityDefinition<AnnotatedT>),
+
+    /// Set state.
+    #[depict(as(display), style(symbol))]
+    SetState(ByteString),
+
+    /// Call operation.
+    #[depict(as(depict))]
+    CallOperation(CallOperationActivityDefinition<AnnotatedT>),
+
+    /// Inline workflow.
+    #[depict(as(depict))]
+    Inline(WorkflowActivityDefinition<AnnotatedT>),
+}
+
+impl<AnnotatedT> ActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// Activity keyname.
+    pub fn keyname(&self) -> &'static str {
+        match self {
+            Self::Delegate(_) => DELEGATE_ACTIVITY,
+            Self::SetState(_) => SET_STATE_ACTIVITY,
+            Self::CallOperation(_) => CALL_OPERATION_ACTIVITY,
+            Self::Inline(_) => INLINE_ACTIVITY,
+        }
+    }
+
+    /// Workflow activity definition, if this is a delegate or inline activity.
+    pub fn workflow(&self) -> Option<&WorkflowActivityDefinition<AnnotatedT>> {
+        match self {
+            Self::Delegate(workflow) | Self::Inline(workflow) => Some(workflow),
+            _ => None,
+        }
+    }
+}
+
+impl<AnnotatedT> Subentity<Self> for ActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn complete(
+        &mut self,
+        _name: Option<&Name>,
+        _parent: Option<&Self>,
+        _parent_namespace: Option<&Namespace>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        match self {
+            Self::CallOperation(call_operation) => {
+                if call_operation.interface_and_operation().is_none() {
+                    context.problems.give(
+                        MalformedError::as_problem(
+                            CALL_OPERATION_ACTIVITY,
+                            "operation must be in the form \"<interface_name>.<operation_name>\"",
+                        )
+                        .with_annotations_from_field(call_operation, "operation"),
+                    )?;
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> ToNamespace<Self> for ActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn to_namespace(&self, _namespace: Option<&Namespace>) -> Self {
+        self.clone()
+    }
+}
+
+impl<AnnotatedT> Resolve<ActivityDefinition<AnnotatedT>> for Variant<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn resolve_with_problems<ProblemReceiverT>(
+        self,
+        problems: &mut ProblemReceiverT,
+    ) -> ResolveResult<ActivityDefinition<AnnotatedT>>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        // Single-key map
+        let Self::Map(map) = self else {
+            problems
+                .give(IncompatibleVariantTypeError::as_problem_from(&self, &["map"]).with_annotations_from(&self))?;
+            return Ok(None);
+        };
+
+        if map.inner.len() != 1 {
+            problems.give(
+                MalformedError::as_problem("activity", "is not a map with a single key").with_annotations_from(&map),
+            )?;
+            return Ok(None);
+        }
+
+        let (key, value) = map.into_key_value_pair().expect("single-key map");
+
+        let Self::Text(keyname) = &key else {
+            problems
+                .give(IncompatibleVariantTypeError::as_problem_from(&key, &["text"]).with_annotations_from(&key))?;
+            return Ok(None);
+        };
+
+        Ok(match &keyname.inner[..] {
+            DELEGATE_ACTIVITY => {
+                WorkflowActivityDefinition::resolve_short(value, problems)?.map(ActivityDefinition::Delegate)
+            }
+
+            SET_STATE_ACTIVITY => {
+                let state: Option<ByteString> = value.resolve_with_problems(problems)?;
+                state.map(ActivityDefinition::SetState)
+            }
+
+            CALL_OPERATION_ACTIVITY => {
+                CallOperationActivityDefinition::resolve_short(value, problems)?.map(ActivityDefinition::CallOperation)
+            }
+
+            INLINE_ACTIVITY => {
+                WorkflowActivityDefinition::resolve_short(value, problems)?.map(ActivityDefinition::Inline)
+            }
+
+            _ => {
+                problems.give(InvalidKeyError::as_problem(key.clone().into_annotated()).with_annotations_from(&key))?;
+                None
+            }
+        })
+    }
+}
+
+/// Delegate activity keyname.
+pub const DELEGATE_ACTIVITY: &str = "delegate";
+
+/// Set state activity keyname.
+pub const SET_STATE_ACTIVITY: &str = "set_state";
+
+/// Call operation activity keyname.
+pub const CALL_OPERATION_ACTIVITY: &str = "call_operation";
+
+/// Inline activity keyname.
+pub const INLINE_ACTIVITY: &str = "inline";
+
+//
+// WorkflowActivityDefinition
+//
+
+/// Delegate or inline workflow activity definition.
+///
+/// The short notation is just the name of the workflow.
+#[derive(Clone, Debug, Default, Depict, Resolve)]
+#[depict(tag = tag::source_and_span)]
+#[resolve(annotated_parameter=AnnotatedT)]
+pub struct WorkflowActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// The name of the workflow.
+    #[resolve(required)]
+    #[depict(as(display), style(name))]
+    pub workflow: Name,
+
+    /// The optional map of input parameter assignments for the workflow.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub inputs: ValueAssignments<AnnotatedT>,
+
+    #[resolve(annotations)]
+    #[depict(skip)]
+    pub(crate) annotations: StructAnnotations,
+}
+
+impl<AnnotatedT> WorkflowActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn resolve_short<ProblemReceiverT>(
+        variant: Variant<AnnotatedT>,
+        problems: &mut ProblemReceiverT,
+    ) -> ResolveResult<Self>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        Ok(match variant {
+            Variant::Text(_) => {
+                let annotations = variant.annotations().cloned();
+                let workflow: Option<Name> = variant.resolve_with_problems(problems)?;
+                workflow.map(|workflow| {
+                    let mut definition = Self { workflow, ..Default::default() };
+                    if let Some(annotations) = annotations {
+                        definition.annotations.insert("workflow".into(), annotations);
+                    }
+                    definition
+                })
+            }
+
+            Variant::Map(_) => variant.resolve_with_problems(problems)?,
+
+            _ => {
+                problems.give(
+                    IncompatibleVariantTypeError::as_problem_from(&variant, &["text", "map"])
+                        .with_annotations_from(&variant),
+                )?;
+                None
+            }
+        })
+    }
+}
+
+//
+// CallOperationActivityDefinition
+//
+
+/// Call operation activity definition.
+///
+/// The short notation is just the name of the operation.
+#[derive(Clone, Debug, Default, Depict, Resolve)]
+#[depict(tag = tag::source_and_span)]
+#[resolve(annotated_parameter=AnnotatedT)]
+pub struct CallOperationActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// The name of the operation to call, using the `<interface_name>.<operation_name>` notation.
+    #[resolve(required)]
+    #[depict(as(display), style(name))]
+    pub operation: ByteString,
+
+    /// The optional map of input parameter assignments for the called operation. Any provided
+    /// input assignments will override the operation input assignment in the target node template
+    /// for this operation call.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub inputs: ValueAssignments<AnnotatedT>,
+
+    #[resolve(annotations)]
+    #[depict(skip)]
+    pub(crate) annotations: StructAnnotations,
+}
+
+impl<AnnotatedT> CallOperationActivityDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    /// Split the operation into interface name and operation name.
+    pub fn interface_and_operation(&self) -> Option<(Name, Name)> {
+        let (interface, operation) = self.operation.split_once('.')?;
+        Some((interface.parse().ok()?, operation.parse().ok()?))
+    }
+
+    fn resolve_short<ProblemReceiverT>(
+        variant: Variant<AnnotatedT>,
+        problems: &mut ProblemReceiverT,
+    ) -> ResolveResult<Self>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        Ok(match variant {
+            Variant::Text(_) => {
+                let annotations = variant.annotations().cloned();
+                let operation: Option<ByteString> = variant.resolve_with_problems(problems)?;
+                operation.map(|operation| {
+                    let mut definition = Self { operation, ..Default::default() };
+                    if let Some(annotations) = annotations {
+                        definition.annotations.insert("operation".into(), annotations);
+                    }
+                    definition
+                })
+            }
+
+            Variant::Map(_) => variant.resolve_with_problems(problems)?,
+
+            _ => {
+                problems.give(
+                    IncompatibleVariantTypeError::as_problem_from(&variant, &["text", "map"])
+                        .with_annotations_from(&variant),
+                )?;
+                None
+            }
+        })
+    }
+}
+
+//
+// ActivityDefinitions
+//
+
+/// List of [ActivityDefinition].
+pub type ActivityDefinitions<AnnotatedT> = Vec<ActivityDefinition<AnnotatedT>>;
@@ -1,21 +1,146 @@
-use super::super::super::super::grammar::*;
+use super::{
+    super::{super::super::grammar::*, data::*},
+    implementation_definition::*,
+    parameter_definition::*,
+    value_assignment::*,
+    workflow_step_definition::*,
+};
 
-use {compris::resolve::*, depiction::*, std::collections::*};
+use {
+    compris::{annotate::*, depict::*, errors::*, resolve::*},
+    depiction::*,
+    kutil::std::immutable::*,
+    problemo::*,
+    std::collections::*,
+};
 
 //
 // WorkflowDefinition
 //
 
+/// A workflow definition defines an imperative workflow that is associated with a TOSCA service.
+/// A workflow definition can either include the steps that make up the workflow, or it can refer
+/// to an artifact that expresses the workflow using an external workflow language.
+///
 /// (Documentation copied from
 /// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
-///
-/// TODO
 #[derive(Clone, Debug, Default, Depict, Resolve)]
-pub struct WorkflowDefinition {}
+#[depict(tag = tag::source_and_span)]
+#[resolve(annotated_parameter=AnnotatedT)]
+pub struct WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// The optional description for the workflow definition.
+    #[resolve]
+    #[depict(option, style(string))]
+    pub description: Option<ByteString>,
+
+    /// Defines a section used to declare additional metadata information.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_style(string))]
+    pub metadata: Metadata<AnnotatedT>,
+
+    /// The optional map of input parameter definitions.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_style(string))]
+    pub inputs: ParameterDefinitions<AnnotatedT>,
+
+    /// Condition clause that must evaluate to true before the workflow can be processed.
+    #[resolve]
+    #[depict(option, as(depict))]
+    pub precondition: Option<Expression<AnnotatedT>>,
+
+    /// An optional map of valid imperative workflow step definitions.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_style(string))]
+    pub steps: WorkflowStepDefinitions<AnnotatedT>,
+
+    /// The optional definition of an external workflow definition. This keyname is mutually
+    /// exclusive with the steps keyname above.
+    #[resolve(single)]
+    #[depict(option, as(depict))]
+    pub implementation: Option<ImplementationDefinition<AnnotatedT>>,
+
+    /// The optional map of attribute mappings that specify workflow output values and their
+    /// mappings onto attributes of a node or relationship defined in the service.
+    #[resolve]
+    #[depict(iter(kv), as(depict), key_as(display), key_style(name))]
+    pub outputs: ValueAssignments<AnnotatedT>,
+
+    /// Input assignments.
+    #[depict(skip)]
+    pub input_assignments: ValueAssignments<AnnotatedT>,
+
+    #[resolve(annotations)]
+    #[depict(skip)]
+    pub(crate) annotations: StructAnnotations,
+}
+
+impl<AnnotatedT> WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// Steps that are not the target of any other step's `on_success` or `on_failure`.
+    ///
+    /// These are where the workflow starts.
+    pub fn initial_steps(&self) -> Vec<&Name> {
+        let next_steps: BTreeSet<_> =
+            self.steps.values().flat_map(|step| step.next_steps()).map(|(_field, name)| name).collect();
+        self.steps.keys().filter(|name| !next_steps.contains(name)).collect()
+    }
+}
+
+impl<AnnotatedT> Subentity<Self> for WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn complete(
+        &mut self,
+        _name: Option<&Name>,
+        _parent: Option<&Self>,
+        _parent_namespace: Option<&Namespace>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        complete_subentity_map_field!(parameter, inputs, self, Option::<Self>::None, None, false, context);
+        complete_subentity_map_field!(step, steps, self, Option::<Self>::None, None, false, context);
+        complete_subentity_field!(implementation, self, Option::<Self>::None, None, context);
+
+        complete_subentity_map("input", &mut self.input_assignments, Some(&self.inputs), None, false, context)?;
+
+        if self.implementation.is_some() && !self.steps.is_empty() {
+            context.problems.give(
+                MalformedError::as_problem("workflow", "\"steps\" and \"implementation\" are mutually exclusive")
+                    .with_annotations_from_field(self, "implementation"),
+            )?;
+        }
+
+        for step in self.steps.values() {
+            for (field, name) in step.next_steps() {
+                if !self.steps.contains_key(name) {
+                    context
+                        .problems
+                        .give(UndeclaredError::as_problem("step", name).with_annotations_from_field(step, field))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> ToNamespace<Self> for WorkflowDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn to_namespace(&self, _namespace: Option<&Namespace>) -> Self {
+        self.clone()
+    }
+}
 
 //
 // WorkflowDefinitions
 //
 
 /// Map of [WorkflowDefinition].
-pub type WorkflowDefinitions = BTreeMap<Name, WorkflowDefinition>;
+pub type WorkflowDefinitions<AnnotatedT> = BTreeMap<Name, WorkflowDefinition<AnnotatedT>>;
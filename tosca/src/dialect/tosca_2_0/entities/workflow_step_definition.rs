@@ -0,0 +1,217 @@
+use super::{
+    super::{super::super::grammar::*, data::*, dialect::*},
+    node_template::*,
+    workflow_activity_definition::*,
+};
+
+use {
+    compris::{annotate::*, depict::*, errors::*, normal::*, resolve::*},
+    depiction::*,
+    kutil::std::immutable::*,
+    problemo::*,
+    std::collections::*,
+};
+
+//
+// WorkflowStepDefinition
+//
+
+/// A workflow step allows to define one or multiple sequenced activities in a workflow and how
+/// they are connected to other steps in the workflow. They are the building blocks of a
+/// declarative workflow.
+///
+/// (Documentation copied from
+/// [TOSCA specification 2.0](https://docs.oasis-open.org/tosca/TOSCA/v2.0/TOSCA-v2.0.html))
+#[derive(Clone, Debug, Default, Depict, Resolve)]
+#[depict(tag = tag::source_and_span)]
+#[resolve(annotated_parameter=AnnotatedT)]
+pub struct WorkflowStepDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// The target of the step (this can be a node template name, a group name).
+    #[resolve(required)]
+    #[depict(as(display), style(name))]
+    pub target: Name,
+
+    /// The optional name of a requirement of the target in case the step refers to a relationship
+    /// rather than a node or group. Note that this is applicable only if the target is a node.
+    #[resolve]
+    #[depict(option, as(display), style(name))]
+    pub target_relationship: Option<Name>,
+
+    /// The optional filter that must evaluate to true for the step to be performed on the target.
+    #[resolve]
+    #[depict(option, as(depict))]
+    pub filter: Option<Expression<AnnotatedT>>,
+
+    /// The list of sequential activities to be performed in this step.
+    #[resolve(required)]
+    #[depict(iter(item), as(depict))]
+    pub activities: ActivityDefinitions<AnnotatedT>,
+
+    /// The optional list of step names to be performed after this one has been completed with
+    /// success (all activities has been correctly processed).
+    #[resolve]
+    #[depict(iter(item), as(depict))]
+    pub on_success: Vec<Name>,
+
+    /// The optional list of step names to be called after this one in case one of the step
+    /// activity failed.
+    #[resolve]
+    #[depict(iter(item), as(depict))]
+    pub on_failure: Vec<Name>,
+
+    /// The node on which operations should be executed (for TOSCA call_operation activities).
+    /// This element is mandatory only for relationships and groups target. If target is a
+    /// relationship, then operation_host is mandatory and valid_values are SOURCE or TARGET –
+    /// referring to the relationship source or target node. If target is a group, then
+    /// operation_host is optional. If not specified the operation will be triggered on every node
+    /// of the group. If specified the valid_value is a node_type or the name of a node template.
+    #[resolve]
+    #[depict(option, as(display), style(name))]
+    pub operation_host: Option<ByteString>,
+
+    #[resolve(annotations)]
+    #[depict(skip)]
+    pub(crate) annotations: StructAnnotations,
+}
+
+impl<AnnotatedT> WorkflowStepDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    /// All the step names referenced by `on_success` and `on_failure`.
+    pub fn next_steps(&self) -> impl Iterator<Item = (&'static str, &Name)> {
+        self.on_success
+            .iter()
+            .map(|name| ("on_success", name))
+            .chain(self.on_failure.iter().map(|name| ("on_failure", name)))
+    }
+}
+
+impl<AnnotatedT> Subentity<Self> for WorkflowStepDefinition<AnnotatedT>
+where
+    AnnotatedT: 'static + Annotated + Clone + Default,
+{
+    fn complete(
+        &mut self,
+        _name: Option<&Name>,
+        _parent: Option<&Self>,
+        _parent_namespace: Option<&Namespace>,
+        context: &mut CompletionContext,
+    ) -> Result<(), Problem> {
+        let target: FullName = self.target.clone().into();
+        let source = give_unwrap!(context.catalog.source(context.source_id), &mut context.problems);
+        let is_node_template = source.has_entity(NODE_TEMPLATE, &target);
+        let is_group_template = !is_node_template && source.has_entity(GROUP_TEMPLATE, &target);
+
+        if !is_node_template && !is_group_template {
+            context.problems.give(
+                UndeclaredError::as_problem("node template or group", &self.target)
+                    .with_annotations_from_field(self, "target"),
+            )?;
+        }
+
+        let node_template = if is_node_template {
+            context
+                .catalog
+                .completed_entity::<NodeTemplate<AnnotatedT>, _>(
+                    NODE_TEMPLATE,
+                    &target,
+                    context.source_id,
+                    &mut context.problems.with_fallback_annotations_from_field(self, "target"),
+                )?
+                .map(|(node_template, _source)| node_template.clone())
+        } else {
+            None
+        };
+
+        if let Some(target_relationship) = &self.target_relationship {
+            if is_group_template {
+                context.problems.give(
+                    MalformedError::as_problem("target_relationship", "only applicable if the target is a node")
+                        .with_annotations_from_field(self, "target_relationship"),
+                )?;
+            }
+
+            if let Some(node_template) = &node_template
+                && !node_template.requirements.contains_name(target_relationship)
+            {
+                context.problems.give(
+                    UndeclaredError::as_problem("requirement", target_relationship)
+                        .with_annotations_from_field(self, "target_relationship"),
+                )?;
+            }
+
+            match self.operation_host.as_ref().map(|operation_host| &operation_host[..]) {
+                Some(OPERATION_HOST_SOURCE | OPERATION_HOST_TARGET) => {}
+
+                Some(_) => context.problems.give(
+                    MalformedError::as_problem(
+                        "operation_host",
+                        format!("must be {:?} or {:?}", OPERATION_HOST_SOURCE, OPERATION_HOST_TARGET),
+                    )
+                    .with_annotations_from_field(self, "operation_host"),
+                )?,
+
+                None => context.problems.give(
+                    MissingRequiredKeyError::as_problem(Variant::<WithoutAnnotations>::from("operation_host"))
+                        .with_annotations_from(self),
+                )?,
+            }
+        }
+
+        problems_with_fallback_annotations_from_field!(problems, context.problems, self, "activities", {
+            let context = context_with_problems!(context, problems);
+            for activity in &mut self.activities {
+                activity.complete(None, None, None, context)?;
+            }
+        });
+
+        // Operations on relationships and on group members can only be found after instantiation
+        if self.target_relationship.is_none()
+            && let Some(node_template) = &node_template
+        {
+            for activity in &self.activities {
+                if let ActivityDefinition::CallOperation(call_operation) = activity
+                    && let Some((interface_name, operation_name)) = call_operation.interface_and_operation()
+                    && !node_template
+                        .interfaces
+                        .get(&interface_name)
+                        .map(|interface| interface.operations.contains_key(&operation_name))
+                        .unwrap_or_default()
+                {
+                    context.problems.give(
+                        UndeclaredError::as_problem("operation", &call_operation.operation)
+                            .with_annotations_from_field(call_operation, "operation"),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<AnnotatedT> ToNamespace<Self> for WorkflowStepDefinition<AnnotatedT>
+where
+    AnnotatedT: Annotated + Clone + Default,
+{
+    fn to_namespace(&self, _namespace: Option<&Namespace>) -> Self {
+        self.clone()
+    }
+}
+
+/// Operation host for the relationship source node.
+pub const OPERATION_HOST_SOURCE: &str = "SOURCE";
+
+/// Operation host for the relationship target node.
+pub const OPERATION_HOST_TARGET: &str = "TARGET";
+
+//
+// WorkflowStepDefinitions
+//
+
+/// Map of [WorkflowStepDefinition].
+pub type WorkflowStepDefinitions<AnnotatedT> = BTreeMap<Name, WorkflowStepDefinition<AnnotatedT>>;
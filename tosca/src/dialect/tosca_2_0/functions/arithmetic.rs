@@ -0,0 +1,113 @@
+use super::{evaluate::*, utils::*};
+
+use compris::normal::*;
+
+/// The $sum function.
+pub fn sum<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count_min(&arguments, 1)?;
+    let mut arguments = arguments.into_iter();
+
+    Ok(Some(match arguments.next().expect("first argument") {
+        Variant::Integer(integer) => {
+            let mut sum = integer.inner;
+            for argument in arguments {
+                sum = sum.checked_add(cast_i64(&argument, "argument")?).ok_or(OVERFLOW)?;
+            }
+            sum.into()
+        }
+
+        Variant::Float(float) => {
+            let mut sum: f64 = float.inner.into();
+            for argument in arguments {
+                sum += cast_f64(&argument, "argument")?;
+            }
+            sum.into()
+        }
+
+        // Can't be created by TOSCA
+        Variant::UnsignedInteger(_) => return Ok(None),
+
+        argument => return Err(not_of_types("argument", &argument, &["integer", "float", "scalar"])),
+    }))
+}
+
+/// The $difference function.
+pub fn difference<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let left = Number::from_variant(&arguments[0], "first argument")?;
+    let right = Number::from_variant(&arguments[1], "second argument")?;
+    Ok(Some(left.sub(right)?.into()))
+}
+
+/// The $product function.
+pub fn product<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count_min(&arguments, 1)?;
+
+    let mut product = Number::from_variant(&arguments[0], "argument")?;
+    for argument in &arguments[1..] {
+        product = product.mul(Number::from_variant(argument, "argument")?)?;
+    }
+
+    Ok(Some(product.into()))
+}
+
+/// The $quotient function.
+pub fn quotient<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let left = cast_f64(&arguments[0], "first argument")?;
+    let right = cast_f64(&arguments[1], "second argument")?;
+
+    if right == 0. {
+        return Err(DIVISION_BY_ZERO.into());
+    }
+
+    Ok(Some((left / right).into()))
+}
+
+/// The $remainder function.
+pub fn remainder<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let right = cast_i64(&arguments[1], "second argument")?;
+
+    match &arguments[0] {
+        Variant::Integer(left) => {
+            if right == 0 {
+                return Err(DIVISION_BY_ZERO.into());
+            }
+
+            Ok(Some(left.inner.checked_rem(right).ok_or(OVERFLOW)?.into()))
+        }
+
+        left => Err(not_of_types("first argument", left, &["integer", "integer scalar"])),
+    }
+}
+
+/// The $round, $floor, and $ceil functions.
+pub fn round_with<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>, round: fn(f64) -> f64) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 1)?;
+    let argument = round(cast_f64(&arguments[0], "argument")?);
+
+    if !argument.is_finite() || (argument < i64::MIN as f64) || (argument > i64::MAX as f64) {
+        return Err(OVERFLOW.into());
+    }
+
+    Ok(Some((argument as i64).into()))
+}
@@ -0,0 +1,192 @@
+use super::{evaluate::*, utils::*};
+
+use {compris::normal::*, std::cmp::*};
+
+// Logic
+
+/// The $and and $or functions.
+pub fn and_or<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>, or: bool) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count_min(&arguments, 2)?;
+
+    for argument in &arguments {
+        // Short-circuit
+        if cast_bool(argument, "argument")? == or {
+            return Ok(Some(or.into()));
+        }
+    }
+
+    Ok(Some((!or).into()))
+}
+
+/// The $not function.
+pub fn not<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 1)?;
+    Ok(Some((!cast_bool(&arguments[0], "argument")?).into()))
+}
+
+/// The $xor function.
+pub fn xor<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let left = cast_bool(&arguments[0], "first argument")?;
+    let right = cast_bool(&arguments[1], "second argument")?;
+    Ok(Some((left != right).into()))
+}
+
+// Comparison
+
+/// The $equal function.
+pub fn equal<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    assert_same_type(&arguments[0], &arguments[1], "=")?;
+    Ok(Some((arguments[0] == arguments[1]).into()))
+}
+
+/// The $greater_than, $greater_or_equal, $less_than, and $less_or_equal functions.
+pub fn compare<AnnotatedT>(
+    arguments: Vec<Variant<AnnotatedT>>,
+    operator: &str,
+    test: fn(Ordering) -> bool,
+) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    assert_same_type(&arguments[0], &arguments[1], operator)?;
+
+    let ordering = match (&arguments[0], &arguments[1]) {
+        (Variant::Integer(left), Variant::Integer(right)) => left.inner.partial_cmp(&right.inner),
+        (Variant::UnsignedInteger(left), Variant::UnsignedInteger(right)) => left.inner.partial_cmp(&right.inner),
+        (Variant::Text(left), Variant::Text(right)) => left.inner.partial_cmp(&right.inner),
+        (Variant::Float(left), Variant::Float(right)) => {
+            let left: f64 = left.inner.into();
+            let right: f64 = right.inner.into();
+            left.partial_cmp(&right)
+        }
+
+        // Other types are compared by the plugin
+        _ => None,
+    };
+
+    Ok(ordering.map(|ordering| test(ordering).into()))
+}
+
+/// The $valid_values function.
+pub fn valid_values<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let haystack = cast_list(&arguments[1], "second argument")?;
+    Ok(Some(haystack.contains(&arguments[0]).into()))
+}
+
+// Collection
+
+/// The $has_prefix and $has_suffix functions.
+pub fn has_prefix_or_suffix<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>, prefix: bool) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+
+    Ok(Some(
+        match &arguments[0] {
+            Variant::Text(haystack) => {
+                let needle = cast_str(&arguments[1], "second argument")?;
+                if prefix { haystack.inner.starts_with(needle) } else { haystack.inner.ends_with(needle) }
+            }
+
+            Variant::List(haystack) => {
+                let needle = cast_list(&arguments[1], "second argument")?;
+                if prefix { haystack.inner.starts_with(needle) } else { haystack.inner.ends_with(needle) }
+            }
+
+            haystack => return Err(not_of_types("first argument", haystack, &["string", "list"])),
+        }
+        .into(),
+    ))
+}
+
+/// The $contains function.
+pub fn contains<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+
+    Ok(Some(
+        match &arguments[0] {
+            Variant::Text(haystack) => haystack.inner.contains(cast_str(&arguments[1], "second argument")?),
+            Variant::List(haystack) => haystack.inner.contains(&arguments[1]),
+            haystack => return Err(not_of_types("first argument", haystack, &["string", "list"])),
+        }
+        .into(),
+    ))
+}
+
+/// The $has_entry function.
+pub fn has_entry<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+
+    Ok(Some(
+        match &arguments[0] {
+            Variant::List(haystack) => haystack.inner.contains(&arguments[1]),
+            Variant::Map(haystack) => haystack.inner.values().any(|value| *value == arguments[1]),
+            haystack => return Err(not_of_types("first argument", haystack, &["list", "map"])),
+        }
+        .into(),
+    ))
+}
+
+/// The $has_key function.
+pub fn has_key<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let map = cast_map(&arguments[0], "first argument")?;
+    Ok(Some(map.contains_key(&arguments[1]).into()))
+}
+
+/// The $has_all_entries and $has_any_entry functions.
+pub fn has_entries<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>, all: bool) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+
+    let has_entry: Box<dyn Fn(&Variant<AnnotatedT>) -> bool> = match &arguments[0] {
+        Variant::List(list) => Box::new(|entry| list.inner.contains(entry)),
+        Variant::Map(map) => Box::new(|entry| map.inner.values().any(|value| value == entry)),
+        container => return Err(not_of_types("first argument", container, &["list", "map"])),
+    };
+
+    let mut entries = cast_list(&arguments[1], "second argument")?.iter();
+    Ok(Some(if all { entries.all(has_entry) } else { entries.any(has_entry) }.into()))
+}
+
+/// The $has_all_keys and $has_any_key functions.
+pub fn has_keys<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>, all: bool) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 2)?;
+    let map = cast_map(&arguments[0], "first argument")?;
+    let mut keys = cast_list(&arguments[1], "second argument")?.iter();
+    Ok(Some(if all { keys.all(|key| map.contains_key(key)) } else { keys.any(|key| map.contains_key(key)) }.into()))
+}
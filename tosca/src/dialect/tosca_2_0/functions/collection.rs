@@ -0,0 +1,91 @@
+use super::{evaluate::*, utils::*};
+
+use compris::normal::*;
+
+/// The $length function.
+pub fn length<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 1)?;
+
+    let length = match &arguments[0] {
+        Variant::Text(text) => text.inner.chars().count(),
+        Variant::List(list) => list.inner.len(),
+        Variant::Map(map) => map.inner.len(),
+        argument => return Err(not_of_types("argument", argument, &["string", "list", "map"])),
+    };
+
+    Ok(Some(i64::try_from(length).map_err(|_| OVERFLOW)?.into()))
+}
+
+/// The $concat function.
+pub fn concat<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Clone + Default,
+{
+    assert_argument_count_min(&arguments, 1)?;
+
+    Ok(Some(match &arguments[0] {
+        Variant::Text(_) => {
+            let mut concat = String::default();
+            for argument in &arguments {
+                concat.push_str(cast_str(argument, "argument")?);
+            }
+            concat.into()
+        }
+
+        Variant::List(_) => {
+            let mut concat = Vec::default();
+            for argument in &arguments {
+                concat.extend_from_slice(cast_list(argument, "argument")?);
+            }
+            into_list(concat)
+        }
+
+        argument => return Err(not_of_types("argument", argument, &["string", "list"])),
+    }))
+}
+
+/// The $join function.
+pub fn join<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count_range(&arguments, 1, 2)?;
+    let list = cast_list(&arguments[0], "first argument")?;
+
+    let delimiter = match arguments.get(1) {
+        Some(delimiter) => cast_str(delimiter, "second argument")?,
+        None => "",
+    };
+
+    let mut items = Vec::with_capacity(list.len());
+    for item in list {
+        items.push(cast_str(item, "list item")?);
+    }
+
+    Ok(Some(items.join(delimiter).into()))
+}
+
+/// The $token function.
+pub fn token<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    assert_argument_count(&arguments, 3)?;
+    let string = cast_str(&arguments[0], "first argument")?;
+
+    let delimiters = cast_str(&arguments[1], "second argument")?;
+    if delimiters.is_empty() {
+        return Err("second argument empty".into());
+    }
+
+    let index = cast_i64(&arguments[2], "third argument")?;
+
+    let token = usize::try_from(index).ok().and_then(|index| string.split(|c| delimiters.contains(c)).nth(index));
+    match token {
+        Some(token) => Ok(Some(token.to_string().into())),
+        None => Err(format!("token not found: {}", index)),
+    }
+}
@@ -0,0 +1,275 @@
+use super::{arithmetic::*, boolean::*, collection::*, set::*};
+
+use compris::normal::*;
+
+/// Result of [evaluate_native].
+///
+/// [None] means that the arguments are valid but the function cannot evaluate them natively
+/// (e.g. scalars), so the call should be left to the plugin.
+pub type Evaluation<AnnotatedT> = Result<Option<Variant<AnnotatedT>>, String>;
+
+pub(crate) const OVERFLOW: &str = "integer overflow";
+pub(crate) const DIVISION_BY_ZERO: &str = "division by zero";
+
+/// TOSCA built-in functions that can be evaluated natively.
+///
+/// These are pure: their results depend only on their arguments. Note that "matches" is not
+/// included because we do not have a regular expression engine here.
+pub const NATIVE_FUNCTIONS: &[&str] = &[
+    // Arithmetic
+    "sum",
+    "difference",
+    "product",
+    "quotient",
+    "remainder",
+    "round",
+    "floor",
+    "ceil",
+    // Boolean logic
+    "and",
+    "or",
+    "not",
+    "xor",
+    // Boolean comparison
+    "equal",
+    "greater_than",
+    "greater_or_equal",
+    "less_than",
+    "less_or_equal",
+    "valid_values",
+    // Boolean collection
+    "has_suffix",
+    "has_prefix",
+    "contains",
+    "has_entry",
+    "has_key",
+    "has_all_entries",
+    "has_all_keys",
+    "has_any_entry",
+    "has_any_key",
+    // Collection
+    "length",
+    "concat",
+    "join",
+    "token",
+    // Set
+    "union",
+    "intersection",
+];
+
+/// Evaluate a TOSCA built-in function natively, without the Wasm plugin.
+///
+/// The semantics follow those of the implementations in the Puccini plugin, including the
+/// error messages. Only the functions in [NATIVE_FUNCTIONS] are supported. Other functions, as
+/// well as arguments we cannot handle natively, evaluate to [None].
+///
+/// The arguments must already be evaluated.
+pub fn evaluate_native<AnnotatedT>(function: &str, arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Clone + Default,
+{
+    match function {
+        // Arithmetic
+        "sum" => sum(arguments),
+        "difference" => difference(arguments),
+        "product" => product(arguments),
+        "quotient" => quotient(arguments),
+        "remainder" => remainder(arguments),
+        "round" => round_with(arguments, f64::round),
+        "floor" => round_with(arguments, f64::floor),
+        "ceil" => round_with(arguments, f64::ceil),
+
+        // Boolean logic
+        "and" => and_or(arguments, false),
+        "or" => and_or(arguments, true),
+        "not" => not(arguments),
+        "xor" => xor(arguments),
+
+        // Boolean comparison
+        "equal" => equal(arguments),
+        "greater_than" => compare(arguments, ">", |ordering| ordering.is_gt()),
+        "greater_or_equal" => compare(arguments, "≥", |ordering| ordering.is_ge()),
+        "less_than" => compare(arguments, "<", |ordering| ordering.is_lt()),
+        "less_or_equal" => compare(arguments, "≤", |ordering| ordering.is_le()),
+        "valid_values" => valid_values(arguments),
+
+        // Boolean collection
+        "has_suffix" => has_prefix_or_suffix(arguments, false),
+        "has_prefix" => has_prefix_or_suffix(arguments, true),
+        "contains" => contains(arguments),
+        "has_entry" => has_entry(arguments),
+        "has_key" => has_key(arguments),
+        "has_all_entries" => has_entries(arguments, true),
+        "has_all_keys" => has_keys(arguments, true),
+        "has_any_entry" => has_entries(arguments, false),
+        "has_any_key" => has_keys(arguments, false),
+
+        // Collection
+        "length" => length(arguments),
+        "concat" => concat(arguments),
+        "join" => join(arguments),
+        "token" => token(arguments),
+
+        // Set
+        "union" => union(arguments),
+        "intersection" => intersection(arguments),
+
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, compris::annotate::*};
+
+    type Variant = compris::normal::Variant<WithoutAnnotations>;
+
+    fn text(text: &str) -> Variant {
+        text.to_string().into()
+    }
+
+    fn list(items: Vec<Variant>) -> Variant {
+        items.into_iter().collect::<List<_>>().into()
+    }
+
+    fn map(entries: Vec<(Variant, Variant)>) -> Variant {
+        let mut map = Map::default();
+        for (key, value) in entries {
+            map.into_insert(key, value);
+        }
+        map.into()
+    }
+
+    fn evaluate_ok(function: &str, arguments: Vec<Variant>) -> Option<Variant> {
+        evaluate_native(function, arguments).expect("evaluation")
+    }
+
+    fn evaluate_err(function: &str, arguments: Vec<Variant>) -> String {
+        evaluate_native(function, arguments).err().expect("evaluation error")
+    }
+
+    #[test]
+    fn native_functions() {
+        for function in NATIVE_FUNCTIONS {
+            // All of them require at least one argument
+            assert!(evaluate_native(function, Vec::<Variant>::default()).is_err(), "{}", function);
+        }
+    }
+
+    #[test]
+    fn boolean_logic() {
+        assert!(evaluate_ok("and", vec![true.into(), true.into()]) == Some(true.into()));
+        assert!(evaluate_ok("and", vec![true.into(), false.into()]) == Some(false.into()));
+        assert!(evaluate_ok("or", vec![false.into(), true.into()]) == Some(true.into()));
+        assert!(evaluate_ok("or", vec![false.into(), false.into()]) == Some(false.into()));
+        assert!(evaluate_ok("not", vec![false.into()]) == Some(true.into()));
+        assert!(evaluate_ok("xor", vec![true.into(), false.into()]) == Some(true.into()));
+        assert!(evaluate_ok("xor", vec![true.into(), true.into()]) == Some(false.into()));
+
+        // Short-circuit
+        assert!(evaluate_ok("and", vec![false.into(), text("a")]) == Some(false.into()));
+        assert_eq!(evaluate_err("and", vec![true.into(), text("a")]), "argument is string, expected boolean");
+        assert_eq!(evaluate_err("and", vec![true.into()]), "expected at least 2 arguments, got 1");
+    }
+
+    #[test]
+    fn boolean_comparison() {
+        assert!(evaluate_ok("equal", vec![1i64.into(), 1i64.into()]) == Some(true.into()));
+        assert!(evaluate_ok("equal", vec![text("a"), text("b")]) == Some(false.into()));
+        assert_eq!(evaluate_err("equal", vec![1i64.into(), 1.0.into()]), "cannot compare integer = float");
+
+        assert!(evaluate_ok("greater_than", vec![2i64.into(), 1i64.into()]) == Some(true.into()));
+        assert!(evaluate_ok("greater_or_equal", vec![1.5.into(), 1.5.into()]) == Some(true.into()));
+        assert!(evaluate_ok("less_than", vec![text("a"), text("b")]) == Some(true.into()));
+        assert!(evaluate_ok("less_or_equal", vec![2i64.into(), 1i64.into()]) == Some(false.into()));
+        assert_eq!(evaluate_err("less_than", vec![text("a"), 1i64.into()]), "cannot compare string < integer");
+
+        // Left to the plugin
+        assert!(evaluate_ok("less_than", vec![true.into(), false.into()]).is_none());
+
+        assert!(
+            evaluate_ok("valid_values", vec![2i64.into(), list(vec![1i64.into(), 2i64.into()])]) == Some(true.into())
+        );
+        assert!(
+            evaluate_ok("valid_values", vec![3i64.into(), list(vec![1i64.into(), 2i64.into()])]) == Some(false.into())
+        );
+    }
+
+    #[test]
+    fn boolean_collection() {
+        assert!(evaluate_ok("has_prefix", vec![text("hello"), text("he")]) == Some(true.into()));
+        assert!(evaluate_ok("has_suffix", vec![text("hello"), text("he")]) == Some(false.into()));
+        assert!(
+            evaluate_ok("has_suffix", vec![list(vec![1i64.into(), 2i64.into()]), list(vec![2i64.into()])])
+                == Some(true.into())
+        );
+        assert!(evaluate_ok("contains", vec![text("hello"), text("ell")]) == Some(true.into()));
+        assert!(evaluate_ok("contains", vec![list(vec![1i64.into()]), 2i64.into()]) == Some(false.into()));
+        assert_eq!(
+            evaluate_err("contains", vec![1i64.into(), 1i64.into()]),
+            "first argument is integer, expected string or list"
+        );
+
+        let entries = map(vec![(text("a"), 1i64.into()), (text("b"), 2i64.into())]);
+        assert!(evaluate_ok("has_entry", vec![entries.clone(), 2i64.into()]) == Some(true.into()));
+        assert!(evaluate_ok("has_key", vec![entries.clone(), text("a")]) == Some(true.into()));
+        assert!(evaluate_ok("has_key", vec![entries.clone(), text("c")]) == Some(false.into()));
+        assert!(
+            evaluate_ok("has_all_entries", vec![entries.clone(), list(vec![1i64.into(), 3i64.into()])])
+                == Some(false.into())
+        );
+        assert!(
+            evaluate_ok("has_any_entry", vec![entries.clone(), list(vec![1i64.into(), 3i64.into()])])
+                == Some(true.into())
+        );
+        assert!(
+            evaluate_ok("has_all_keys", vec![entries.clone(), list(vec![text("a"), text("b")])]) == Some(true.into())
+        );
+        assert!(evaluate_ok("has_any_key", vec![entries, list(vec![text("c")])]) == Some(false.into()));
+    }
+
+    #[test]
+    fn collection() {
+        assert!(evaluate_ok("length", vec![text("héllo")]) == Some(5i64.into()));
+        assert!(evaluate_ok("length", vec![list(vec![1i64.into(), 2i64.into()])]) == Some(2i64.into()));
+        assert!(evaluate_ok("length", vec![map(vec![(text("a"), 1i64.into())])]) == Some(1i64.into()));
+        assert_eq!(evaluate_err("length", vec![true.into()]), "argument is boolean, expected string or list or map");
+
+        assert!(evaluate_ok("concat", vec![text("a"), text("b"), text("c")]) == Some(text("abc")));
+        assert!(
+            evaluate_ok("concat", vec![list(vec![1i64.into()]), list(vec![2i64.into()])])
+                == Some(list(vec![1i64.into(), 2i64.into()]))
+        );
+        assert_eq!(evaluate_err("concat", vec![text("a"), 1i64.into()]), "argument is integer, expected string");
+
+        assert!(evaluate_ok("join", vec![list(vec![text("a"), text("b")])]) == Some(text("ab")));
+        assert!(evaluate_ok("join", vec![list(vec![text("a"), text("b")]), text(", ")]) == Some(text("a, b")));
+        assert_eq!(evaluate_err("join", vec![list(vec![1i64.into()])]), "list item is integer, expected string");
+
+        assert!(evaluate_ok("token", vec![text("a.b/c"), text("./"), 2i64.into()]) == Some(text("c")));
+        assert_eq!(evaluate_err("token", vec![text("a.b"), text("."), 2i64.into()]), "token not found: 2");
+        assert_eq!(evaluate_err("token", vec![text("a.b"), text(""), 0i64.into()]), "second argument empty");
+    }
+
+    #[test]
+    fn set() {
+        assert!(
+            evaluate_ok("union", vec![list(vec![1i64.into(), 2i64.into()]), list(vec![2i64.into(), 3i64.into()])])
+                == Some(list(vec![1i64.into(), 2i64.into(), 3i64.into()]))
+        );
+        // Like the plugin, an item is added once per list in which it is found
+        assert!(
+            evaluate_ok(
+                "intersection",
+                vec![list(vec![1i64.into(), 2i64.into()]), list(vec![2i64.into(), 3i64.into()])]
+            ) == Some(list(vec![2i64.into(), 2i64.into()]))
+        );
+        assert_eq!(evaluate_err("union", vec![1i64.into()]), "argument is integer, expected list");
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(evaluate_ok("matches", vec![text("a"), text("a")]).is_none());
+        assert!(evaluate_ok("get_input", vec![text("a")]).is_none());
+    }
+}
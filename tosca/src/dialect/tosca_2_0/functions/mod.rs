@@ -0,0 +1,9 @@
+mod arithmetic;
+mod boolean;
+mod collection;
+mod evaluate;
+mod set;
+mod utils;
+
+#[allow(unused_imports)]
+pub use evaluate::*;
@@ -0,0 +1,51 @@
+use super::{evaluate::*, utils::*};
+
+use compris::normal::*;
+
+/// The $union function.
+pub fn union<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Clone + Default,
+{
+    assert_argument_count_min(&arguments, 1)?;
+
+    let mut union = Vec::default();
+    for argument in &arguments {
+        for item in cast_list(argument, "argument")? {
+            if !union.contains(item) {
+                union.push(item.clone());
+            }
+        }
+    }
+
+    Ok(Some(into_list(union)))
+}
+
+/// The $intersection function.
+pub fn intersection<AnnotatedT>(arguments: Vec<Variant<AnnotatedT>>) -> Evaluation<AnnotatedT>
+where
+    AnnotatedT: Clone + Default,
+{
+    assert_argument_count_min(&arguments, 1)?;
+
+    let mut lists = Vec::with_capacity(arguments.len());
+    for argument in &arguments {
+        lists.push(cast_list(argument, "argument")?);
+    }
+
+    let mut intersection = Vec::default();
+    for (index, list) in lists.iter().enumerate() {
+        for item in list.iter() {
+            let in_all_others = lists
+                .iter()
+                .enumerate()
+                .all(|(other_index, other_list)| (index == other_index) || other_list.contains(item));
+
+            if in_all_others {
+                intersection.push(item.clone());
+            }
+        }
+    }
+
+    Ok(Some(into_list(intersection)))
+}
@@ -0,0 +1,182 @@
+use super::evaluate::*;
+
+use {
+    compris::normal::*,
+    std::{collections::*, mem::*},
+};
+
+//
+// Number
+//
+
+/// Integer or float.
+#[derive(Clone, Copy)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn from_variant<AnnotatedT>(variant: &Variant<AnnotatedT>, name: &str) -> Result<Self, String> {
+        match variant {
+            Variant::Integer(integer) => Ok(Self::Integer(integer.inner)),
+            Variant::Float(float) => Ok(Self::Float(float.inner.into())),
+            _ => Err(not_of_types(name, variant, &["integer", "float"])),
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            Self::Integer(integer) => *integer as f64,
+            Self::Float(float) => *float,
+        }
+    }
+
+    pub fn sub(self, other: Self) -> Result<Self, String> {
+        Ok(match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => Self::Integer(left.checked_sub(right).ok_or(OVERFLOW)?),
+            _ => Self::Float(self.as_f64() - other.as_f64()),
+        })
+    }
+
+    pub fn mul(self, other: Self) -> Result<Self, String> {
+        Ok(match (self, other) {
+            (Self::Integer(left), Self::Integer(right)) => Self::Integer(left.checked_mul(right).ok_or(OVERFLOW)?),
+            _ => Self::Float(self.as_f64() * other.as_f64()),
+        })
+    }
+}
+
+impl<AnnotatedT> Into<Variant<AnnotatedT>> for Number
+where
+    AnnotatedT: Default,
+{
+    fn into(self) -> Variant<AnnotatedT> {
+        match self {
+            Self::Integer(integer) => integer.into(),
+            Self::Float(float) => float.into(),
+        }
+    }
+}
+
+pub fn into_list<AnnotatedT>(items: Vec<Variant<AnnotatedT>>) -> Variant<AnnotatedT>
+where
+    AnnotatedT: Default,
+{
+    let list: List<AnnotatedT> = items.into_iter().collect();
+    list.into()
+}
+
+pub fn type_name<AnnotatedT>(variant: &Variant<AnnotatedT>) -> &'static str {
+    match variant {
+        Variant::Null(_) => "null",
+        Variant::Integer(_) => "integer",
+        Variant::UnsignedInteger(_) => "unsigned integer",
+        Variant::Float(_) => "float",
+        Variant::Boolean(_) => "boolean",
+        Variant::Text(_) => "string",
+        Variant::Blob(_) => "bytes",
+        Variant::List(_) => "list",
+        Variant::Map(_) => "map",
+        _ => "unknown",
+    }
+}
+
+pub fn not_of_types<AnnotatedT>(name: &str, variant: &Variant<AnnotatedT>, type_names: &[&str]) -> String {
+    format!("{} is {}, expected {}", name, type_name(variant), type_names.join(" or "))
+}
+
+pub fn assert_same_type<AnnotatedT>(
+    left: &Variant<AnnotatedT>,
+    right: &Variant<AnnotatedT>,
+    operator: &str,
+) -> Result<(), String> {
+    if discriminant(left) == discriminant(right) {
+        Ok(())
+    } else {
+        Err(format!("cannot compare {} {} {}", type_name(left), operator, type_name(right)))
+    }
+}
+
+pub fn assert_argument_count<AnnotatedT>(arguments: &[Variant<AnnotatedT>], count: usize) -> Result<(), String> {
+    assert_argument_count_range(arguments, count, count)
+}
+
+pub fn assert_argument_count_min<AnnotatedT>(arguments: &[Variant<AnnotatedT>], min: usize) -> Result<(), String> {
+    assert_argument_count_range(arguments, min, usize::MAX)
+}
+
+pub fn assert_argument_count_range<AnnotatedT>(
+    arguments: &[Variant<AnnotatedT>],
+    min: usize,
+    max: usize,
+) -> Result<(), String> {
+    let count = arguments.len();
+    if (count < min) || (count > max) {
+        Err(if min == max {
+            format!("expected {} arguments, got {}", min, count)
+        } else if max == usize::MAX {
+            format!("expected at least {} arguments, got {}", min, count)
+        } else {
+            format!("expected {} to {} arguments, got {}", min, max, count)
+        })
+    } else {
+        Ok(())
+    }
+}
+
+pub fn cast_i64<AnnotatedT>(variant: &Variant<AnnotatedT>, name: &str) -> Result<i64, String> {
+    match variant {
+        Variant::Integer(integer) => Ok(integer.inner),
+        Variant::UnsignedInteger(unsigned_integer) => {
+            i64::try_from(unsigned_integer.inner).map_err(|_| OVERFLOW.into())
+        }
+        _ => Err(not_of_types(name, variant, &["integer"])),
+    }
+}
+
+pub fn cast_f64<AnnotatedT>(variant: &Variant<AnnotatedT>, name: &str) -> Result<f64, String> {
+    match variant {
+        Variant::Integer(integer) => Ok(integer.inner as f64),
+        Variant::UnsignedInteger(unsigned_integer) => Ok(unsigned_integer.inner as f64),
+        Variant::Float(float) => Ok(float.inner.into()),
+        _ => Err(not_of_types(name, variant, &["integer", "float"])),
+    }
+}
+
+pub fn cast_bool<AnnotatedT>(variant: &Variant<AnnotatedT>, name: &str) -> Result<bool, String> {
+    match variant {
+        Variant::Boolean(boolean) => Ok(boolean.inner),
+        _ => Err(not_of_types(name, variant, &["boolean"])),
+    }
+}
+
+pub fn cast_str<'variant, AnnotatedT>(
+    variant: &'variant Variant<AnnotatedT>,
+    name: &str,
+) -> Result<&'variant str, String> {
+    match variant {
+        Variant::Text(text) => Ok(&text.inner),
+        _ => Err(not_of_types(name, variant, &["string"])),
+    }
+}
+
+pub fn cast_list<'variant, AnnotatedT>(
+    variant: &'variant Variant<AnnotatedT>,
+    name: &str,
+) -> Result<&'variant Vec<Variant<AnnotatedT>>, String> {
+    match variant {
+        Variant::List(list) => Ok(&list.inner),
+        _ => Err(not_of_types(name, variant, &["list"])),
+    }
+}
+
+pub fn cast_map<'variant, AnnotatedT>(
+    variant: &'variant Variant<AnnotatedT>,
+    name: &str,
+) -> Result<&'variant BTreeMap<Variant<AnnotatedT>, Variant<AnnotatedT>>, String> {
+    match variant {
+        Variant::Map(map) => Ok(&map.inner),
+        _ => Err(not_of_types(name, variant, &["map"])),
+    }
+}
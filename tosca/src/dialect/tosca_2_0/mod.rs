@@ -2,7 +2,8 @@ mod compile;
 mod data;
 mod dialect;
 mod entities;
+mod functions;
 mod schema;
 
 #[allow(unused_imports)]
-pub use {compile::*, data::*, dialect::*, entities::*, schema::*};
+pub use {compile::*, data::*, dialect::*, entities::*, functions::*, schema::*};
@@ -32,11 +32,10 @@ where
     AnnotatedT: Annotated + Clone + Default,
 {
     fn into(self) -> ScalarSchema<AnnotatedT> {
-        ScalarSchema::new(
-            self.scalar_data_kind,
-            self.scalar_units.clone().unwrap_or_default(),
-            self.scalar_canonical_unit.clone(),
-            self.scalar_prefixes.clone().unwrap_or_default(),
-        )
+        // Prefixes are expanded into the units so that the runtime does not have to
+        let units = self.scalar_expanded_units();
+        let canonical_unit = self.scalar_resolved_canonical_unit(&units);
+        let units = units.into_iter().map(|(unit, factor)| (unit, factor.into())).collect();
+        ScalarSchema::new(self.scalar_data_kind, units, canonical_unit, Default::default())
     }
 }
@@ -1,4 +1,4 @@
-use super::super::{dialect::*, source::*};
+use super::super::{dialect::*, profile::*, source::*};
 
 use kutil::std::collections::*;
 
@@ -14,4 +14,7 @@ pub struct Catalog {
 
     /// Sources.
     pub sources: FastHashMap<SourceID, Source>,
+
+    /// Profiles.
+    pub profiles: ProfileRegistry,
 }
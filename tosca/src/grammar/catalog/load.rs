@@ -1,11 +1,12 @@
 use super::{
-    super::{dialect::*, errors::*, source::*},
+    super::{dialect::*, errors::*, profile::*, source::*},
     catalog::*,
 };
 
 use {
     compris::{parse::*, *},
     duplicate::*,
+    kutil::std::immutable::*,
     problemo::*,
     read_url::*,
     std::io::{self, IsTerminal},
@@ -40,6 +41,7 @@ impl Catalog {
         let (variant, url_context) = match source_id {
             SourceID::URL(url) => {
                 tracing::info!(source = source_id.to_string(), "reading");
+                let url = give_unwrap!(expand_url_placeholders(url), problems, new_url_context);
                 let url = give_unwrap!(url_context.url_or_file_path(&url), problems, new_url_context);
                 let mut reader = io::BufReader::new(give_unwrap!(url.open(), problems, new_url_context));
                 let parser = Parser::new(Format::YAML).with_source(source_id.into());
@@ -56,10 +58,27 @@ impl Catalog {
                 )
             }
 
-            SourceID::Profile(_profile) => {
-                // TODO
-                return Ok(new_url_context);
-            }
+            SourceID::Profile(profile) => match self.profiles.resolve(profile, url_context, problems)? {
+                Some(ProfileLocation::Source(profile_source_id)) => {
+                    tracing::info!(source = source_id.to_string(), "loading from {}", profile_source_id);
+                    self.load_profile_alias(source_id, &profile_source_id, url_context, problems)?;
+                    return Ok(new_url_context);
+                }
+
+                Some(ProfileLocation::Embedded(yaml)) => {
+                    tracing::info!(source = source_id.to_string(), "reading embedded");
+                    let parser = Parser::new(Format::YAML).with_source(source_id.into());
+                    (
+                        give_unwrap!(parser.parse_reader(&mut yaml.as_bytes()), problems, new_url_context),
+                        url_context.clone(),
+                    )
+                }
+
+                None => {
+                    problems.give(UnknownProfileError::as_problem(profile.clone()))?;
+                    return Ok(new_url_context);
+                }
+            },
 
             SourceID::ID(id) => {
                 tracing::info!(source = source_id.to_string(), "reading from stdin");
@@ -97,8 +116,16 @@ impl Catalog {
                 tracing::debug!(source = source_id.to_string(), "initializing");
                 dialect.initialize_source(&mut source, variant, problems.as_ref())?;
 
+                // Register declared profile
+                if let Some(profile) = &source.profile {
+                    self.register_declared_profile(profile, source_id);
+                }
+
                 // Load dependencies (recurse)
-                for source_id in source.dependencies.keys() {
+                // (Profiles last, so that they can be declared by the other dependencies)
+                let mut dependency_source_ids: Vec<_> = source.dependencies.keys().cloned().collect();
+                dependency_source_ids.sort_by_key(|source_id| matches!(source_id, SourceID::Profile(_)));
+                for source_id in &dependency_source_ids {
                     self.load_source(source_id, &url_context, problems)?;
                 }
 
@@ -119,4 +146,54 @@ impl Catalog {
             }
         })
     }
+
+    #[duplicate_item(
+      load_profile_alias                       load_source;
+      [load_profile_alias_with_annotations]    [load_source_with_annotations];
+      [load_profile_alias_without_annotations] [load_source_without_annotations];
+    )]
+    // A profile that is located at another source is loaded as an alias: a source with no entities
+    // of its own that depends on that source and merges its namespace
+    fn load_profile_alias<ProblemReceiverT>(
+        &mut self,
+        source_id: &SourceID,
+        profile_source_id: &SourceID,
+        url_context: &UrlContextRef,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<(), Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        if profile_source_id == source_id {
+            problems.give(SourceNotLoadedError::as_problem(source_id.clone()))?;
+            return Ok(());
+        }
+
+        self.load_source(profile_source_id, url_context, problems)?;
+
+        if let Some(profile_source) = self.source(profile_source_id).give_ok(problems)? {
+            let mut source = Source::new(source_id.clone(), profile_source.dialect_id.clone());
+            source.add_dependency(profile_source_id.clone(), Default::default());
+            source.merge_namespace(profile_source, &Default::default(), problems)?;
+            self.add_source(source);
+        }
+
+        Ok(())
+    }
+
+    // A source that declares a profile name can be imported by that name
+    fn register_declared_profile(&mut self, profile: &ByteString, source_id: &SourceID) {
+        let profile_source_id = SourceID::Profile(profile.clone());
+
+        if profile_source_id == *source_id {
+            return;
+        }
+
+        if self.sources.contains_key(&profile_source_id) {
+            tracing::warn!(source = source_id.to_string(), "profile already loaded: {}", profile);
+            return;
+        }
+
+        self.profiles.register_source(profile.clone(), source_id.clone());
+    }
 }
@@ -6,6 +6,7 @@ mod entities;
 mod load;
 mod namespace;
 mod sources;
+mod types;
 
 #[allow(unused_imports)]
 pub use catalog::*;
@@ -0,0 +1,79 @@
+use super::{
+    super::{entity::*, name::*, source::*},
+    catalog::*,
+};
+
+use std::collections::*;
+
+impl Catalog {
+    /// Whether a type is the same as or derived from another type.
+    ///
+    /// Both type names are looked up in the namespace of the source. Note that the ancestor's
+    /// entity must currently be in the catalog, i.e. not removed for its completion phase.
+    pub fn is_type_derived_from<TypeEntityT, AnnotatedT>(
+        &self,
+        entity_kind: EntityKind,
+        type_name: &FullName,
+        ancestor_type_name: &FullName,
+        source_id: &SourceID,
+    ) -> bool
+    where
+        TypeEntityT: 'static + TypeEntity<AnnotatedT>,
+    {
+        self.is_type_derived_from_in::<TypeEntityT, AnnotatedT>(
+            entity_kind,
+            type_name,
+            source_id,
+            ancestor_type_name,
+            source_id,
+        )
+    }
+
+    /// Whether a type is the same as or derived from another type.
+    ///
+    /// Each type name is looked up in the namespace of its own source, so that types can be
+    /// compared across sources.
+    pub fn is_type_derived_from_in<TypeEntityT, AnnotatedT>(
+        &self,
+        entity_kind: EntityKind,
+        type_name: &FullName,
+        source_id: &SourceID,
+        ancestor_type_name: &FullName,
+        ancestor_source_id: &SourceID,
+    ) -> bool
+    where
+        TypeEntityT: 'static + TypeEntity<AnnotatedT>,
+    {
+        let Some(ancestor_source_id) = self.try_lookup(entity_kind, ancestor_type_name, ancestor_source_id) else {
+            return false;
+        };
+
+        let mut type_name = type_name.clone();
+        let mut source_id = source_id.clone();
+        let mut visited = BTreeSet::default();
+
+        loop {
+            let Ok((type_, type_source)) = self.entity::<TypeEntityT>(entity_kind, &type_name, &source_id) else {
+                return false;
+            };
+
+            if (type_source.source_id == *ancestor_source_id) && (type_name.name == ancestor_type_name.name) {
+                return true;
+            }
+
+            // Protect against circular derivation
+            if !visited.insert((type_source.source_id.clone(), type_name.name.clone())) {
+                return false;
+            }
+
+            match type_.descriptor().parent {
+                Some(parent) => {
+                    type_name = parent.clone();
+                    source_id = type_source.source_id.clone();
+                }
+
+                None => return false,
+            }
+        }
+    }
+}
@@ -26,18 +26,9 @@ impl FloriaToscaType for Vec<floria::ID> {
         type_name: &FullName,
         context: &mut CompilationContext,
     ) -> Result<(), Problem> {
-        if type_name.is_empty() {
+        let Some(mut id) = tosca_type_class_id(entity_kind, entity_kind_name, type_name, context)? else {
             return Ok(());
-        }
-
-        let source = give_unwrap!(context.source(), &mut context.problems);
-        let type_name = give_unwrap!(
-            source.canonical_full_name_for(entity_kind, entity_kind_name, type_name),
-            &mut context.problems
-        );
-
-        let name = type_name.to_floria_name(entity_kind_name);
-        let mut id = floria::ID::new_with_name(floria::EntityKind::Class, context.directory.clone(), name)?;
+        };
 
         loop {
             match give_unwrap!(context.store.get_class(&id), &mut context.problems) {
@@ -60,3 +51,34 @@ impl FloriaToscaType for Vec<floria::ID> {
         Ok(())
     }
 }
+
+/// The Floria class ID for a TOSCA type.
+pub fn tosca_type_class_id(
+    entity_kind: EntityKind,
+    entity_kind_name: &str,
+    type_name: &FullName,
+    context: &mut CompilationContext,
+) -> Result<Option<floria::ID>, Problem> {
+    let directory = context.directory.clone();
+    tosca_type_class_id_in(entity_kind, entity_kind_name, type_name, directory, context)
+}
+
+/// The Floria class ID for a TOSCA type in a Floria directory.
+pub fn tosca_type_class_id_in(
+    entity_kind: EntityKind,
+    entity_kind_name: &str,
+    type_name: &FullName,
+    directory: floria::Directory,
+    context: &mut CompilationContext,
+) -> Result<Option<floria::ID>, Problem> {
+    if type_name.is_empty() {
+        return Ok(None);
+    }
+
+    let source = give_unwrap!(context.source(), &mut context.problems);
+    let type_name =
+        give_unwrap!(source.canonical_full_name_for(entity_kind, entity_kind_name, type_name), &mut context.problems);
+
+    let name = type_name.to_floria_name(entity_kind_name);
+    Ok(Some(floria::ID::new_with_name(floria::EntityKind::Class, directory, name)?))
+}
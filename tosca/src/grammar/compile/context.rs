@@ -22,6 +22,16 @@ pub struct CompilationContext<'context> {
 
     /// Problems.
     pub problems: ProblemReceiverRef<'context>,
+
+    /// Floria ID of the inventory vertex.
+    ///
+    /// The TOSCA nodes contained in it can be selected by node templates marked with the "select"
+    /// directive.
+    pub inventory: Option<floria::ID>,
+
+    /// True to evaluate pure built-in function calls with literal arguments at compile time,
+    /// replacing them with their values.
+    pub fold_constants: bool,
 }
 
 impl<'context> CompilationContext<'context> {
@@ -33,7 +43,19 @@ impl<'context> CompilationContext<'context> {
         store: floria::StoreRef,
         problems: ProblemReceiverRef<'context>,
     ) -> Self {
-        Self { source_id, catalog, directory, store, problems }
+        Self { source_id, catalog, directory, store, problems, inventory: None, fold_constants: false }
+    }
+
+    /// With inventory.
+    pub fn with_inventory(mut self, inventory: Option<floria::ID>) -> Self {
+        self.inventory = inventory;
+        self
+    }
+
+    /// With constant folding.
+    pub fn with_fold_constants(mut self, fold_constants: bool) -> Self {
+        self.fold_constants = fold_constants;
+        self
     }
 
     /// With source.
@@ -44,6 +66,8 @@ impl<'context> CompilationContext<'context> {
             directory: self.directory,
             store: self.store.clone(),
             problems: self.problems.clone(),
+            inventory: self.inventory.clone(),
+            fold_constants: self.fold_constants,
         }
     }
 
@@ -9,7 +9,7 @@ use {
 // FloriaToscaMetadata
 //
 
-/// Set Floria metadata for TOSCA.
+/// Floria metadata for TOSCA.
 pub trait FloriaToscaMetadata {
     /// Set TOSCA metadata.
     fn set_tosca_metadata<ValueT>(&mut self, key: &'static str, value: ValueT)
@@ -41,6 +41,11 @@ pub trait FloriaToscaMetadata {
 
     /// Set TOSCA `directives` metadata.
     fn set_tosca_directives(&mut self, directives: &Vec<ByteString>);
+
+    /// Set TOSCA `count` metadata.
+    ///
+    /// Not set if the count is 1.
+    fn set_tosca_count(&mut self, count: u64);
 }
 
 impl FloriaToscaMetadata for floria::Metadata {
@@ -108,4 +113,10 @@ impl FloriaToscaMetadata for floria::Metadata {
             self.set_tosca_metadata("directives", directives);
         }
     }
+
+    fn set_tosca_count(&mut self, count: u64) {
+        if count != 1 {
+            self.set_tosca_metadata("count", count);
+        }
+    }
 }
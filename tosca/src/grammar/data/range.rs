@@ -32,6 +32,16 @@ impl Range {
             RangeUpperBound::Bounded(upper) => (number >= self.lower) && (number <= upper),
         }
     }
+
+    /// True if the range is within the other range.
+    pub fn is_within(&self, other: &Self) -> bool {
+        (self.lower >= other.lower)
+            && match (&self.upper, &other.upper) {
+                (_, RangeUpperBound::Unbounded) => true,
+                (RangeUpperBound::Unbounded, RangeUpperBound::Bounded(_)) => false,
+                (RangeUpperBound::Bounded(upper), RangeUpperBound::Bounded(other_upper)) => upper <= other_upper,
+            }
+    }
 }
 
 impl<AnnotatedT> Resolve<Range> for Variant<AnnotatedT>
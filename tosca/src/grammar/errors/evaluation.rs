@@ -0,0 +1,63 @@
+use {
+    compris::annotate::*,
+    depiction::*,
+    derive_more::*,
+    problemo::*,
+    std::{fmt, io},
+};
+
+//
+// EvaluationError
+//
+
+/// Evaluation error.
+///
+/// A function call could not be evaluated at compile time.
+#[derive(Debug, Error, PartialEq)]
+pub struct EvaluationError {
+    /// Function name.
+    pub function: String,
+
+    /// Reason.
+    pub reason: String,
+}
+
+impl EvaluationError {
+    /// Constructor.
+    pub fn new<FunctionT, ReasonT>(function: FunctionT, reason: ReasonT) -> Self
+    where
+        FunctionT: ToString,
+        ReasonT: ToString,
+    {
+        Self { function: function.to_string(), reason: reason.to_string() }
+    }
+
+    /// Constructor.
+    #[track_caller]
+    pub fn as_problem<FunctionT, ReasonT>(function: FunctionT, reason: ReasonT) -> Problem
+    where
+        FunctionT: ToString,
+        ReasonT: ToString,
+    {
+        Self::new(function, reason)
+            .into_problem()
+            .with(AnnotatedCauseEquality::new::<Self>())
+            .with(ErrorDepiction::new::<Self>())
+    }
+}
+
+impl Depict for EvaluationError {
+    fn depict<WriteT>(&self, writer: &mut WriteT, context: &DepictionContext) -> io::Result<()>
+    where
+        WriteT: io::Write,
+    {
+        let function = format!("{:?}", self.function);
+        write!(writer, "function {} failed: {}", context.theme.name(function), context.theme.error(&self.reason))
+    }
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}: {}", self.function, self.reason)
+    }
+}
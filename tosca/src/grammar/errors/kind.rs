@@ -0,0 +1,48 @@
+use super::{
+    cyclical_derivation::*, evaluation::*, missing_required::*, name_reused::*, override_prohibited::*,
+    source_not_loaded::*, undeclared::*, unknown_profile::*, unknown_type::*, unsupported_dialect::*,
+    unsupported_source::*, wrong_arguments::*, wrong_type::*,
+};
+
+use {compris::errors::*, problemo::*};
+
+/// Error kind used when the problem's error is not one of the known errors.
+pub const UNKNOWN_ERROR_KIND: &str = "Error";
+
+macro_rules! error_kind {
+    ( $problem:ident, $( $error:ident ),* $(,)? ) => {
+        $(
+            if $problem.has_error_type::<$error>() {
+                return stringify!($error);
+            }
+        )*
+    };
+}
+
+/// Kind of a problem's error.
+///
+/// This is the name of the error type, e.g. `UndeclaredError`, for the errors emitted by the TOSCA
+/// grammar and by Compris while reading the sources. Otherwise it is [UNKNOWN_ERROR_KIND].
+pub fn error_kind(problem: &Problem) -> &'static str {
+    error_kind!(
+        problem,
+        CyclicalDerivationError,
+        EvaluationError,
+        MissingRequiredError,
+        NameReusedError,
+        OverrideProhibitedError,
+        SourceNotLoadedError,
+        UndeclaredError,
+        UnknownProfileError,
+        UnknownTypeError,
+        UnsupportedDialectError,
+        UnsupportedSourceError,
+        WrongArgumentsError,
+        WrongTypeError,
+        IncompatibleVariantTypeError,
+        MalformedError,
+        MissingRequiredKeyError,
+    );
+
+    UNKNOWN_ERROR_KIND
+}
@@ -1,4 +1,6 @@
 mod cyclical_derivation;
+mod evaluation;
+mod kind;
 mod missing_required;
 mod name_reused;
 mod override_prohibited;
@@ -6,14 +8,16 @@ mod r#ref;
 mod source_not_loaded;
 mod tosca;
 mod undeclared;
+mod unknown_profile;
 mod unknown_type;
 mod unsupported_dialect;
 mod unsupported_source;
+mod wrong_arguments;
 mod wrong_type;
 
 #[allow(unused_imports)]
 pub use {
-    cyclical_derivation::*, missing_required::*, name_reused::*, override_prohibited::*, r#ref::*,
-    source_not_loaded::*, tosca::*, undeclared::*, unknown_type::*, unsupported_dialect::*, unsupported_source::*,
-    wrong_type::*,
+    cyclical_derivation::*, evaluation::*, kind::*, missing_required::*, name_reused::*, override_prohibited::*,
+    r#ref::*, source_not_loaded::*, tosca::*, undeclared::*, unknown_profile::*, unknown_type::*,
+    unsupported_dialect::*, unsupported_source::*, wrong_arguments::*, wrong_type::*,
 };
@@ -0,0 +1,50 @@
+use {
+    compris::annotate::*,
+    depiction::*,
+    derive_more::*,
+    kutil::std::immutable::*,
+    problemo::*,
+    std::{fmt, io},
+};
+
+//
+// UnknownProfileError
+//
+
+/// Unknown profile error.
+#[derive(Debug, Error, PartialEq)]
+pub struct UnknownProfileError {
+    /// Profile name.
+    pub profile: ByteString,
+}
+
+impl UnknownProfileError {
+    /// Constructor.
+    pub fn new(profile: ByteString) -> Self {
+        Self { profile }
+    }
+
+    /// Constructor.
+    #[track_caller]
+    pub fn as_problem(profile: ByteString) -> Problem {
+        Self::new(profile)
+            .into_problem()
+            .with(AnnotatedCauseEquality::new::<Self>())
+            .with(ErrorDepiction::new::<Self>())
+    }
+}
+
+impl Depict for UnknownProfileError {
+    fn depict<WriteT>(&self, writer: &mut WriteT, context: &DepictionContext) -> io::Result<()>
+    where
+        WriteT: io::Write,
+    {
+        write!(writer, "unknown profile: {}", context.theme.error(&self.profile))
+    }
+}
+
+impl fmt::Display for UnknownProfileError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.profile, formatter)
+    }
+}
@@ -0,0 +1,66 @@
+use {
+    compris::annotate::*,
+    depiction::*,
+    derive_more::*,
+    problemo::*,
+    std::{fmt, io},
+};
+
+//
+// WrongArgumentsError
+//
+
+/// Wrong arguments error.
+///
+/// No signature of the function accepts the number of arguments.
+#[derive(Debug, Error, PartialEq)]
+pub struct WrongArgumentsError {
+    /// Function name.
+    pub function: String,
+
+    /// Number of arguments.
+    pub arguments: usize,
+}
+
+impl WrongArgumentsError {
+    /// Constructor.
+    pub fn new<FunctionT>(function: FunctionT, arguments: usize) -> Self
+    where
+        FunctionT: ToString,
+    {
+        Self { function: function.to_string(), arguments }
+    }
+
+    /// Constructor.
+    #[track_caller]
+    pub fn as_problem<FunctionT>(function: FunctionT, arguments: usize) -> Problem
+    where
+        FunctionT: ToString,
+    {
+        Self::new(function, arguments)
+            .into_problem()
+            .with(AnnotatedCauseEquality::new::<Self>())
+            .with(ErrorDepiction::new::<Self>())
+    }
+}
+
+impl Depict for WrongArgumentsError {
+    fn depict<WriteT>(&self, writer: &mut WriteT, context: &DepictionContext) -> io::Result<()>
+    where
+        WriteT: io::Write,
+    {
+        let function = format!("{:?}", self.function);
+        write!(
+            writer,
+            "no signature of function {} accepts {} arguments",
+            context.theme.name(function),
+            context.theme.error(self.arguments.to_string())
+        )
+    }
+}
+
+impl fmt::Display for WrongArgumentsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}: {} arguments", self.function, self.arguments)
+    }
+}
@@ -6,7 +6,10 @@ mod dialect;
 mod entity;
 mod errors;
 mod name;
+mod profile;
 mod source;
 
 #[allow(unused_imports)]
-pub use {catalog::*, compile::*, complete::*, data::*, dialect::*, entity::*, errors::*, name::*, source::*};
+pub use {
+    catalog::*, compile::*, complete::*, data::*, dialect::*, entity::*, errors::*, name::*, profile::*, source::*,
+};
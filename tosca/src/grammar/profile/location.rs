@@ -0,0 +1,15 @@
+use super::super::source::*;
+
+//
+// ProfileLocation
+//
+
+/// Where a profile's TOSCA file can be found.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProfileLocation {
+    /// A source. It will be loaded if it is not already in the catalog.
+    Source(SourceID),
+
+    /// Embedded TOSCA YAML.
+    Embedded(&'static str),
+}
@@ -0,0 +1,6 @@
+mod location;
+mod registry;
+mod version;
+
+#[allow(unused_imports)]
+pub use {location::*, registry::*, version::*};
@@ -0,0 +1,148 @@
+use super::{super::source::*, location::*, version::*};
+
+use {
+    kutil::std::immutable::*,
+    problemo::{common::*, *},
+    read_url::*,
+    std::{collections::*, env},
+};
+
+/// Environment variable for profile paths.
+///
+/// Uses the platform's path list separator (`:` on Unix, `;` on Windows).
+pub const PROFILE_PATH_ENVIRONMENT_VARIABLE: &str = "PUCCINI_TOSCA_PROFILE_PATH";
+
+/// Profile file name.
+pub const PROFILE_FILE_NAME: &str = "profile.yaml";
+
+//
+// ProfileRegistry
+//
+
+/// Maps profile names to the locations of their TOSCA files.
+///
+/// Profiles that are not registered are looked for in the profile paths, in order. Within a path
+/// a profile named `<name>:<version>` is expected at `<name>/<version>/profile.yaml` and an
+/// unversioned profile at `<name>/profile.yaml`.
+///
+/// A path can be a directory URL or file path. With the `csar` feature enabled it can also be a
+/// CSAR, in which case the profile is looked for inside the archive.
+#[derive(Clone, Debug, Default)]
+pub struct ProfileRegistry {
+    /// Registered profiles.
+    pub profiles: BTreeMap<ByteString, ProfileLocation>,
+
+    /// Profile paths.
+    pub paths: Vec<ByteString>,
+}
+
+impl ProfileRegistry {
+    /// Register a profile.
+    ///
+    /// Returns the previous location if there was one.
+    pub fn register(&mut self, profile: ByteString, location: ProfileLocation) -> Option<ProfileLocation> {
+        tracing::debug!(profile = profile.to_string(), "registering profile: {:?}", location);
+        self.profiles.insert(profile, location)
+    }
+
+    /// Register a profile as a source.
+    pub fn register_source(&mut self, profile: ByteString, source_id: SourceID) -> Option<ProfileLocation> {
+        self.register(profile, ProfileLocation::Source(source_id))
+    }
+
+    /// Register a profile as embedded TOSCA YAML.
+    pub fn register_embedded(&mut self, profile: ByteString, yaml: &'static str) -> Option<ProfileLocation> {
+        self.register(profile, ProfileLocation::Embedded(yaml))
+    }
+
+    /// Add a profile path.
+    pub fn add_path(&mut self, path: ByteString) {
+        self.paths.push(path);
+    }
+
+    /// Add the profile paths in [PROFILE_PATH_ENVIRONMENT_VARIABLE], if it is set.
+    pub fn add_paths_from_environment(&mut self) {
+        if let Some(paths) = env::var_os(PROFILE_PATH_ENVIRONMENT_VARIABLE) {
+            for path in env::split_paths(&paths) {
+                if !path.as_os_str().is_empty() {
+                    self.add_path(path.display().to_string().into());
+                }
+            }
+        }
+    }
+
+    /// Get a registered profile.
+    ///
+    /// If the profile has no version and is not registered as is then the registered profile
+    /// with the same name and the latest version is returned.
+    pub fn get(&self, profile: &str) -> Option<&ProfileLocation> {
+        if let Some(location) = self.profiles.get(profile) {
+            return Some(location);
+        }
+
+        if let (name, None) = split_profile_version(profile) {
+            return self
+                .profiles
+                .iter()
+                .filter_map(|(registered_profile, location)| match split_profile_version(registered_profile) {
+                    (registered_name, Some(version)) if registered_name == name => Some((version, location)),
+                    _ => None,
+                })
+                .max_by(|(version1, _), (version2, _)| compare_profile_versions(version1, version2))
+                .map(|(_version, location)| location);
+        }
+
+        None
+    }
+
+    /// Find a profile in the profile paths.
+    pub fn find<ProblemReceiverT>(
+        &self,
+        profile: &str,
+        url_context: &UrlContextRef,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<UrlRef>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        let relative_path = match split_profile_version(profile) {
+            (name, Some(version)) => format!("{}/{}/{}", name, version, PROFILE_FILE_NAME),
+            (name, None) => format!("{}/{}", name, PROFILE_FILE_NAME),
+        };
+
+        for path in &self.paths {
+            match url_context.url_or_file_path(&join_url(path, &relative_path)) {
+                Ok(url) => return Ok(Some(url)),
+
+                Err(problem) => {
+                    if !problem.has_error_type::<UnreachableError>() {
+                        problems.give(problem)?;
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Resolve a profile to its location.
+    ///
+    /// Registered profiles take precedence over the profile paths.
+    pub fn resolve<ProblemReceiverT>(
+        &self,
+        profile: &str,
+        url_context: &UrlContextRef,
+        problems: &mut ProblemReceiverT,
+    ) -> Result<Option<ProfileLocation>, Problem>
+    where
+        ProblemReceiverT: ProblemReceiver,
+    {
+        if let Some(location) = self.get(profile) {
+            return Ok(Some(location.clone()));
+        }
+
+        Ok(self
+            .find(profile, url_context, problems)?
+            .map(|url| ProfileLocation::Source(SourceID::URL(url.to_string().into()))))
+    }
+}
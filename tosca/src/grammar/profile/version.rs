@@ -0,0 +1,42 @@
+use std::cmp::*;
+
+/// Split a profile name into its name and version.
+///
+/// Profile names conventionally end with `:<version>`, e.g. `org.oasis-open.simple:2.0`. The
+/// version is [None] if there is no such suffix.
+pub fn split_profile_version(profile: &str) -> (&str, Option<&str>) {
+    match profile.rsplit_once(':') {
+        Some((name, version)) if version.starts_with(|c: char| c.is_ascii_digit()) => (name, Some(version)),
+        _ => (profile, None),
+    }
+}
+
+/// Compare profile versions.
+///
+/// Dot-separated segments are compared numerically if they are both numbers, otherwise
+/// lexically.
+pub fn compare_profile_versions(version1: &str, version2: &str) -> Ordering {
+    let mut segments1 = version1.split('.');
+    let mut segments2 = version2.split('.');
+
+    loop {
+        return match (segments1.next(), segments2.next()) {
+            (Some(segment1), Some(segment2)) => {
+                let ordering = match (segment1.parse::<u64>(), segment2.parse::<u64>()) {
+                    (Ok(number1), Ok(number2)) => number1.cmp(&number2),
+                    _ => segment1.cmp(segment2),
+                };
+
+                if ordering == Ordering::Equal {
+                    continue;
+                }
+
+                ordering
+            }
+
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        };
+    }
+}
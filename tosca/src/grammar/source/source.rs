@@ -3,7 +3,7 @@ use super::{
     id::*,
 };
 
-use kutil::std::collections::*;
+use kutil::std::{collections::*, immutable::*};
 
 //
 // Source
@@ -18,6 +18,9 @@ pub struct Source {
     /// Dialect ID.
     pub dialect_id: DialectID,
 
+    /// Profile name declared by the source.
+    pub profile: Option<ByteString>,
+
     /// Dependencies.
     pub dependencies: FastHashMap<SourceID, Namespace>,
 
@@ -37,6 +40,7 @@ impl Source {
         Self {
             source_id,
             dialect_id,
+            profile: None,
             dependencies: Default::default(),
             entities: Default::default(),
             fallback_entities: Default::default(),